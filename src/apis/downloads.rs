@@ -1,6 +1,6 @@
 use bon::bon;
 
-use crate::{models, Debrid, DebridError, Error, Result};
+use crate::{models, models::ids::DownloadId, Debrid, DebridError, Error, Result};
 
 /// RealDebrid downloads API
 pub struct DownloadsApi<'rd>(pub(crate) &'rd Debrid);
@@ -94,11 +94,11 @@ impl<'rd> DownloadsApi<'rd> {
         let response = self.0.get::<_, ()>("/downloads", None).await?;
 
         match response.headers().get("X-Total-Count") {
-            Some(value) => Ok(usize::from_str_radix(
-                value.to_str().map_err(Error::SerializeHeaderValue)?,
-                10,
-            )
-            .map_err(Error::ParseInt)?),
+            Some(value) => Ok(value
+                .to_str()
+                .map_err(Error::SerializeHeaderValue)?
+                .parse::<usize>()
+                .map_err(Error::ParseInt)?),
             None => Err(Error::Debrid(DebridError::InternalError)),
         }
     }
@@ -133,7 +133,8 @@ impl<'rd> DownloadsApi<'rd> {
     pub async fn delete(
         &self,
         /// ID of RealDebrid download to delete
-        id: String,
+        #[builder(into)]
+        id: DownloadId,
     ) -> Result<()> {
         self.0
             .delete::<_, ()>(format!("/downloads/delete/{}", id), None)
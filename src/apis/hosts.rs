@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use bon::bon;
 use regex::Regex;
 
-use crate::{models, Debrid, Error, Result};
+use crate::{hosts::LinkExtractor, models, Debrid, Error, Result};
 
 /// RealDebrid hosts API
 pub struct HostsApi<'rd>(pub(crate) &'rd Debrid);
@@ -117,7 +117,7 @@ impl<'rd> HostsApi<'rd> {
             .map(|s| {
                 let unescaped = s.replace("\\\\", "\\");
                 let trimmed = unescaped.trim_matches('/');
-                Regex::new(&trimmed)
+                Regex::new(trimmed)
             })
             .collect::<std::result::Result<Vec<Regex>, _>>()
             .map_err(Error::Regex)
@@ -162,7 +162,7 @@ impl<'rd> HostsApi<'rd> {
             .map(|s| {
                 let unescaped = s.replace("\\\\", "\\");
                 let trimmed = unescaped.trim_matches('/');
-                Regex::new(&trimmed)
+                Regex::new(trimmed)
             })
             .collect::<std::result::Result<Vec<Regex>, _>>()
             .map_err(Error::Regex)
@@ -201,4 +201,39 @@ impl<'rd> HostsApi<'rd> {
             .await
             .map_err(Error::Reqwest)
     }
+
+    /// Get a link extractor built from the supported links and folders regexes and domains.
+    /// This request does not require authentication.
+    //
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder().build()?;
+    ///
+    ///     let extractor = client.hosts().extractor()
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(extractor) = extractor {
+    ///         for link in extractor.extract("<a href=\"https://example.com/abcdefghijkl\">Mirror</a>") {
+    ///             println!("{} ({:?})", link.url, link.kind);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn extractor(&self) -> Result<LinkExtractor> {
+        let (files, folders, domains) = tokio::try_join!(
+            self.regex().send(),
+            self.regex_folder().send(),
+            self.domains().send(),
+        )?;
+
+        LinkExtractor::new(&files, &folders, domains)
+    }
 }
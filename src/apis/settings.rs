@@ -3,7 +3,7 @@ use reqwest::Body;
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
-use crate::{models, Debrid, Error, Result};
+use crate::{models, models::settings::Setting, Debrid, Error, Result};
 
 /// RealDebrid settings API
 pub struct SettingsApi<'rd>(pub(crate) &'rd Debrid);
@@ -100,6 +100,97 @@ impl<'rd> SettingsApi<'rd> {
         Ok(())
     }
 
+    /// Update a user setting, checked first against the values advertised by `get()`.
+    //
+    /// # Examples
+    /// ```
+    /// use debrid::{models::settings::Setting, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let res = client.settings().set()
+    ///         .setting(Setting::Locale("fr".to_string()))
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Err(Error::InvalidSetting { name, value }) = res {
+    ///         println!("{} is not a valid {}", value, name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn set(
+        &self,
+        /// Setting to update
+        setting: Setting,
+    ) -> Result<()> {
+        self.get().send().await?.validate(&setting)?;
+
+        self.update()
+            .name(setting.name().to_string())
+            .value(setting.value().to_string())
+            .send()
+            .await
+    }
+
+    /// Update the user settings which differ from the desired ones, returning them.
+    ///
+    /// Every desired setting is checked against the values advertised by `get()`
+    /// before any update is sent.
+    //
+    /// # Examples
+    /// ```
+    /// use debrid::{models::settings::Setting, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let updated = client.settings().apply()
+    ///         .desired(vec![
+    ///             Setting::Locale("en".to_string()),
+    ///             Setting::StreamingQuality("original".to_string()),
+    ///             Setting::DownloadProtocol("IPv4".to_string()),
+    ///         ])
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(updated) = updated {
+    ///         for setting in updated {
+    ///             println!("{} set to {}", setting.name(), setting.value());
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn apply(
+        &self,
+        /// Desired settings
+        desired: Vec<Setting>,
+    ) -> Result<Vec<Setting>> {
+        let changes = self.get().send().await?.diff(&desired)?;
+
+        for change in &changes {
+            self.update()
+                .name(change.name().to_string())
+                .value(change.value().to_string())
+                .send()
+                .await?;
+        }
+
+        Ok(changes)
+    }
+
     /// Convert fidelity points.
     //
     /// # Examples
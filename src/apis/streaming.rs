@@ -1,6 +1,11 @@
 use bon::bon;
 
-use crate::{models, Debrid, Error, Result};
+use crate::{
+    models,
+    models::ids::LinkId,
+    streams::{StreamPreference, StreamUrl},
+    Debrid, Error, Result,
+};
 
 /// RealDebrid streaming API
 pub struct StreamingApi<'rd>(pub(crate) &'rd Debrid);
@@ -35,7 +40,8 @@ impl<'rd> StreamingApi<'rd> {
     pub async fn transcode(
         &self,
         /// RealDebrid file ID
-        id: String,
+        #[builder(into)]
+        id: LinkId,
     ) -> Result<models::streaming::Transcode> {
         self.0
             .get::<_, ()>(format!("/streaming/transcode/{}", id), None)
@@ -73,7 +79,8 @@ impl<'rd> StreamingApi<'rd> {
     pub async fn media_info(
         &self,
         /// RealDebrid file ID
-        id: String,
+        #[builder(into)]
+        id: LinkId,
     ) -> Result<models::streaming::MediaInfo> {
         self.0
             .get::<_, ()>(format!("/streaming/mediaInfos/{}", id), None)
@@ -82,4 +89,48 @@ impl<'rd> StreamingApi<'rd> {
             .await
             .map_err(Error::Reqwest)
     }
+
+    /// Get the best stream link of a given file, or `None` if none matches the preferences.
+    //
+    /// # Examples
+    /// ```
+    /// use debrid::{streams::StreamPreference, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let preference = StreamPreference::builder().max_height(1080).build();
+    ///
+    ///     let stream = client.streaming().stream()
+    ///         .id("ABCDEFGHIJKLMNOP".to_string())
+    ///         .preference(&preference)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(Some(stream)) = stream {
+    ///         println!("{}", stream.url);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn stream(
+        &self,
+        /// RealDebrid file ID
+        #[builder(into)]
+        id: LinkId,
+        /// Stream preferences, default: all formats, HLS first, best quality
+        preference: Option<&StreamPreference>,
+    ) -> Result<Option<StreamUrl>> {
+        let transcode = self.transcode().id(id).send().await?;
+
+        Ok(match preference {
+            Some(preference) => preference.select(&transcode),
+            None => StreamPreference::default().select(&transcode),
+        })
+    }
 }
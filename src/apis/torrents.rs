@@ -6,7 +6,11 @@ use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
 use crate::{
-    models::{self, torrents::AddedTorrent},
+    models::{
+        self,
+        ids::{InfoHash, TorrentId},
+        torrents::AddedTorrent,
+    },
     Debrid, DebridError, Error, Result,
 };
 
@@ -106,11 +110,11 @@ impl<'rd> TorrentsApi<'rd> {
         let response = self.0.get::<_, ()>("/torrents", None).await?;
 
         match response.headers().get("X-Total-Count") {
-            Some(value) => Ok(usize::from_str_radix(
-                value.to_str().map_err(Error::SerializeHeaderValue)?,
-                10,
-            )
-            .map_err(Error::ParseInt)?),
+            Some(value) => Ok(value
+                .to_str()
+                .map_err(Error::SerializeHeaderValue)?
+                .parse::<usize>()
+                .map_err(Error::ParseInt)?),
             None => Err(Error::Debrid(DebridError::InternalError)),
         }
     }
@@ -143,7 +147,8 @@ impl<'rd> TorrentsApi<'rd> {
     pub async fn info(
         &self,
         /// RealDebrid torrent ID
-        id: String,
+        #[builder(into)]
+        id: TorrentId,
     ) -> Result<models::torrents::TorrentInfo> {
         self.0
             .get::<_, ()>(format!("/torrents/info/{}", id), None)
@@ -167,9 +172,8 @@ impl<'rd> TorrentsApi<'rd> {
     ///
     ///     let available = client.torrents().instant_availability()
     ///         .hashes(vec![
-    ///             "ABCDEFGHIJKLMNOP".to_string(),
-    ///             "ABCDEFGHIJKLMNOP".to_string(),
-    ///             "ABCDEFGHIJKLMNOP".to_string(),
+    ///             "c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab".parse()?,
+    ///             "dd8255ecdc7ca55fb0bbf81323d87062db1f6d1c".parse()?,
     ///          ])
     ///         .send()
     ///         .await;
@@ -187,15 +191,18 @@ impl<'rd> TorrentsApi<'rd> {
     pub async fn instant_availability(
         &self,
         /// Torrent hashes to check
-        hashes: Vec<String>,
-    ) -> Result<HashMap<String, models::torrents::InstantAvailability>> {
+        hashes: Vec<InfoHash>,
+    ) -> Result<HashMap<InfoHash, models::torrents::InstantAvailability>> {
+        let hashes = hashes
+            .iter()
+            .map(InfoHash::as_str)
+            .collect::<Vec<_>>()
+            .join(",");
+
         self.0
-            .get::<_, ()>(
-                format!("/torrents/instantAvailability/{}", hashes.join(",")),
-                None,
-            )
+            .get::<_, ()>(format!("/torrents/instantAvailability/{}", hashes), None)
             .await?
-            .json::<HashMap<String, models::torrents::InstantAvailability>>()
+            .json::<HashMap<InfoHash, models::torrents::InstantAvailability>>()
             .await
             .map_err(Error::Reqwest)
     }
@@ -398,7 +405,8 @@ impl<'rd> TorrentsApi<'rd> {
     pub async fn select_files(
         &self,
         /// RealDebrid torrent ID
-        id: String,
+        #[builder(into)]
+        id: TorrentId,
         /// Selected file IDs
         files: Vec<String>,
     ) -> Result<()> {
@@ -448,7 +456,8 @@ impl<'rd> TorrentsApi<'rd> {
     pub async fn delete(
         &self,
         /// RealDebrid torrent ID
-        id: String,
+        #[builder(into)]
+        id: TorrentId,
     ) -> Result<()> {
         self.0
             .delete::<_, ()>(format!("/torrents/delete/{}", id), None)
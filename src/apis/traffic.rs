@@ -1,8 +1,13 @@
 use std::collections::HashMap;
 
 use bon::bon;
+use chrono::NaiveDate;
 
-use crate::{models, Debrid, Error, Result};
+use crate::{
+    models,
+    usage::{self, Forecast, Quota, UsageReport, MAX_DETAILS_DAYS},
+    Debrid, Error, Result,
+};
 
 /// RealDebrid traffic API
 pub struct TrafficApi<'rd>(pub(crate) &'rd Debrid);
@@ -90,4 +95,126 @@ impl<'rd> TrafficApi<'rd> {
             .await
             .map_err(Error::Reqwest)
     }
+
+    /// Get the normalized quota of each limited hoster, sorted by host.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     if let Ok(quotas) = client.traffic().quotas().send().await {
+    ///         for quota in quotas {
+    ///             println!("{}: {} {:?} left", quota.host, quota.remaining, quota.unit);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn quotas(&self) -> Result<Vec<Quota>> {
+        let mut quotas = self
+            .get()
+            .send()
+            .await?
+            .iter()
+            .filter_map(|(host, traffic)| Quota::from_traffic(host, traffic))
+            .collect::<Vec<_>>();
+        quotas.sort_by(|a, b| a.host.cmp(&b.host));
+
+        Ok(quotas)
+    }
+
+    /// Estimate when each hoster quota runs out from the traffic details of a period.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     if let Ok(forecasts) = client.traffic().forecast().send().await {
+    ///         for forecast in forecasts {
+    ///             println!("{}: {:?}", forecast.quota.host, forecast.exhausted_on);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn forecast(
+        &self,
+        /// Start of the history, default: a week ago
+        start: Option<String>,
+    ) -> Result<Vec<Forecast>> {
+        let (quotas, details) = tokio::try_join!(
+            self.quotas().send(),
+            self.details().maybe_start(start).send()
+        )?;
+
+        let today = chrono::Utc::now().date_naive();
+        Ok(usage::forecast(&quotas, &details, today))
+    }
+
+    /// Get the traffic details of any date range, bounds included, as a report.
+    ///
+    /// The range is split into periods RealDebrid accepts, fetched one after the other.
+    ///
+    /// # Examples
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use debrid::{usage::Period, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let report = client.traffic().report()
+    ///         .start(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+    ///         .end(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(report) = report {
+    ///         println!("{}", report.to_csv(Period::Month));
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn report(
+        &self,
+        /// First day of the report
+        start: NaiveDate,
+        /// Last day of the report, included
+        end: NaiveDate,
+    ) -> Result<UsageReport> {
+        let mut details = Vec::new();
+
+        for (from, to) in usage::windows(start, end, MAX_DETAILS_DAYS) {
+            details.push(
+                self.details()
+                    .start(from.format(usage::DATE_FORMAT).to_string())
+                    .end(to.format(usage::DATE_FORMAT).to_string())
+                    .send()
+                    .await?,
+            );
+        }
+
+        Ok(UsageReport::new(start, end, details))
+    }
 }
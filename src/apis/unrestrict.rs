@@ -1,9 +1,30 @@
-use crate::{models, Debrid, Error, Result};
+use std::{future::Future, time::Duration};
+
+use crate::{
+    error::DebridError,
+    hosts::{ExtractedLink, LinkKind, Passwords},
+    models::{
+        self,
+        traffic::{Traffic, REMOTE_TRAFFIC},
+    },
+    usage::{Quota, QuotaGuard, QuotaUnit},
+    Debrid, Error, Result,
+};
 use bon::bon;
+use futures::StreamExt;
 use reqwest::Body;
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
+/// Default number of links unrestricted at the same time by [`UnrestrictApi::links`].
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default number of retries of a rate limited request by [`UnrestrictApi::links`].
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a rate limited request, doubled on each retry.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
 /// RealDebrid unrestrict API
 pub struct UnrestrictApi<'rd>(pub(crate) &'rd Debrid);
 
@@ -111,6 +132,333 @@ impl<'rd> UnrestrictApi<'rd> {
             .map_err(Error::Reqwest)
     }
 
+    /// Unrestrict a hoster link, providing a password or using remote traffic only when needed.
+    ///
+    /// The link is first unrestricted as is. If the hoster rejects it with
+    /// [`DebridError::InvalidPassword`], the passwords matching the link are tried in order.
+    /// If the hoster limit or the traffic is exhausted, it is retried with remote traffic
+    /// when `traffic().get()` reports some left, keeping the password that worked.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{hosts::Passwords, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let passwords = Passwords::new().host("example.com", "secret");
+    ///
+    ///     let link = client.unrestrict().resolve()
+    ///         .link("https://example.com/abcdefghijkl".to_string())
+    ///         .passwords(&passwords)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(link) = link {
+    ///         println!("{}", link.download);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn resolve(
+        &self,
+        /// Original hoster link
+        link: String,
+        /// Passwords to try when the hoster asks for one
+        passwords: Option<&Passwords>,
+        /// Retry with remote traffic when the hoster limit or traffic is exhausted, default: true
+        remote_fallback: Option<bool>,
+    ) -> Result<models::unrestrict::Link> {
+        let mut password = None;
+        let mut result = self.link().link(link.clone()).send().await;
+
+        if matches!(result, Err(Error::Debrid(DebridError::InvalidPassword))) {
+            for candidate in passwords.map(|p| p.for_url(&link)).unwrap_or_default() {
+                result = self
+                    .link()
+                    .link(link.clone())
+                    .password(candidate.to_string())
+                    .send()
+                    .await;
+
+                if !matches!(result, Err(Error::Debrid(DebridError::InvalidPassword))) {
+                    password = Some(candidate.to_string());
+                    break;
+                }
+            }
+        }
+
+        let exhausted = matches!(
+            result,
+            Err(Error::Debrid(
+                DebridError::HosterLimitReached | DebridError::TrafficExhausted
+            ))
+        );
+
+        if exhausted && remote_fallback.unwrap_or(true) && self.has_remote_traffic().await {
+            result = self
+                .link()
+                .link(link)
+                .maybe_password(password)
+                .remote(true)
+                .send()
+                .await;
+        }
+
+        result
+    }
+
+    /// Unrestrict a hoster link only if it fits in the hoster's remaining traffic.
+    ///
+    /// The link is checked first, then its size, plus the guard's margin, is compared to
+    /// the traffic left on its hoster, or the remote traffic when `remote` is set.
+    /// Hosters limited in links need one link left. Unknown sizes and unlimited
+    /// hosters are not guarded. Fails with [`Error::QuotaWouldBeExceeded`] without unrestricting.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{usage::QuotaGuard, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let guard = QuotaGuard::builder().build();
+    ///
+    ///     let link = client.unrestrict().guarded()
+    ///         .link("https://example.com/abcdefghijkl".to_string())
+    ///         .guard(&guard)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(link) = link {
+    ///         println!("{}", link.download);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn guarded(
+        &self,
+        /// Original hoster link
+        link: String,
+        /// Password to unlock the file access hoster side
+        password: Option<String>,
+        /// Use remote traffic, dedicated servers and account sharing protections lifted
+        remote: Option<bool>,
+        /// Quota guard holding the cached traffic
+        guard: &QuotaGuard,
+    ) -> Result<models::unrestrict::Link> {
+        let check = self
+            .check()
+            .link(link.clone())
+            .maybe_password(password.clone())
+            .send()
+            .await?;
+
+        if !check.supported {
+            return Err(Error::Debrid(DebridError::UnsupportedHoster));
+        }
+
+        let host = match remote {
+            Some(true) => REMOTE_TRAFFIC,
+            _ => &check.host,
+        };
+
+        let need = match guard.quota(self.0, host).await? {
+            Some(Quota {
+                unit: QuotaUnit::Links,
+                remaining,
+                ..
+            }) => Some((1, 0, remaining)),
+            Some(Quota {
+                unit: QuotaUnit::Bytes,
+                remaining,
+                ..
+            }) if check.filesize > 0 => Some((check.filesize, guard.margin(), remaining)),
+            _ => None,
+        };
+
+        if let Some((needed, margin, left)) = need {
+            if needed.saturating_add(margin) > left {
+                return Err(Error::QuotaWouldBeExceeded {
+                    host: host.to_string(),
+                    needed,
+                    margin,
+                    left,
+                });
+            }
+        }
+
+        let unrestricted = self
+            .link()
+            .link(link)
+            .maybe_password(password)
+            .maybe_remote(remote)
+            .send()
+            .await?;
+
+        if let Some((needed, _, _)) = need {
+            guard.consume(host, needed);
+        }
+
+        Ok(unrestricted)
+    }
+
+    /// Unrestrict many hoster links, a few at a time.
+    ///
+    /// Returns one result per link, in input order, so that a single failing link does
+    /// not fail the batch. Requests rate limited by RealDebrid are retried with an
+    /// exponential backoff. When `check` is set, links are checked first and
+    /// unsupported ones fail with [`DebridError::UnsupportedHoster`] without being unrestricted.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let links = client.unrestrict().links()
+    ///         .links(vec![
+    ///             "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+    ///             "https://www.youtube.com/watch?v=9bZkp7q19f0".to_string(),
+    ///         ])
+    ///         .concurrency(2)
+    ///         .check(true)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     for link in links {
+    ///         match link {
+    ///             Ok(link) => println!("{}", link.download),
+    ///             Err(e) => println!("Failed: {}", e),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn links<I: IntoIterator<Item = String>>(
+        &self,
+        /// Original hoster links
+        links: I,
+        /// Password to unlock the files access hoster side
+        password: Option<String>,
+        /// Use remote traffic, dedicated servers and account sharing protections lifted
+        remote: Option<bool>,
+        /// Max links unrestricted at the same time, default: 4
+        concurrency: Option<usize>,
+        /// Check links first, skipping unsupported ones, default: false
+        check: Option<bool>,
+        /// Max retries of a rate limited request, default: 3
+        retries: Option<u32>,
+    ) -> Vec<Result<models::unrestrict::Link>> {
+        let check = check.unwrap_or(false);
+        let retries = retries.unwrap_or(DEFAULT_RETRIES);
+        let password = &password;
+
+        futures::stream::iter(links)
+            .map(|link| async move {
+                if check {
+                    let checked = retrying(retries, || {
+                        self.check()
+                            .link(link.clone())
+                            .maybe_password(password.clone())
+                            .send()
+                    })
+                    .await?;
+
+                    if !checked.supported {
+                        return Err(Error::Debrid(DebridError::UnsupportedHoster));
+                    }
+                }
+
+                retrying(retries, || {
+                    self.link()
+                        .link(link.clone())
+                        .maybe_password(password.clone())
+                        .maybe_remote(remote)
+                        .send()
+                })
+                .await
+            })
+            .buffered(concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1))
+            .collect()
+            .await
+    }
+
+    /// Unrestrict a link found by a [`LinkExtractor`](crate::hosts::LinkExtractor),
+    /// expanding folders into their files first.
+    ///
+    /// Returns one result per file, in folder order.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     if let Ok(extractor) = client.hosts().extractor().send().await {
+    ///         let text = "https://drive.google.com/drive/folders/123ABC456def789ghi";
+    ///
+    ///         for link in extractor.extract(text) {
+    ///             for file in client.unrestrict().extracted().link(link).send().await {
+    ///                 if let Ok(file) = file {
+    ///                     println!("{}", file.download);
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn extracted(
+        &self,
+        /// Extracted file or folder link
+        link: ExtractedLink,
+        /// Password to unlock the files access hoster side
+        password: Option<String>,
+        /// Use remote traffic, dedicated servers and account sharing protections lifted
+        remote: Option<bool>,
+        /// Max folder links unrestricted at the same time, default: 4
+        concurrency: Option<usize>,
+    ) -> Vec<Result<models::unrestrict::Link>> {
+        let links = match link.kind {
+            LinkKind::File => vec![link.url],
+            LinkKind::Folder => match self.folder().link(link.url).send().await {
+                Ok(links) => links,
+                Err(e) => return vec![Err(e)],
+            },
+        };
+
+        self.links()
+            .links(links)
+            .maybe_password(password)
+            .maybe_remote(remote)
+            .maybe_concurrency(concurrency)
+            .send()
+            .await
+    }
+
     /// Unrestrict a hoster folder link and get individual links, returns an empty array if no links found.
     ///
     /// # Examples
@@ -243,3 +591,36 @@ impl<'rd> UnrestrictApi<'rd> {
             .map_err(Error::Reqwest)
     }
 }
+
+impl UnrestrictApi<'_> {
+    /// Whether or not `traffic().get()` reports remote traffic left, false if it fails.
+    async fn has_remote_traffic(&self) -> bool {
+        self.0.traffic().get().send().await.is_ok_and(|traffic| {
+            traffic
+                .get(REMOTE_TRAFFIC)
+                .and_then(Traffic::left)
+                .is_some_and(|left| left > 0)
+        })
+    }
+}
+
+/// Runs a request, retrying it with an exponential backoff while RealDebrid rate limits it.
+async fn retrying<T, F, Fut>(retries: u32, mut request: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = RETRY_BACKOFF;
+
+    for _ in 0..retries {
+        match request().await {
+            Err(Error::Debrid(DebridError::SlowDown | DebridError::TooManyRequests)) => {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+
+    request().await
+}
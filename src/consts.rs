@@ -1,2 +1,4 @@
 /// Default RealDebrid API base URL
-pub const REAL_DEBRID_BASE_URL: &'static str = "https://api.real-debrid.com/rest/1.0";
\ No newline at end of file
+pub const REAL_DEBRID_BASE_URL: &str = "https://api.real-debrid.com/rest/1.0";
+/// Default JDownloader service exchanging DLC container keys
+pub const DLC_SERVICE_URL: &str = "https://service.jdownloader.org/dlcrypt/service.php";
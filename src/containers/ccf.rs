@@ -0,0 +1,157 @@
+use std::sync::LazyLock;
+
+use aes::{
+    cipher::{block_padding::NoPadding, BlockDecryptMut, KeyIvInit},
+    Aes128, Aes192, Aes256,
+};
+use regex::Regex;
+
+use crate::{Error, Result};
+
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<Url>(.*?)</Url>").expect("valid url regex"));
+static DOWNLOAD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<Download\s[^>]*\bUrl="([^"]*)""#).expect("valid download regex")
+});
+
+/// AES key and IV of a CCF container version, such as the published CCF 3.0 and 5.0 ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CcfKey {
+    /// AES-128, AES-192 or AES-256 key
+    pub key: Vec<u8>,
+    /// CBC initialization vector
+    pub iv: [u8; 16],
+}
+
+/// Decodes a CCF container into its links, trying each key in order.
+///
+/// The file is a CryptLoad XML document encrypted with AES-CBC, its key
+/// depending on the version of the container.
+pub fn decode_ccf(data: &[u8], keys: &[CcfKey]) -> Result<Vec<String>> {
+    if keys.is_empty() {
+        return Err(invalid("no keys"));
+    }
+
+    let xml = keys
+        .iter()
+        .filter_map(|key| decrypt(data, key))
+        .map(|content| String::from_utf8_lossy(&content).into_owned())
+        .find(|xml| xml.contains("<CryptLoad"))
+        .ok_or_else(|| invalid("wrong key"))?;
+
+    let mut links = URL
+        .captures_iter(&xml)
+        .map(|captures| unescape(captures[1].trim()))
+        .filter(|link| !link.is_empty())
+        .collect::<Vec<_>>();
+
+    // Older containers only hold links as attributes
+    if links.is_empty() {
+        links = DOWNLOAD
+            .captures_iter(&xml)
+            .map(|captures| unescape(captures[1].trim()))
+            .filter(|link| !link.is_empty())
+            .collect();
+    }
+
+    Ok(links)
+}
+
+fn decrypt(data: &[u8], key: &CcfKey) -> Option<Vec<u8>> {
+    let mut data = data[..data.len() - data.len() % 16].to_vec();
+
+    let len = match key.key.len() {
+        16 => cbc::Decryptor::<Aes128>::new_from_slices(&key.key, &key.iv)
+            .ok()?
+            .decrypt_padded_mut::<NoPadding>(&mut data)
+            .ok()?
+            .len(),
+        24 => cbc::Decryptor::<Aes192>::new_from_slices(&key.key, &key.iv)
+            .ok()?
+            .decrypt_padded_mut::<NoPadding>(&mut data)
+            .ok()?
+            .len(),
+        32 => cbc::Decryptor::<Aes256>::new_from_slices(&key.key, &key.iv)
+            .ok()?
+            .decrypt_padded_mut::<NoPadding>(&mut data)
+            .ok()?
+            .len(),
+        _ => return None,
+    };
+
+    data.truncate(len);
+    Some(data)
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidContainer(format!("CCF: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use aes::cipher::BlockEncryptMut;
+
+    use super::*;
+
+    fn encrypt(xml: &str, key: &CcfKey) -> Vec<u8> {
+        let mut data = xml.as_bytes().to_vec();
+        data.resize(data.len().div_ceil(16) * 16, 0);
+        let len = data.len();
+
+        cbc::Encryptor::<Aes256>::new_from_slices(&key.key, &key.iv)
+            .unwrap()
+            .encrypt_padded_mut::<NoPadding>(&mut data, len)
+            .unwrap();
+
+        data
+    }
+
+    fn key(byte: u8) -> CcfKey {
+        CcfKey {
+            key: vec![byte; 32],
+            iv: [byte; 16],
+        }
+    }
+
+    #[test]
+    fn test_decode_ccf_with_matching_key() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?><CryptLoad><Package name="a">
+            <Download Url="https://example.com/a?x=1&amp;y=2"><Url>https://example.com/a?x=1&amp;y=2</Url></Download>
+            <Download Url="https://example.com/b"><Url>https://example.com/b</Url></Download>
+            </Package></CryptLoad>"#;
+        let data = encrypt(xml, &key(7));
+
+        assert_eq!(
+            decode_ccf(&data, &[key(1), key(7)]).unwrap(),
+            vec!["https://example.com/a?x=1&y=2", "https://example.com/b"]
+        );
+        assert!(matches!(
+            decode_ccf(&data, &[key(1)]),
+            Err(Error::InvalidContainer(_))
+        ));
+        assert!(matches!(
+            decode_ccf(&data, &[]),
+            Err(Error::InvalidContainer(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_ccf_download_attributes() {
+        let xml =
+            r#"<CryptLoad><Package><Download Url="https://example.com/c"/></Package></CryptLoad>"#;
+
+        assert_eq!(
+            decode_ccf(&encrypt(xml, &key(3)), &[key(3)]).unwrap(),
+            vec!["https://example.com/c"]
+        );
+    }
+}
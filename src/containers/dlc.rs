@@ -0,0 +1,129 @@
+use std::sync::LazyLock;
+
+use aes::{
+    cipher::{block_padding::NoPadding, BlockDecryptMut, KeyIvInit},
+    Aes128,
+};
+use base64::{
+    alphabet,
+    engine::{general_purpose::GeneralPurpose, DecodePaddingMode, GeneralPurposeConfig},
+    Engine,
+};
+use regex::Regex;
+
+use crate::{Error, Result};
+
+/// Well-known AES-128 key and IV decrypting the key returned by the DLC service.
+const KEY: &[u8; 16] = b"cb99b5cbc24db398";
+const IV: &[u8; 16] = b"9bc24cb995cb8db3";
+
+/// Length of the encrypted key trailing a DLC container.
+const KEY_LENGTH: usize = 88;
+
+static RC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<rc>(.+?)</rc>").expect("valid rc regex"));
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<url>(.*?)</url>").expect("valid url regex"));
+
+/// Base64 engine accepting missing padding, as found in DLC containers.
+const LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// DLC container split into its encrypted content and the key sent to the DLC service.
+pub(crate) struct Dlc {
+    pub key: String,
+    data: Vec<u8>,
+}
+
+impl Dlc {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(data)
+            .map_err(|_| invalid("not base64 encoded"))?
+            .split_ascii_whitespace()
+            .collect::<String>();
+
+        if text.len() <= KEY_LENGTH || !text.is_char_boundary(text.len() - KEY_LENGTH) {
+            return Err(invalid("too short"));
+        }
+
+        let (data, key) = text.split_at(text.len() - KEY_LENGTH);
+
+        Ok(Self {
+            key: key.to_string(),
+            data: decode_base64(data).ok_or_else(|| invalid("not base64 encoded"))?,
+        })
+    }
+
+    /// Decrypt the links using the DLC service response for [`Dlc::key`].
+    pub fn decrypt(self, response: &str) -> Result<Vec<String>> {
+        let rc = RC
+            .captures(response)
+            .and_then(|captures| decode_base64(captures[1].trim()))
+            .filter(|rc| rc.len() >= 16)
+            .ok_or_else(|| invalid("no key returned by the DLC service"))?;
+
+        let mut key = [0; 16];
+        key.copy_from_slice(&rc[..16]);
+        cbc::Decryptor::<Aes128>::new(KEY.into(), IV.into())
+            .decrypt_padded_mut::<NoPadding>(&mut key)
+            .map_err(|_| invalid("invalid key"))?;
+
+        let mut data = self.data;
+        let len = data.len() - data.len() % 16;
+        let content = cbc::Decryptor::<Aes128>::new(&key.into(), &key.into())
+            .decrypt_padded_mut::<NoPadding>(&mut data[..len])
+            .map_err(|_| invalid("invalid content"))?;
+
+        // Padding is not standard, keep the base64 characters only
+        let content = content
+            .iter()
+            .copied()
+            .filter(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'='))
+            .collect::<Vec<_>>();
+        let xml = decode_base64(&content)
+            .and_then(|xml| String::from_utf8(xml).ok())
+            .ok_or_else(|| invalid("wrong key"))?;
+
+        URL.captures_iter(&xml)
+            .map(|captures| {
+                decode_base64(captures[1].trim())
+                    .and_then(|url| String::from_utf8(url).ok())
+                    .map(|url| url.trim().to_string())
+                    .ok_or_else(|| invalid("link is not base64 encoded"))
+            })
+            .collect()
+    }
+}
+
+fn decode_base64(data: impl AsRef<[u8]>) -> Option<Vec<u8>> {
+    let data = data.as_ref();
+    let end = data.iter().rposition(|b| *b != b'=').map_or(0, |i| i + 1);
+
+    LENIENT.decode(&data[..end]).ok()
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidContainer(format!("DLC: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_base64_padding() {
+        assert_eq!(decode_base64("YQ").as_deref(), Some(&b"a"[..]));
+        assert_eq!(decode_base64("YQ==").as_deref(), Some(&b"a"[..]));
+        assert_eq!(decode_base64("YQ===").as_deref(), Some(&b"a"[..]));
+    }
+
+    #[test]
+    fn test_parse_rejects_short_input() {
+        assert!(matches!(
+            Dlc::parse(b"YWJj"),
+            Err(Error::InvalidContainer(_))
+        ));
+    }
+}
@@ -0,0 +1,251 @@
+use bon::bon;
+use reqwest::Body;
+
+use crate::{consts::DLC_SERVICE_URL, Debrid, Error, Result};
+
+mod ccf;
+mod dlc;
+mod rsdf;
+
+pub use ccf::{decode_ccf, CcfKey};
+pub use rsdf::decode_rsdf;
+
+/// Format of a link container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerFormat {
+    /// RSDF container, decoded locally
+    Rsdf,
+    /// CCF container, decoded locally with the given keys
+    Ccf,
+    /// DLC container, decoded locally after a key exchange with the DLC service
+    Dlc,
+}
+
+impl ContainerFormat {
+    /// Detects the format of a container from its file name extension, if known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+
+        match extension.to_ascii_lowercase().as_str() {
+            "rsdf" => Some(ContainerFormat::Rsdf),
+            "ccf" => Some(ContainerFormat::Ccf),
+            "dlc" => Some(ContainerFormat::Dlc),
+            _ => None,
+        }
+    }
+
+    /// Detects the format of a container from its contents.
+    /// RSDF is hex encoded, DLC is base64 encoded and CCF is binary.
+    pub fn from_contents(data: &[u8]) -> Self {
+        let mut text = data.iter().filter(|b| !b.is_ascii_whitespace());
+
+        if text.clone().all(u8::is_ascii_hexdigit) {
+            ContainerFormat::Rsdf
+        } else if text.all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=')) {
+            ContainerFormat::Dlc
+        } else {
+            ContainerFormat::Ccf
+        }
+    }
+}
+
+/// Decodes link containers locally, falling back to RealDebrid when it is not possible.
+///
+/// RSDF containers are decoded without any request, and CCF containers with the keys
+/// given in `ccf_keys`. DLC containers only send their key to the DLC service, never
+/// their contents. Container links and the DLC service are fetched without the RealDebrid
+/// API token. Containers that fail to decode locally are sent to
+/// `unrestrict().container_file()` or `unrestrict().container_link()` unless `local_only` is set.
+pub struct Containers<'rd>(pub(crate) &'rd Debrid);
+
+#[bon]
+impl<'rd> Containers<'rd> {
+    /// Decode a container file into its links.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     if let Ok(data) = tokio::fs::read("example.rsdf").await {
+    ///         let links = client.containers().file()
+    ///             .data(data)
+    ///             .name("example.rsdf")
+    ///             .send()
+    ///             .await;
+    ///
+    ///         if let Ok(links) = links {
+    ///             println!("{:?}", links);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn file(
+        &self,
+        /// Container file contents
+        #[builder(into)]
+        data: Vec<u8>,
+        /// Container file name, detecting the format from its extension
+        #[builder(into)]
+        name: Option<String>,
+        /// Fail instead of sending the container to RealDebrid, default: false
+        local_only: Option<bool>,
+        /// DLC key exchange service URL, default: [`DLC_SERVICE_URL`]
+        dlc_service: Option<String>,
+        /// Keys of the CCF versions to decode locally, tried in order
+        ccf_keys: Option<Vec<CcfKey>>,
+    ) -> Result<Vec<String>> {
+        let local = self
+            .decode(&data, name.as_deref(), dlc_service, ccf_keys)
+            .await;
+
+        match local {
+            Err(_) if !local_only.unwrap_or(false) => self
+                .0
+                .put::<_, _, ()>("/unrestrict/containerFile", Body::from(data), None)
+                .await?
+                .json::<Vec<String>>()
+                .await
+                .map_err(Error::Reqwest),
+            local => local,
+        }
+    }
+
+    /// Download a container from a link and decode it into its links.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let links = client.containers().link()
+    ///         .link("https://example.com/example.dlc".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(links) = links {
+    ///         println!("{:?}", links);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn link(
+        &self,
+        /// HTTP Link of the container file
+        link: String,
+        /// Fail instead of sending the link to RealDebrid, default: false
+        local_only: Option<bool>,
+        /// DLC key exchange service URL, default: [`DLC_SERVICE_URL`]
+        dlc_service: Option<String>,
+        /// Keys of the CCF versions to decode locally, tried in order
+        ccf_keys: Option<Vec<CcfKey>>,
+    ) -> Result<Vec<String>> {
+        let local = async {
+            let data = self
+                .0
+                .http()
+                .get(&link)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(Error::Reqwest)?
+                .bytes()
+                .await
+                .map_err(Error::Reqwest)?;
+
+            let name = link.split(['?', '#']).next().unwrap_or(&link);
+            self.decode(&data, Some(name), dlc_service, ccf_keys).await
+        };
+
+        match local.await {
+            Err(_) if !local_only.unwrap_or(false) => {
+                self.0.unrestrict().container_link().link(link).send().await
+            }
+            local => local,
+        }
+    }
+}
+
+impl Containers<'_> {
+    async fn decode(
+        &self,
+        data: &[u8],
+        name: Option<&str>,
+        dlc_service: Option<String>,
+        ccf_keys: Option<Vec<CcfKey>>,
+    ) -> Result<Vec<String>> {
+        let format = name
+            .and_then(ContainerFormat::from_name)
+            .unwrap_or_else(|| ContainerFormat::from_contents(data));
+
+        let links = match format {
+            ContainerFormat::Rsdf => decode_rsdf(data)?,
+            ContainerFormat::Dlc => {
+                let dlc = dlc::Dlc::parse(data)?;
+                let service = dlc_service.unwrap_or_else(|| DLC_SERVICE_URL.to_string());
+
+                let response = self
+                    .0
+                    .http()
+                    .get(service)
+                    .query(&[("srcType", "dlc"), ("destType", "pylo"), ("data", &dlc.key)])
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(Error::Reqwest)?
+                    .text()
+                    .await
+                    .map_err(Error::Reqwest)?;
+
+                dlc.decrypt(&response)?
+            }
+            ContainerFormat::Ccf => decode_ccf(data, ccf_keys.as_deref().unwrap_or_default())?,
+        };
+
+        match links.is_empty() {
+            true => Err(Error::InvalidContainer("no links found".to_string())),
+            false => Ok(links),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            ContainerFormat::from_name("https://example.com/a.RSDF"),
+            Some(ContainerFormat::Rsdf)
+        );
+        assert_eq!(ContainerFormat::from_name("a.txt"), None);
+        assert_eq!(
+            ContainerFormat::from_contents(b"0A1b\n2c"),
+            ContainerFormat::Rsdf
+        );
+        assert_eq!(
+            ContainerFormat::from_contents(b"YWJj+/=="),
+            ContainerFormat::Dlc
+        );
+        assert_eq!(
+            ContainerFormat::from_contents(&[0x00, 0xFF]),
+            ContainerFormat::Ccf
+        );
+    }
+}
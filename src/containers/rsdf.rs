@@ -0,0 +1,80 @@
+use aes::{
+    cipher::{generic_array::GenericArray, BlockDecryptMut, BlockEncrypt, KeyInit, KeyIvInit},
+    Aes192,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{Error, Result};
+
+/// Well-known AES-192 key of RSDF containers.
+const KEY: [u8; 24] = [
+    0x8C, 0x35, 0x19, 0x2D, 0x96, 0x4D, 0xC3, 0x18, 0x2C, 0x6F, 0x84, 0xF3, 0x25, 0x22, 0x39, 0xEB,
+    0x4A, 0x32, 0x0D, 0x25, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Decodes an RSDF container into its links.
+///
+/// The file is hex encoded text holding one base64 encrypted link per line,
+/// all decrypted by a single AES-192 CFB-8 stream.
+pub fn decode_rsdf(data: &[u8]) -> Result<Vec<String>> {
+    let invalid = |reason: &str| Error::InvalidContainer(format!("RSDF: {}", reason));
+
+    let hex = data
+        .iter()
+        .filter(|b| !b.is_ascii_whitespace())
+        .copied()
+        .collect::<Vec<_>>();
+    let text = unhex(&hex).ok_or_else(|| invalid("not hex encoded"))?;
+
+    let cipher = Aes192::new(&KEY.into());
+    let mut iv = GenericArray::from([0xFF; 16]);
+    cipher.encrypt_block(&mut iv);
+    let mut decryptor = cfb8::Decryptor::<Aes192>::new(&KEY.into(), &iv);
+
+    text.split(|b| *b == b'\n' || *b == b'\r')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut link = STANDARD
+                .decode(line)
+                .map_err(|_| invalid("line is not base64 encoded"))?;
+
+            for byte in link.iter_mut() {
+                decryptor
+                    .decrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+            }
+
+            let link = String::from_utf8(link).map_err(|_| invalid("wrong key"))?;
+            Ok(link.replace("CCF: ", "").trim().to_string())
+        })
+        .collect()
+}
+
+fn unhex(hex: &[u8]) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    hex.chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unhex() {
+        assert_eq!(unhex(b"00fFa1"), Some(vec![0x00, 0xFF, 0xA1]));
+        assert_eq!(unhex(b"0"), None);
+        assert_eq!(unhex(b"zz"), None);
+    }
+
+    #[test]
+    fn test_decode_rsdf_rejects_invalid_input() {
+        assert!(matches!(
+            decode_rsdf(b"not hex"),
+            Err(Error::InvalidContainer(_))
+        ));
+    }
+}
@@ -13,6 +13,10 @@ use crate::{
 use crate::{
     apis::{root::RootApi, streaming::StreamingApi, traffic::TrafficApi},
     consts::REAL_DEBRID_BASE_URL,
+    containers::Containers,
+    download::Downloader,
+    library::Library,
+    profile::Provisioner,
 };
 use crate::{
     apis::{torrents::TorrentsApi, user::UserApi},
@@ -23,6 +27,7 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Debrid {
     client: Client,
+    http: Client,
     base_url: String,
 }
 
@@ -64,10 +69,20 @@ impl Debrid {
             .default_headers(headers)
             .build()
             .map_err(Error::Reqwest)?;
+        let http = Client::builder().build().map_err(Error::Reqwest)?;
 
         let base_url = base_url.unwrap_or_else(|| REAL_DEBRID_BASE_URL.to_string());
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            http,
+            base_url,
+        })
+    }
+
+    /// Client without the API token, for URLs outside of RealDebrid.
+    pub(crate) fn http(&self) -> &Client {
+        &self.http
     }
 
     fn request<P: AsRef<str>>(&self, method: Method, path: P) -> RequestBuilder {
@@ -185,7 +200,7 @@ impl Debrid {
     /// ```
     #[builder(finish_fn = send)]
     pub async fn disable_access_token(&self) -> Result<()> {
-        RootApi(&self).disable_access_token().send().await
+        RootApi(self).disable_access_token().send().await
     }
 
     /// Get server time.
@@ -212,7 +227,7 @@ impl Debrid {
     /// ```
     #[builder(finish_fn = send)]
     pub async fn time(&self) -> Result<String> {
-        RootApi(&self).time().send().await
+        RootApi(self).time().send().await
     }
     
     /// Get server time in ISO.
@@ -239,46 +254,66 @@ impl Debrid {
     /// ```
     #[builder(finish_fn = send)]
     pub async fn time_iso(&self) -> Result<String> {
-        RootApi(&self).time_iso().send().await
+        RootApi(self).time_iso().send().await
     }
 
     /// RealDebrid user api.
     pub fn user<'rd>(&'rd self) -> UserApi<'rd> {
-        UserApi(&self)
+        UserApi(self)
     }
 
     /// RealDebrid unrestrict api.
     pub fn unrestrict<'rd>(&'rd self) -> UnrestrictApi<'rd> {
-        UnrestrictApi(&self)
+        UnrestrictApi(self)
     }
 
     /// RealDebrid traffic api.
     pub fn traffic<'rd>(&'rd self) -> TrafficApi<'rd> {
-        TrafficApi(&self)
+        TrafficApi(self)
     }
 
     /// RealDebrid streaming api.
     pub fn streaming<'rd>(&'rd self) -> StreamingApi<'rd> {
-        StreamingApi(&self)
+        StreamingApi(self)
     }
 
     /// RealDebrid downloads api.
     pub fn downloads<'rd>(&'rd self) -> DownloadsApi<'rd> {
-        DownloadsApi(&self)
+        DownloadsApi(self)
     }
 
     /// RealDebrid torrents api.
     pub fn torrents<'rd>(&'rd self) -> TorrentsApi<'rd> {
-        TorrentsApi(&self)
+        TorrentsApi(self)
     }
 
     /// RealDebrid hosts api.
     pub fn hosts<'rd>(&'rd self) -> HostsApi<'rd> {
-        HostsApi(&self)
+        HostsApi(self)
     }
 
     /// RealDebrid settings api.
     pub fn settings<'rd>(&'rd self) -> SettingsApi<'rd> {
-        SettingsApi(&self)
+        SettingsApi(self)
+    }
+
+    /// Link containers decoder, falling back to the unrestrict api.
+    pub fn containers<'rd>(&'rd self) -> Containers<'rd> {
+        Containers(self)
+    }
+
+    /// File downloader for unrestricted links.
+    pub fn downloader<'rd>(&'rd self) -> Downloader<'rd> {
+        Downloader(self)
+    }
+
+    /// STRM and NFO library export for media servers.
+    pub fn library<'rd>(&'rd self) -> Library<'rd> {
+        Library(self)
+    }
+
+    /// Declarative account configuration from profiles.
+    pub fn provisioner<'rd>(&'rd self) -> Provisioner<'rd> {
+        Provisioner(self)
     }
 }
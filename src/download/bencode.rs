@@ -0,0 +1,150 @@
+/// Decoded bencode value, borrowing from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Value<'a> {
+    Int(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    /// Entries in input order, and the raw bytes of the whole dictionary
+    Dict(Vec<(&'a [u8], Value<'a>)>, &'a [u8]),
+}
+
+impl<'a> Value<'a> {
+    /// Looks up a key of a dictionary.
+    pub fn get(&self, key: &str) -> Option<&Value<'a>> {
+        match self {
+            Value::Dict(entries, _) => entries
+                .iter()
+                .find(|(k, _)| *k == key.as_bytes())
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            Value::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.as_bytes()?).ok()
+    }
+
+    pub fn as_list(&self) -> Option<&[Value<'a>]> {
+        match self {
+            Value::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Raw bytes of a dictionary, as found in the input.
+    pub fn raw(&self) -> Option<&'a [u8]> {
+        match self {
+            Value::Dict(_, raw) => Some(raw),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a single bencode value spanning the whole input.
+pub(crate) fn decode(input: &[u8]) -> Option<Value<'_>> {
+    let mut position = 0;
+    let value = parse(input, &mut position, 0)?;
+
+    (position == input.len()).then_some(value)
+}
+
+/// Max nesting of lists and dictionaries, bounding recursion on hostile input.
+const MAX_DEPTH: usize = 64;
+
+fn parse<'a>(input: &'a [u8], position: &mut usize, depth: usize) -> Option<Value<'a>> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    let start = *position;
+
+    match *input.get(start)? {
+        b'i' => {
+            let end = start + input[start..].iter().position(|b| *b == b'e')?;
+            let n = std::str::from_utf8(&input[start + 1..end])
+                .ok()?
+                .parse()
+                .ok()?;
+            *position = end + 1;
+            Some(Value::Int(n))
+        }
+        b'l' => {
+            *position += 1;
+            let mut items = Vec::new();
+
+            while *input.get(*position)? != b'e' {
+                items.push(parse(input, position, depth + 1)?);
+            }
+
+            *position += 1;
+            Some(Value::List(items))
+        }
+        b'd' => {
+            *position += 1;
+            let mut entries = Vec::new();
+
+            while *input.get(*position)? != b'e' {
+                let key = parse(input, position, depth + 1)?.as_bytes()?;
+                let value = parse(input, position, depth + 1)?;
+                entries.push((key, value));
+            }
+
+            *position += 1;
+            Some(Value::Dict(entries, &input[start..*position]))
+        }
+        b'0'..=b'9' => {
+            let colon = start + input[start..].iter().position(|b| *b == b':')?;
+            let len: usize = std::str::from_utf8(&input[start..colon])
+                .ok()?
+                .parse()
+                .ok()?;
+            let end = colon.checked_add(1)?.checked_add(len)?;
+            let bytes = input.get(colon + 1..end)?;
+            *position = end;
+            Some(Value::Bytes(bytes))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let input = b"d4:infod6:lengthi12e4:name5:a.txte4:listl1:ai-3eee";
+        let value = decode(input).unwrap();
+
+        let info = value.get("info").unwrap();
+        assert_eq!(info.get("length").and_then(Value::as_int), Some(12));
+        assert_eq!(info.get("name").and_then(Value::as_str), Some("a.txt"));
+        assert_eq!(info.raw(), Some(&b"d6:lengthi12e4:name5:a.txte"[..]));
+        assert_eq!(
+            value.get("list").and_then(Value::as_list),
+            Some(&[Value::Bytes(b"a"), Value::Int(-3)][..])
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_input() {
+        assert!(decode(b"d4:name").is_none());
+        assert!(decode(b"5:abc").is_none());
+        assert!(decode(b"i1ei2e").is_none());
+        assert!(decode(&[b'l'; 100]).is_none());
+    }
+}
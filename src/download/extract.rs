@@ -0,0 +1,432 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufReader, Read, Seek},
+    path::{Component, Path, PathBuf},
+};
+
+use bon::bon;
+use flate2::read::GzDecoder;
+
+use super::Downloader;
+use crate::{
+    models::torrents::{TorrentFile, TorrentInfo},
+    Error, Result,
+};
+
+/// Bytes read from the start of a file to detect its format.
+const MAGIC_LEN: usize = 262;
+
+/// Archive formats which can be extracted after download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    SevenZip,
+}
+
+impl ArchiveFormat {
+    /// Detects an archive format from the first bytes of a file.
+    ///
+    /// Any gzip stream is reported as [`ArchiveFormat::TarGz`], use
+    /// [`ArchiveFormat::detect`] to also check the decompressed content.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::download::ArchiveFormat;
+    ///
+    /// assert_eq!(ArchiveFormat::from_magic(b"PK\x03\x04...."), Some(ArchiveFormat::Zip));
+    /// assert_eq!(ArchiveFormat::from_magic(b"\x1aE\xdf\xa3"), None);
+    /// ```
+    pub fn from_magic(header: &[u8]) -> Option<Self> {
+        if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+            Some(ArchiveFormat::Zip)
+        } else if header.starts_with(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+            Some(ArchiveFormat::SevenZip)
+        } else if header.starts_with(&[0x1F, 0x8B]) {
+            Some(ArchiveFormat::TarGz)
+        } else if is_tar(header) {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Detects an archive format from a MIME type, such as `Download.mime_type`.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::download::ArchiveFormat;
+    ///
+    /// assert_eq!(ArchiveFormat::from_mime_type("application/x-7z-compressed"), Some(ArchiveFormat::SevenZip));
+    /// assert_eq!(ArchiveFormat::from_mime_type("video/x-matroska"), None);
+    /// ```
+    pub fn from_mime_type(mime_type: &str) -> Option<Self> {
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+
+        match essence.to_ascii_lowercase().as_str() {
+            "application/zip" | "application/x-zip" | "application/x-zip-compressed" => {
+                Some(ArchiveFormat::Zip)
+            }
+            "application/x-tar" | "application/x-gtar" => Some(ArchiveFormat::Tar),
+            "application/gzip" | "application/x-gzip" | "application/x-compressed-tar" => {
+                Some(ArchiveFormat::TarGz)
+            }
+            "application/x-7z-compressed" => Some(ArchiveFormat::SevenZip),
+            _ => None,
+        }
+    }
+
+    /// Detects the archive format of a file on disk.
+    ///
+    /// Magic bytes take precedence; `mime_type` is only used when they are inconclusive.
+    /// Gzip streams which do not hold a tarball are not considered archives.
+    pub async fn detect(path: impl AsRef<Path>, mime_type: Option<&str>) -> Result<Option<Self>> {
+        let path = path.as_ref().to_path_buf();
+        let mime_type = mime_type.map(str::to_string);
+
+        blocking(move || detect(&path, mime_type.as_deref())).await
+    }
+}
+
+#[bon]
+impl<'rd> Downloader<'rd> {
+    /// Extract a downloaded ZIP, TAR, TAR.GZ or 7z archive.
+    ///
+    /// Returns the extracted files, or `None` if the file is not an archive.
+    /// Entries which would be written outside of `destination` fail the
+    /// extraction with [`Error::UnsafePath`]; links inside tarballs are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let extracted = client.downloader().extract()
+    ///         .archive("downloads/release.zip")
+    ///         .destination("downloads/release")
+    ///         .mime_type("application/zip".to_string())
+    ///         .remove_archive(true)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(Some(files)) = extracted {
+    ///         println!("Extracted {} files", files.len());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn extract(
+        &self,
+        /// Archive to extract
+        #[builder(into)]
+        archive: PathBuf,
+        /// Directory to extract into, default: the archive path without its extension
+        #[builder(into)]
+        destination: Option<PathBuf>,
+        /// MIME type of the archive, used when magic bytes are inconclusive
+        mime_type: Option<String>,
+        /// Remove the archive once extracted, default: false
+        remove_archive: Option<bool>,
+    ) -> Result<Option<Vec<PathBuf>>> {
+        let destination = destination.unwrap_or_else(|| default_destination(&archive));
+
+        blocking(move || {
+            let Some(format) = detect(&archive, mime_type.as_deref())? else {
+                return Ok(None);
+            };
+
+            std::fs::create_dir_all(&destination).map_err(Error::Io)?;
+            let file = File::open(&archive).map_err(Error::Io)?;
+
+            let files = match format {
+                ArchiveFormat::Zip => extract_zip(file, &destination)?,
+                ArchiveFormat::Tar => extract_tar(BufReader::new(file), &destination)?,
+                ArchiveFormat::TarGz => {
+                    extract_tar(GzDecoder::new(BufReader::new(file)), &destination)?
+                }
+                ArchiveFormat::SevenZip => extract_7z(file, &destination)?,
+            };
+
+            if remove_archive.unwrap_or(false) {
+                std::fs::remove_file(&archive).map_err(Error::Io)?;
+            }
+
+            Ok(Some(files))
+        })
+        .await
+    }
+}
+
+/// Pair the selected files of a torrent with files on disk, such as extracted files.
+///
+/// Files are matched by their path inside the torrent first, then by filename and size.
+/// Each file on disk is matched at most once.
+pub fn match_torrent_files<'t>(
+    torrent: &'t TorrentInfo,
+    files: &[PathBuf],
+) -> Vec<(&'t TorrentFile, Option<PathBuf>)> {
+    let sizes = files
+        .iter()
+        .map(|path| std::fs::metadata(path).ok().map(|m| m.len()))
+        .collect::<Vec<_>>();
+    let mut used = HashSet::new();
+
+    let mut find = |file: &TorrentFile, by_path: bool| {
+        let components = file
+            .path
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>();
+
+        let found = (0..files.len()).find(|&i| {
+            if used.contains(&i) || sizes[i].is_some_and(|size| size != file.bytes) {
+                return false;
+            }
+
+            let local = files[i]
+                .components()
+                .rev()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>();
+
+            match by_path {
+                true => {
+                    components.len() <= local.len()
+                        && components.iter().rev().zip(&local).all(|(a, b)| a == b)
+                }
+                false => {
+                    sizes[i].is_some() && components.last().copied() == local.first().map(|c| &**c)
+                }
+            }
+        });
+
+        found.map(|i| {
+            used.insert(i);
+            files[i].clone()
+        })
+    };
+
+    let selected = torrent
+        .files
+        .iter()
+        .filter(|f| f.selected)
+        .collect::<Vec<_>>();
+    let mut matches = selected
+        .iter()
+        .map(|file| (*file, find(file, true)))
+        .collect::<Vec<_>>();
+
+    for (file, local) in matches.iter_mut().filter(|(_, local)| local.is_none()) {
+        *local = find(file, false);
+    }
+
+    matches
+}
+
+/// Runs blocking archive work off the async runtime.
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::Io(io::Error::other(e)))?
+}
+
+fn detect(path: &Path, mime_type: Option<&str>) -> Result<Option<ArchiveFormat>> {
+    let mut header = Vec::with_capacity(MAGIC_LEN);
+    File::open(path)
+        .and_then(|file| file.take(MAGIC_LEN as u64).read_to_end(&mut header))
+        .map_err(Error::Io)?;
+
+    let format = ArchiveFormat::from_magic(&header)
+        .or_else(|| mime_type.and_then(ArchiveFormat::from_mime_type));
+
+    if format != Some(ArchiveFormat::TarGz) {
+        return Ok(format);
+    }
+
+    // A gzip stream is only an archive if it wraps a tarball
+    let mut decompressed = Vec::with_capacity(MAGIC_LEN);
+    let file = File::open(path).map_err(Error::Io)?;
+    let _ = GzDecoder::new(file)
+        .take(MAGIC_LEN as u64)
+        .read_to_end(&mut decompressed);
+
+    Ok(is_tar(&decompressed).then_some(ArchiveFormat::TarGz))
+}
+
+fn is_tar(header: &[u8]) -> bool {
+    header.get(257..262) == Some(b"ustar")
+}
+
+/// Destination next to the archive, named after it without its extension.
+fn default_destination(archive: &Path) -> PathBuf {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let lower = name.to_ascii_lowercase();
+
+    let stem = [".tar.gz", ".tgz", ".tar", ".zip", ".7z"]
+        .iter()
+        .find(|ext| lower.ends_with(*ext) && lower.len() > ext.len())
+        .map(|ext| &name[..name.len() - ext.len()])
+        .unwrap_or_else(|| match name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => stem,
+            _ => &name,
+        });
+
+    let destination = archive.with_file_name(stem);
+
+    match destination == archive {
+        true => archive.with_file_name(format!("{}.extracted", stem)),
+        false => destination,
+    }
+}
+
+/// Joins an archive entry name to `root`, refusing anything escaping it.
+/// Empty names, such as the root directory entry of some archives, resolve to `root`.
+fn safe_join(root: &Path, name: &Path) -> Result<PathBuf> {
+    let mut path = root.to_path_buf();
+
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return Err(Error::UnsafePath(name.display().to_string())),
+        }
+    }
+
+    Ok(path)
+}
+
+fn create_parent(path: &Path) -> Result<()> {
+    match path.parent() {
+        Some(parent) => std::fs::create_dir_all(parent).map_err(Error::Io),
+        None => Ok(()),
+    }
+}
+
+fn extract_zip<R: Read + Seek>(reader: R, destination: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(Error::Zip)?;
+    let mut files = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(Error::Zip)?;
+
+        let name = entry
+            .enclosed_name()
+            .ok_or_else(|| Error::UnsafePath(entry.name().to_string()))?;
+        let path = safe_join(destination, &name)?;
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path).map_err(Error::Io)?;
+        } else if entry.is_file() {
+            create_parent(&path)?;
+            let mut file = File::create(&path).map_err(Error::Io)?;
+            io::copy(&mut entry, &mut file).map_err(Error::Io)?;
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+fn extract_tar<R: Read>(reader: R, destination: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut files = Vec::new();
+
+    for entry in archive.entries().map_err(Error::Io)? {
+        let mut entry = entry.map_err(Error::Io)?;
+        let name = entry.path().map_err(Error::Io)?.into_owned();
+        let kind = entry.header().entry_type();
+
+        if kind.is_dir() {
+            std::fs::create_dir_all(safe_join(destination, &name)?).map_err(Error::Io)?;
+        } else if kind.is_file() {
+            let path = safe_join(destination, &name)?;
+            create_parent(&path)?;
+            entry.unpack(&path).map_err(Error::Io)?;
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+fn extract_7z<R: Read + Seek>(reader: R, destination: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut unsafe_path = None;
+
+    let result = sevenz_rust::decompress_with_extract_fn(reader, destination, |entry, data, _| {
+        let path = match safe_join(destination, Path::new(entry.name())) {
+            Ok(path) => path,
+            Err(e) => {
+                unsafe_path = Some(e);
+                return Err(sevenz_rust::Error::other("unsafe entry path"));
+            }
+        };
+
+        if entry.is_directory() {
+            std::fs::create_dir_all(&path).map_err(sevenz_rust::Error::io)?;
+        } else if !entry.is_anti_item() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(sevenz_rust::Error::io)?;
+            }
+
+            let mut file = File::create(&path).map_err(sevenz_rust::Error::io)?;
+            io::copy(data, &mut file).map_err(sevenz_rust::Error::io)?;
+            files.push(path);
+        }
+
+        Ok(true)
+    });
+
+    match (result, unsafe_path) {
+        (_, Some(e)) => Err(e),
+        (Err(e), None) => Err(Error::SevenZip(e)),
+        (Ok(()), None) => Ok(files),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_join_rejects_escaping_paths() {
+        let root = Path::new("/downloads/release");
+
+        assert_eq!(
+            safe_join(root, Path::new("./sub/file.mkv")).unwrap(),
+            root.join("sub/file.mkv")
+        );
+        assert!(safe_join(root, Path::new("../evil.sh")).is_err());
+        assert!(safe_join(root, Path::new("sub/../../evil.sh")).is_err());
+        assert!(safe_join(root, Path::new("/etc/passwd")).is_err());
+        assert_eq!(safe_join(root, Path::new("./")).unwrap(), root);
+    }
+
+    #[test]
+    fn test_default_destination() {
+        assert_eq!(
+            default_destination(Path::new("dl/Release.tar.gz")),
+            Path::new("dl/Release")
+        );
+        assert_eq!(
+            default_destination(Path::new("dl/Release.ZIP")),
+            Path::new("dl/Release")
+        );
+        assert_eq!(
+            default_destination(Path::new("dl/archive")),
+            Path::new("dl/archive.extracted")
+        );
+    }
+}
@@ -0,0 +1,768 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bon::bon;
+use tokio::sync::{broadcast, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+use super::{with_suffix, Progress, Source, Throttle};
+use crate::{
+    models::{downloads::Download, torrents::TorrentInfo, traffic::Traffic, unrestrict::Link},
+    usage::{Quota, QuotaUnit},
+    Debrid, Result,
+};
+
+/// Default number of jobs running at the same time.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default time a `traffic().get()` snapshot is trusted for.
+pub const DEFAULT_TRAFFIC_TTL: Duration = Duration::from_secs(60);
+
+/// Minimum time between two progress events of a job.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Capacity of the event channel, after which slow subscribers lag.
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Identifier of a job in a [`DownloadManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobId(u64);
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Represents something a [`DownloadManager`] can download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// Already unrestricted file
+    Source {
+        /// File to download
+        source: Source,
+        /// Host main domain
+        host: String,
+    },
+    /// Hoster link, unrestricted when the job starts
+    Hoster {
+        /// Original hoster link
+        link: String,
+        /// Host main domain, if known
+        host: Option<String>,
+    },
+}
+
+impl From<&Link> for Item {
+    fn from(link: &Link) -> Self {
+        Item::Source {
+            source: Source::from(link),
+            host: link.host.clone(),
+        }
+    }
+}
+
+impl From<Link> for Item {
+    fn from(link: Link) -> Self {
+        Item::from(&link)
+    }
+}
+
+impl From<&Download> for Item {
+    fn from(download: &Download) -> Self {
+        Item::Source {
+            source: Source::from(download),
+            host: download.host.clone(),
+        }
+    }
+}
+
+impl From<Download> for Item {
+    fn from(download: Download) -> Self {
+        Item::from(&download)
+    }
+}
+
+/// Current status of a job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    /// Waiting for a free slot
+    Queued,
+    /// Downloading
+    Running,
+    /// Paused, until resumed
+    Paused(PauseReason),
+    /// Downloaded and verified
+    Completed,
+    /// Failed with the given error
+    Failed(String),
+    /// Cancelled, partial files removed
+    Cancelled,
+}
+
+/// Reason a job was paused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseReason {
+    /// Paused through [`DownloadManager::pause`]
+    Requested,
+    /// Starting the job would exceed the hoster's remaining traffic
+    QuotaExceeded {
+        /// Host main domain
+        host: String,
+        /// Traffic the job needs, in bytes or links depending on the hoster limit
+        needed: u64,
+        /// Traffic left after running jobs, in the same unit
+        left: u64,
+    },
+}
+
+/// Events emitted by a [`DownloadManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Job added to the queue, or resumed
+    Queued { id: JobId },
+    /// Job started downloading
+    Started {
+        id: JobId,
+        filename: String,
+        path: PathBuf,
+    },
+    /// Job made progress
+    Progress {
+        id: JobId,
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    /// Job paused
+    Paused { id: JobId, reason: PauseReason },
+    /// Job downloaded and verified
+    Completed {
+        id: JobId,
+        path: PathBuf,
+        bytes: u64,
+    },
+    /// Archive downloaded by a job extracted
+    #[cfg(feature = "extract")]
+    Extracted { id: JobId, files: Vec<PathBuf> },
+    /// Job failed
+    Failed { id: JobId, error: String },
+    /// Job cancelled
+    Cancelled { id: JobId },
+}
+
+/// Long-running download queue with a concurrency cap, bandwidth limit and traffic watch.
+///
+/// Cloning the manager returns a handle to the same queue.
+#[derive(Clone)]
+pub struct DownloadManager {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    debrid: Debrid,
+    directory: PathBuf,
+    semaphore: Arc<Semaphore>,
+    throttle: Option<Arc<Throttle>>,
+    traffic_ttl: Option<Duration>,
+    traffic: tokio::sync::Mutex<Option<(Instant, HashMap<String, Traffic>)>>,
+    jobs: Mutex<Jobs>,
+    events: broadcast::Sender<Event>,
+    /// Whether or not to remove archives once extracted, if extraction is enabled
+    #[cfg(feature = "extract")]
+    extract: Mutex<Option<bool>>,
+}
+
+#[derive(Default)]
+struct Jobs {
+    next: u64,
+    entries: HashMap<JobId, Entry>,
+    /// Traffic reserved by running jobs, per host
+    reserved: HashMap<String, u64>,
+    /// Whether or not a job consumed traffic since the traffic snapshot was taken
+    traffic_used: bool,
+}
+
+struct Entry {
+    item: Item,
+    directory: PathBuf,
+    status: JobStatus,
+    token: CancellationToken,
+    path: Option<PathBuf>,
+    reservation: Option<(String, u64)>,
+}
+
+/// Traffic a job consumes on a limited hoster.
+#[derive(Debug, Clone, Copy)]
+enum Need {
+    Links(u64),
+    Bytes(u64),
+}
+
+enum Outcome {
+    Completed {
+        path: PathBuf,
+        bytes: u64,
+        extracted: Option<Vec<PathBuf>>,
+    },
+    Paused(PauseReason),
+    Skipped,
+}
+
+#[bon]
+impl DownloadManager {
+    /// Creates a new download manager.
+    /// Jobs are spawned on the current tokio runtime.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{download::{DownloadManager, Event}, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let manager = DownloadManager::builder()
+    ///         .debrid(client.clone())
+    ///         .directory("downloads")
+    ///         .concurrency(2)
+    ///         .bytes_per_second(10 * 1024 * 1024)
+    ///         .build();
+    ///
+    ///     let mut events = manager.subscribe();
+    ///
+    ///     if let Ok(downloads) = client.downloads().get().limit(5).send().await {
+    ///         for download in downloads {
+    ///             manager.add(download);
+    ///         }
+    ///     }
+    ///
+    ///     while let Ok(Ok(event)) =
+    ///         tokio::time::timeout(std::time::Duration::from_secs(1), events.recv()).await
+    ///     {
+    ///         if let Event::Completed { path, .. } = event {
+    ///             println!("Downloaded {}", path.display());
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// RealDebrid client
+        debrid: Debrid,
+        /// Directory files are downloaded to
+        #[builder(into)]
+        directory: PathBuf,
+        /// Max jobs running at the same time, default: 4
+        concurrency: Option<usize>,
+        /// Bandwidth limit shared by all jobs, default: unlimited
+        bytes_per_second: Option<u64>,
+        /// Pause jobs which would exceed a hoster's remaining traffic, default: true
+        watch_traffic: Option<bool>,
+        /// Time a traffic snapshot is trusted for, default: 60 seconds
+        traffic_ttl: Option<Duration>,
+    ) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_CAPACITY);
+
+        Self {
+            inner: Arc::new(Inner {
+                debrid,
+                directory,
+                semaphore: Arc::new(Semaphore::new(
+                    concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1),
+                )),
+                throttle: bytes_per_second.map(|b| Arc::new(Throttle::new(b))),
+                traffic_ttl: watch_traffic
+                    .unwrap_or(true)
+                    .then(|| traffic_ttl.unwrap_or(DEFAULT_TRAFFIC_TTL)),
+                traffic: tokio::sync::Mutex::new(None),
+                jobs: Mutex::new(Jobs::default()),
+                events,
+                #[cfg(feature = "extract")]
+                extract: Mutex::new(None),
+            }),
+        }
+    }
+}
+
+impl DownloadManager {
+    /// Subscribe to job events.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.inner.events.subscribe()
+    }
+
+    /// Queue an unrestricted link, download or hoster link.
+    pub fn add(&self, item: impl Into<Item>) -> JobId {
+        self.inner.add(item.into(), self.inner.directory.clone())
+    }
+
+    /// Queue every link of a finished torrent, in a directory named after the torrent
+    /// when it holds more than one link.
+    pub fn add_torrent(&self, torrent: &TorrentInfo) -> Vec<JobId> {
+        let directory = match torrent.links.len() {
+            0 | 1 => self.inner.directory.clone(),
+            _ => self.inner.directory.join(sanitize(&torrent.filename)),
+        };
+
+        torrent
+            .links
+            .iter()
+            .map(|link| {
+                let item = Item::Hoster {
+                    link: link.clone(),
+                    host: Some(torrent.host.clone()),
+                };
+
+                self.inner.add(item, directory.clone())
+            })
+            .collect()
+    }
+
+    /// Pause a queued or running job, keeping its partial file for later.
+    /// Returns whether or not the job was paused.
+    pub fn pause(&self, id: JobId) -> bool {
+        let mut jobs = self.inner.jobs.lock().unwrap();
+
+        let Some(entry) = jobs.entries.get_mut(&id) else {
+            return false;
+        };
+
+        if !matches!(entry.status, JobStatus::Queued | JobStatus::Running) {
+            return false;
+        }
+
+        entry.token.cancel();
+        entry.status = JobStatus::Paused(PauseReason::Requested);
+        let reservation = entry.reservation.take();
+        jobs.release(reservation);
+
+        self.inner.emit(Event::Paused {
+            id,
+            reason: PauseReason::Requested,
+        });
+
+        true
+    }
+
+    /// Queue a paused or failed job again.
+    /// Returns whether or not the job was resumed.
+    pub fn resume(&self, id: JobId) -> bool {
+        let token = {
+            let mut jobs = self.inner.jobs.lock().unwrap();
+
+            let Some(entry) = jobs.entries.get_mut(&id) else {
+                return false;
+            };
+
+            if !matches!(entry.status, JobStatus::Paused(_) | JobStatus::Failed(_)) {
+                return false;
+            }
+
+            entry.token = CancellationToken::new();
+            entry.status = JobStatus::Queued;
+            entry.token.clone()
+        };
+
+        self.inner.emit(Event::Queued { id });
+        tokio::spawn(Inner::run(self.inner.clone(), id, token));
+
+        true
+    }
+
+    /// Cancel a job, removing its partial file once its download stopped.
+    /// Returns whether or not the job was cancelled.
+    pub fn cancel(&self, id: JobId) -> bool {
+        let path = {
+            let mut jobs = self.inner.jobs.lock().unwrap();
+
+            let Some(entry) = jobs.entries.get_mut(&id) else {
+                return false;
+            };
+
+            if matches!(entry.status, JobStatus::Completed | JobStatus::Cancelled) {
+                return false;
+            }
+
+            // Queued and running jobs have their files removed by their task once it
+            // stopped, as it may still be writing them
+            let live = matches!(entry.status, JobStatus::Queued | JobStatus::Running);
+
+            entry.token.cancel();
+            entry.status = JobStatus::Cancelled;
+            let path = if live { None } else { entry.path.take() };
+            let reservation = entry.reservation.take();
+            jobs.release(reservation);
+
+            path
+        };
+
+        if let Some(path) = path {
+            let _ = std::fs::remove_file(with_suffix(&path, ".part"));
+            let _ = std::fs::remove_file(with_suffix(&path, ".part.json"));
+        }
+
+        self.inner.emit(Event::Cancelled { id });
+
+        true
+    }
+
+    /// Extract archives downloaded by jobs completing from now on, next to them.
+    /// Extracted files are reported with [`Event::Extracted`].
+    #[cfg(feature = "extract")]
+    pub fn extract_archives(&self, remove_archives: bool) {
+        *self.inner.extract.lock().unwrap() = Some(remove_archives);
+    }
+
+    /// Get the status of a job.
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        let jobs = self.inner.jobs.lock().unwrap();
+        jobs.entries.get(&id).map(|entry| entry.status.clone())
+    }
+
+    /// Get the status of every job, ordered by ID.
+    pub fn jobs(&self) -> Vec<(JobId, JobStatus)> {
+        let jobs = self.inner.jobs.lock().unwrap();
+
+        let mut list = jobs
+            .entries
+            .iter()
+            .map(|(id, entry)| (*id, entry.status.clone()))
+            .collect::<Vec<_>>();
+        list.sort_by_key(|(id, _)| *id);
+        list
+    }
+}
+
+impl Jobs {
+    fn release(&mut self, reservation: Option<(String, u64)>) {
+        if let Some((host, amount)) = reservation {
+            if let Some(reserved) = self.reserved.get_mut(&host) {
+                *reserved = reserved.saturating_sub(amount);
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn emit(&self, event: Event) {
+        // No subscribers is not an error
+        let _ = self.events.send(event);
+    }
+
+    fn add(self: &Arc<Self>, item: Item, directory: PathBuf) -> JobId {
+        let token = CancellationToken::new();
+
+        let id = {
+            let mut jobs = self.jobs.lock().unwrap();
+            let id = JobId(jobs.next);
+            jobs.next += 1;
+
+            jobs.entries.insert(
+                id,
+                Entry {
+                    item,
+                    directory,
+                    status: JobStatus::Queued,
+                    token: token.clone(),
+                    path: None,
+                    reservation: None,
+                },
+            );
+
+            id
+        };
+
+        self.emit(Event::Queued { id });
+        tokio::spawn(Inner::run(self.clone(), id, token));
+
+        id
+    }
+
+    async fn run(self: Arc<Self>, id: JobId, token: CancellationToken) {
+        // Cancellation is checked first, so that a job paused then resumed is
+        // never picked up by its stale task
+        let permit = tokio::select! {
+            biased;
+            _ = token.cancelled() => None,
+            permit = self.semaphore.clone().acquire_owned() => permit.ok(),
+        };
+
+        if let Some(_permit) = permit {
+            let result = tokio::select! {
+                biased;
+                _ = token.cancelled() => None,
+                result = self.process(id) => Some(result),
+            };
+
+            if let Some(result) = result {
+                self.finish(id, result);
+            }
+        }
+
+        self.discard(id).await;
+    }
+
+    /// Removes the partial file of a job cancelled while its task was live, now that
+    /// the task no longer writes it.
+    async fn discard(&self, id: JobId) {
+        let path = match self.jobs.lock().unwrap().entries.get_mut(&id) {
+            Some(entry) if entry.status == JobStatus::Cancelled => entry.path.take(),
+            _ => None,
+        };
+
+        if let Some(path) = path {
+            let _ = tokio::fs::remove_file(with_suffix(&path, ".part")).await;
+            let _ = tokio::fs::remove_file(with_suffix(&path, ".part.json")).await;
+        }
+    }
+
+    async fn process(&self, id: JobId) -> Result<Outcome> {
+        let (item, directory) = {
+            let mut jobs = self.jobs.lock().unwrap();
+
+            match jobs.entries.get_mut(&id) {
+                Some(entry) if entry.status == JobStatus::Queued => {
+                    entry.status = JobStatus::Running;
+                    (entry.item.clone(), entry.directory.clone())
+                }
+                _ => return Ok(Outcome::Skipped),
+            }
+        };
+
+        let (source, host) = match item {
+            Item::Source { source, host } => (source, host),
+            Item::Hoster { link, host } => {
+                if let Some(host) = &host {
+                    if let Some(reason) = self.check_quota(id, host, Need::Links(1)).await? {
+                        return Ok(Outcome::Paused(reason));
+                    }
+                }
+
+                let link = self.debrid.unrestrict().link().link(link).send().await?;
+                let item = Item::from(&link);
+
+                // Resuming the job must not unrestrict the link again
+                if let Some(entry) = self.jobs.lock().unwrap().entries.get_mut(&id) {
+                    entry.item = item;
+                }
+
+                (Source::from(&link), link.host)
+            }
+        };
+
+        if let Some(reason) = self
+            .check_quota(id, &host, Need::Bytes(source.filesize))
+            .await?
+        {
+            return Ok(Outcome::Paused(reason));
+        }
+
+        tokio::fs::create_dir_all(&directory)
+            .await
+            .map_err(crate::Error::Io)?;
+
+        let path = directory.join(sanitize(&source.filename));
+
+        if let Some(entry) = self.jobs.lock().unwrap().entries.get_mut(&id) {
+            entry.path = Some(path.clone());
+        }
+
+        self.emit(Event::Started {
+            id,
+            filename: source.filename.clone(),
+            path: path.clone(),
+        });
+
+        #[cfg(feature = "extract")]
+        let mime_type = source.mime_type.clone();
+
+        let bytes = self
+            .debrid
+            .downloader()
+            .file()
+            .source(source)
+            .path(path.clone())
+            .maybe_throttle(self.throttle.clone())
+            .progress(self.progress(id))
+            .send()
+            .await?;
+
+        #[cfg(feature = "extract")]
+        let extract = *self.extract.lock().unwrap();
+
+        #[cfg(feature = "extract")]
+        let extracted = match extract {
+            Some(remove_archive) => {
+                self.debrid
+                    .downloader()
+                    .extract()
+                    .archive(path.clone())
+                    .maybe_mime_type(mime_type)
+                    .remove_archive(remove_archive)
+                    .send()
+                    .await?
+            }
+            None => None,
+        };
+
+        #[cfg(not(feature = "extract"))]
+        let extracted = None;
+
+        Ok(Outcome::Completed {
+            path,
+            bytes,
+            extracted,
+        })
+    }
+
+    fn finish(&self, id: JobId, result: Result<Outcome>) {
+        let mut extracted = None;
+
+        let event = {
+            let mut jobs = self.jobs.lock().unwrap();
+
+            let Some(entry) = jobs.entries.get_mut(&id) else {
+                return;
+            };
+
+            // Paused or cancelled in the meantime
+            if entry.status != JobStatus::Running {
+                return;
+            }
+
+            let event = match result {
+                Ok(Outcome::Completed {
+                    path,
+                    bytes,
+                    extracted: files,
+                }) => {
+                    entry.status = JobStatus::Completed;
+                    extracted = files;
+                    Event::Completed { id, path, bytes }
+                }
+                Ok(Outcome::Paused(reason)) => {
+                    entry.status = JobStatus::Paused(reason.clone());
+                    Event::Paused { id, reason }
+                }
+                Ok(Outcome::Skipped) => return,
+                Err(e) => {
+                    entry.status = JobStatus::Failed(e.to_string());
+                    Event::Failed {
+                        id,
+                        error: e.to_string(),
+                    }
+                }
+            };
+
+            // The traffic snapshot no longer accounts for what the job consumed
+            let reservation = entry.reservation.take();
+            jobs.traffic_used |= reservation.is_some();
+            jobs.release(reservation);
+
+            event
+        };
+
+        self.emit(event);
+
+        #[cfg(feature = "extract")]
+        if let Some(files) = extracted {
+            self.emit(Event::Extracted { id, files });
+        }
+
+        #[cfg(not(feature = "extract"))]
+        let _ = extracted;
+    }
+
+    /// Progress callback emitting throttled progress events for a job.
+    fn progress(&self, id: JobId) -> super::ProgressFn {
+        let events = self.events.clone();
+        let last = Mutex::new(None::<Instant>);
+
+        Arc::new(move |progress: Progress| {
+            let mut last = last.lock().unwrap();
+            let done = Some(progress.downloaded) == progress.total;
+
+            if done || last.map_or(true, |t| t.elapsed() >= PROGRESS_INTERVAL) {
+                *last = Some(Instant::now());
+
+                let _ = events.send(Event::Progress {
+                    id,
+                    downloaded: progress.downloaded,
+                    total: progress.total,
+                });
+            }
+        })
+    }
+
+    /// Checks a job against the hoster's remaining traffic, reserving it when it fits.
+    async fn check_quota(&self, id: JobId, host: &str, need: Need) -> Result<Option<PauseReason>> {
+        let Some(ttl) = self.traffic_ttl else {
+            return Ok(None);
+        };
+
+        let left = {
+            let mut traffic = self.traffic.lock().await;
+            let used = std::mem::take(&mut self.jobs.lock().unwrap().traffic_used);
+
+            if used || traffic.as_ref().map_or(true, |(at, _)| at.elapsed() >= ttl) {
+                let fresh = self.debrid.traffic().get().send().await?;
+                *traffic = Some((Instant::now(), fresh));
+            }
+
+            let quota = traffic
+                .as_ref()
+                .and_then(|(_, t)| Quota::from_traffic(host, t.get(host)?));
+
+            match (quota, need) {
+                (Some(Quota { unit: QuotaUnit::Links, remaining, .. }), Need::Links(n))
+                | (Some(Quota { unit: QuotaUnit::Bytes, remaining, .. }), Need::Bytes(n)) => {
+                    Some((remaining, n))
+                }
+                _ => None,
+            }
+        };
+
+        let Some((left, needed)) = left else {
+            return Ok(None);
+        };
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let reserved = jobs.reserved.get(host).copied().unwrap_or(0);
+        let left = left.saturating_sub(reserved);
+
+        if needed > left {
+            return Ok(Some(PauseReason::QuotaExceeded {
+                host: host.to_string(),
+                needed,
+                left,
+            }));
+        }
+
+        *jobs.reserved.entry(host.to_string()).or_default() += needed;
+
+        if let Some(entry) = jobs.entries.get_mut(&id) {
+            let previous = entry.reservation.replace((host.to_string(), needed));
+            jobs.release(previous);
+        }
+
+        Ok(None)
+    }
+}
+
+/// Replaces path separators so a remote name stays a single path component.
+pub(crate) fn sanitize(name: &str) -> String {
+    let name = name.replace(['/', '\\'], "_");
+
+    match Path::new(&name).file_name() {
+        Some(_) if name != ".." => name,
+        _ => "_".to_string(),
+    }
+}
@@ -0,0 +1,495 @@
+mod bencode;
+#[cfg(feature = "extract")]
+mod extract;
+mod manager;
+mod remote;
+mod source;
+mod state;
+mod throttle;
+mod verify;
+
+#[cfg(feature = "extract")]
+pub use extract::{match_torrent_files, ArchiveFormat};
+pub use manager::*;
+pub(crate) use manager::sanitize;
+pub use remote::*;
+pub use source::Source;
+pub use throttle::Throttle;
+pub use verify::*;
+
+use std::{
+    ffi::OsString,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bon::bon;
+use futures::StreamExt;
+use reqwest::{
+    header::{ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, RANGE},
+    StatusCode,
+};
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncSeekExt, AsyncWriteExt},
+    sync::Mutex,
+};
+
+use crate::{Debrid, Error, Result};
+use state::State;
+
+/// Bytes downloaded by a connection between two saves of the sidecar state file.
+const SAVE_INTERVAL: u64 = 4 * 1024 * 1024;
+
+/// Callback receiving download progress.
+pub type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// Represents the progress of a download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes written to disk so far, including resumed bytes
+    pub downloaded: u64,
+    /// Total size of the file in bytes, if known
+    pub total: Option<u64>,
+}
+
+/// Callbacks shared by every connection of a download.
+struct Hooks {
+    progress: Option<ProgressFn>,
+    throttle: Option<Arc<Throttle>>,
+}
+
+impl Hooks {
+    /// Reports progress and waits for the throttle, if any.
+    async fn transferred(&self, bytes: u64, progress: Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+
+        if let Some(throttle) = &self.throttle {
+            throttle.acquire(bytes).await;
+        }
+    }
+}
+
+/// Size and range support of a remote file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Probe {
+    /// Size of the file in bytes, if known
+    pub size: Option<u64>,
+    /// Whether or not the server honours range requests
+    pub ranges: bool,
+}
+
+/// File downloader for unrestricted links
+pub struct Downloader<'rd>(pub(crate) &'rd Debrid);
+
+#[bon]
+impl<'rd> Downloader<'rd> {
+    /// Download an unrestricted link or download to disk.
+    ///
+    /// The file is fetched over up to `chunks` parallel range requests into
+    /// `<path>.part`, with progress tracked in `<path>.part.json` so that an
+    /// interrupted download resumes where it stopped. The final size is
+    /// verified against `filesize` before the file is moved to `path`.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let link = client.unrestrict().link()
+    ///         .link("https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(link) = link {
+    ///         let path = link.filename.clone();
+    ///
+    ///         let downloaded = client.downloader().file()
+    ///             .source(link)
+    ///             .path(path)
+    ///             .send()
+    ///             .await;
+    ///
+    ///         if let Ok(bytes) = downloaded {
+    ///             println!("Downloaded {} bytes", bytes);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn file(
+        &self,
+        /// Unrestricted link or download to fetch
+        #[builder(into)]
+        source: Source,
+        /// Destination path of the file
+        #[builder(into)]
+        path: PathBuf,
+        /// Max parallel connections, default: the source's max chunks
+        connections: Option<u64>,
+        /// Callback receiving download progress
+        progress: Option<ProgressFn>,
+        /// Bandwidth limit, possibly shared with other downloads
+        throttle: Option<Arc<Throttle>>,
+    ) -> Result<u64> {
+        let hooks = Hooks { progress, throttle };
+        let part = with_suffix(&path, ".part");
+        let sidecar = with_suffix(&path, ".part.json");
+
+        let probe = self.probe(&source.url).await?;
+        let expected = match source.filesize {
+            0 => probe.size,
+            size => Some(size),
+        };
+
+        let written = match probe.size {
+            Some(size) if probe.ranges => {
+                let connections = connections.unwrap_or(source.chunks);
+                self.ranged(&source.url, &part, &sidecar, size, connections, &hooks)
+                    .await?
+            }
+            _ => self.single(&source.url, &part, expected, &hooks).await?,
+        };
+
+        let actual = tokio::fs::metadata(&part).await.map_err(Error::Io)?.len();
+
+        if let Some(expected) = expected {
+            if written != expected || actual != expected {
+                return Err(Error::SizeMismatch { expected, actual });
+            }
+        }
+
+        tokio::fs::rename(&part, &path).await.map_err(Error::Io)?;
+
+        if tokio::fs::try_exists(&sidecar).await.unwrap_or(false) {
+            tokio::fs::remove_file(&sidecar).await.map_err(Error::Io)?;
+        }
+
+        Ok(actual)
+    }
+
+    /// Open an unrestricted link as a seekable reader, without downloading it fully.
+    ///
+    /// Reads are served from a cache of `block_size` blocks; each miss fetches
+    /// the missing block plus `read_ahead` following blocks in a single range request.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{Debrid, Error, Result};
+    /// use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let file = client.downloader().remote()
+    ///         .source("https://example.com/d/ABCDEFGHIJKLMNO/video.mkv")
+    ///         .block_size(256 * 1024)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(mut file) = file {
+    ///         let mut header = [0u8; 4];
+    ///         file.seek(std::io::SeekFrom::Start(0)).await.ok();
+    ///         file.read_exact(&mut header).await.ok();
+    ///         println!("{} bytes, magic {:?}", file.len(), header);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn remote(
+        &self,
+        /// Unrestricted link, download or URL to open
+        #[builder(into)]
+        source: Source,
+        /// Size of a cached block in bytes, default: 1 MiB
+        block_size: Option<u64>,
+        /// Blocks fetched ahead of the one being read, default: 3
+        read_ahead: Option<u64>,
+        /// Max blocks kept in the cache, default: 16
+        cache_blocks: Option<usize>,
+    ) -> Result<RemoteFile> {
+        let len = match self.head(&source.url).await? {
+            Some(len) => len,
+            None => match self.probe(&source.url).await? {
+                Probe {
+                    size: Some(size),
+                    ranges: true,
+                } => size,
+                _ => return Err(Error::RangeNotSupported),
+            },
+        };
+
+        Ok(RemoteFile::new(
+            self.0.http().clone(),
+            source.url,
+            len,
+            block_size.unwrap_or(DEFAULT_BLOCK_SIZE),
+            read_ahead.unwrap_or(DEFAULT_READ_AHEAD),
+            cache_blocks.unwrap_or(DEFAULT_CACHE_BLOCKS),
+        ))
+    }
+}
+
+impl<'rd> Downloader<'rd> {
+    /// Learns the size of a remote file and whether it can be fetched by range.
+    pub(crate) async fn probe(&self, url: &str) -> Result<Probe> {
+        let response = self
+            .0
+            .http()
+            .get(url)
+            .header(RANGE, "bytes=0-0")
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(Error::Reqwest)?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+        };
+
+        Ok(match response.status() {
+            StatusCode::PARTIAL_CONTENT => Probe {
+                size: header(CONTENT_RANGE).and_then(parse_content_range_size),
+                ranges: true,
+            },
+            _ => Probe {
+                size: header(CONTENT_LENGTH).and_then(|v| v.parse().ok()),
+                ranges: false,
+            },
+        })
+    }
+
+    /// Learns the size of a remote file with a `HEAD` request, if it advertises range support.
+    async fn head(&self, url: &str) -> Result<Option<u64>> {
+        let response = self
+            .0
+            .http()
+            .head(url)
+            .send()
+            .await
+            .map_err(Error::Reqwest)?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let headers = response.headers();
+        let ranges = headers
+            .get(ACCEPT_RANGES)
+            .is_some_and(|v| v.as_bytes() == b"bytes");
+
+        Ok(headers
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .filter(|_| ranges))
+    }
+
+    /// Downloads a file over parallel range requests, resuming from the sidecar state.
+    async fn ranged(
+        &self,
+        url: &str,
+        part: &Path,
+        sidecar: &Path,
+        size: u64,
+        connections: u64,
+        hooks: &Hooks,
+    ) -> Result<u64> {
+        let resumable = match State::load(sidecar).await {
+            Some(state) if state.size == size => tokio::fs::metadata(part)
+                .await
+                .is_ok_and(|m| m.len() == size)
+                .then_some(state),
+            _ => None,
+        };
+
+        let state = match resumable {
+            Some(state) => state,
+            None => {
+                let file = tokio::fs::File::create(part).await.map_err(Error::Io)?;
+                file.set_len(size).await.map_err(Error::Io)?;
+
+                let state = State::new(size, connections);
+                state.save(sidecar).await?;
+                state
+            }
+        };
+
+        let n = state.ranges.len();
+        let state = Mutex::new(state);
+
+        let results = futures::future::join_all(
+            (0..n).map(|i| self.range(url, part, sidecar, &state, i, size, hooks)),
+        )
+        .await;
+
+        let state = state.into_inner();
+        state.save(sidecar).await?;
+
+        results.into_iter().collect::<Result<Vec<_>>>()?;
+
+        if !state.is_complete() {
+            return Err(Error::SizeMismatch {
+                expected: size,
+                actual: state.downloaded(),
+            });
+        }
+
+        Ok(state.downloaded())
+    }
+
+    /// Fetches the remaining bytes of a single range.
+    #[allow(clippy::too_many_arguments)]
+    async fn range(
+        &self,
+        url: &str,
+        part: &Path,
+        sidecar: &Path,
+        state: &Mutex<State>,
+        i: usize,
+        size: u64,
+        hooks: &Hooks,
+    ) -> Result<()> {
+        let (start, end) = {
+            let state = state.lock().await;
+            let range = &state.ranges[i];
+            (range.start + range.written, range.end)
+        };
+
+        if start > end {
+            return Ok(());
+        }
+
+        let response = self
+            .0
+            .http()
+            .get(url)
+            .header(RANGE, format!("bytes={}-{}", start, end))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(Error::Reqwest)?;
+
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(Error::RangeNotSupported);
+        }
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(part)
+            .await
+            .map_err(Error::Io)?;
+        file.seek(SeekFrom::Start(start)).await.map_err(Error::Io)?;
+
+        let mut position = start;
+        let mut unsaved = 0;
+        let mut stream = response.bytes_stream();
+
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes.map_err(Error::Reqwest)?;
+            let remaining = (end + 1 - position) as usize;
+            let bytes = &bytes[..bytes.len().min(remaining)];
+
+            file.write_all(bytes).await.map_err(Error::Io)?;
+            file.flush().await.map_err(Error::Io)?;
+
+            position += bytes.len() as u64;
+            unsaved += bytes.len() as u64;
+
+            let downloaded = {
+                let mut state = state.lock().await;
+                state.ranges[i].written += bytes.len() as u64;
+
+                if unsaved >= SAVE_INTERVAL {
+                    state.save(sidecar).await?;
+                    unsaved = 0;
+                }
+
+                state.downloaded()
+            };
+
+            hooks
+                .transferred(
+                    bytes.len() as u64,
+                    Progress {
+                        downloaded,
+                        total: Some(size),
+                    },
+                )
+                .await;
+
+            if position > end {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Downloads a file over a single connection, without resume support.
+    async fn single(
+        &self,
+        url: &str,
+        part: &Path,
+        total: Option<u64>,
+        hooks: &Hooks,
+    ) -> Result<u64> {
+        let response = self
+            .0
+            .http()
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(Error::Reqwest)?;
+
+        let mut file = tokio::fs::File::create(part).await.map_err(Error::Io)?;
+        let mut downloaded = 0;
+        let mut stream = response.bytes_stream();
+
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes.map_err(Error::Reqwest)?;
+
+            file.write_all(&bytes).await.map_err(Error::Io)?;
+            downloaded += bytes.len() as u64;
+
+            hooks
+                .transferred(bytes.len() as u64, Progress { downloaded, total })
+                .await;
+        }
+
+        file.flush().await.map_err(Error::Io)?;
+
+        Ok(downloaded)
+    }
+}
+
+/// Parses the total size out of a `Content-Range` header (e.g. "bytes 0-0/1234").
+pub(crate) fn parse_content_range_size(value: &str) -> Option<u64> {
+    value.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+/// Appends a suffix to a path (e.g. "file.mkv" -> "file.mkv.part").
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut s = OsString::from(path.as_os_str());
+    s.push(suffix);
+    s.into()
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, SeekFrom},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, FutureExt};
+use reqwest::{header::RANGE, Client, StatusCode};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
+
+use crate::{Error, Result};
+
+/// Default size of a cached block, in bytes.
+pub const DEFAULT_BLOCK_SIZE: u64 = 1024 * 1024;
+
+/// Default number of blocks fetched ahead of the one being read.
+pub const DEFAULT_READ_AHEAD: u64 = 3;
+
+/// Default number of blocks kept in the cache.
+pub const DEFAULT_CACHE_BLOCKS: usize = 16;
+
+/// Seekable reader over a remote file, fetched block by block with range requests.
+///
+/// Created through [`Downloader::remote`](super::Downloader::remote).
+pub struct RemoteFile {
+    client: Client,
+    url: String,
+    len: u64,
+    position: u64,
+    block_size: u64,
+    read_ahead: u64,
+    cache: Cache,
+    pending: Option<Pending>,
+}
+
+/// In-flight fetch of consecutive blocks, starting at `block`.
+struct Pending {
+    block: u64,
+    future: BoxFuture<'static, Result<Bytes>>,
+}
+
+impl RemoteFile {
+    pub(crate) fn new(
+        client: Client,
+        url: String,
+        len: u64,
+        block_size: u64,
+        read_ahead: u64,
+        cache_blocks: usize,
+    ) -> Self {
+        Self {
+            client,
+            url,
+            len,
+            position: 0,
+            block_size: block_size.max(1),
+            read_ahead,
+            cache: Cache::new(cache_blocks.max(read_ahead as usize + 1)),
+            pending: None,
+        }
+    }
+
+    /// Size of the remote file in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether or not the remote file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Current read position in bytes.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// URL of the remote file.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Starts fetching `block` and the blocks read ahead of it.
+    fn fetch(&self, block: u64) -> Pending {
+        let start = block * self.block_size;
+        let end = ((block + 1 + self.read_ahead) * self.block_size).min(self.len) - 1;
+
+        let client = self.client.clone();
+        let url = self.url.clone();
+
+        let future = async move {
+            let response = client
+                .get(url)
+                .header(RANGE, format!("bytes={}-{}", start, end))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(Error::Reqwest)?;
+
+            if response.status() != StatusCode::PARTIAL_CONTENT {
+                return Err(Error::RangeNotSupported);
+            }
+
+            let bytes = response.bytes().await.map_err(Error::Reqwest)?;
+
+            if bytes.len() as u64 != end + 1 - start {
+                return Err(Error::SizeMismatch {
+                    expected: end + 1 - start,
+                    actual: bytes.len() as u64,
+                });
+            }
+
+            Ok(bytes)
+        };
+
+        Pending {
+            block,
+            future: future.boxed(),
+        }
+    }
+}
+
+impl AsyncRead for RemoteFile {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.position >= this.len || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+
+            let block = this.position / this.block_size;
+
+            if let Some(data) = this.cache.get(block) {
+                let offset = (this.position - block * this.block_size) as usize;
+                let n = buf.remaining().min(data.len() - offset);
+
+                buf.put_slice(&data[offset..offset + n]);
+                this.position += n as u64;
+
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.pending.as_ref().map(|p| p.block) != Some(block) {
+                this.pending = Some(this.fetch(block));
+            }
+
+            let pending = this.pending.as_mut().expect("pending fetch");
+
+            match pending.future.poll_unpin(cx) {
+                Poll::Ready(Ok(bytes)) => {
+                    let first = pending.block;
+                    this.pending = None;
+
+                    for (i, start) in (0..bytes.len())
+                        .step_by(this.block_size as usize)
+                        .enumerate()
+                    {
+                        let end = (start + this.block_size as usize).min(bytes.len());
+                        this.cache.insert(first + i as u64, bytes.slice(start..end));
+                    }
+                }
+                Poll::Ready(Err(e)) => {
+                    this.pending = None;
+                    return Poll::Ready(Err(io::Error::other(e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl AsyncSeek for RemoteFile {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        let position = match position {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => this.len.checked_add_signed(n),
+            SeekFrom::Current(n) => this.position.checked_add_signed(n),
+        };
+
+        match position {
+            Some(position) => {
+                this.position = position;
+                Ok(())
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+/// Least-recently-used cache of fetched blocks.
+struct Cache {
+    capacity: usize,
+    blocks: HashMap<u64, Bytes>,
+    order: VecDeque<u64>,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, block: u64) -> Option<Bytes> {
+        let data = self.blocks.get(&block)?.clone();
+        self.touch(block);
+        Some(data)
+    }
+
+    fn insert(&mut self, block: u64, data: Bytes) {
+        self.blocks.insert(block, data);
+        self.touch(block);
+
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.blocks.remove(&evicted);
+            }
+        }
+    }
+
+    fn touch(&mut self, block: u64) {
+        self.order.retain(|b| *b != block);
+        self.order.push_back(block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = Cache::new(2);
+
+        cache.insert(0, Bytes::from_static(b"a"));
+        cache.insert(1, Bytes::from_static(b"b"));
+        assert!(cache.get(0).is_some());
+
+        cache.insert(2, Bytes::from_static(b"c"));
+
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_some());
+    }
+}
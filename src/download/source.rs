@@ -0,0 +1,78 @@
+use crate::models::{downloads::Download, unrestrict::Link};
+
+/// Represents a remote file that can be downloaded to disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    /// Generated download link
+    pub url: String,
+    /// Filename
+    pub filename: String,
+    /// Size of the file in bytes, 0 if unknown
+    pub filesize: u64,
+    /// Max chunks allowed
+    pub chunks: u64,
+    /// MIME type of the file, if known
+    pub mime_type: Option<String>,
+}
+
+impl From<&str> for Source {
+    /// Wraps a bare download URL, of unknown size, fetched over a single chunk.
+    fn from(url: &str) -> Self {
+        let filename = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| path.rsplit('/').next())
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            url: url.to_string(),
+            filename,
+            filesize: 0,
+            chunks: 1,
+            mime_type: None,
+        }
+    }
+}
+
+impl From<String> for Source {
+    fn from(url: String) -> Self {
+        Self::from(url.as_str())
+    }
+}
+
+impl From<&Link> for Source {
+    fn from(link: &Link) -> Self {
+        Self {
+            url: link.download.clone(),
+            filename: link.filename.clone(),
+            filesize: link.filesize,
+            chunks: link.chunks,
+            mime_type: link.mime_type.clone(),
+        }
+    }
+}
+
+impl From<Link> for Source {
+    fn from(link: Link) -> Self {
+        Self::from(&link)
+    }
+}
+
+impl From<&Download> for Source {
+    fn from(download: &Download) -> Self {
+        Self {
+            url: download.download.clone(),
+            filename: download.filename.clone(),
+            filesize: download.filesize,
+            chunks: download.chunks,
+            mime_type: Some(download.mime_type.clone()),
+        }
+    }
+}
+
+impl From<Download> for Source {
+    fn from(download: Download) -> Self {
+        Self::from(&download)
+    }
+}
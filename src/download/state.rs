@@ -0,0 +1,106 @@
+use std::path::Path;
+
+use crate::{Error, Result};
+
+/// Sidecar state of a partially downloaded file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct State {
+    /// Total size of the file in bytes
+    pub size: u64,
+    /// Byte ranges fetched by each connection
+    pub ranges: Vec<Range>,
+}
+
+/// Inclusive byte range of a file and how much of it is on disk.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Range {
+    /// First byte of the range
+    pub start: u64,
+    /// Last byte of the range
+    pub end: u64,
+    /// Bytes of the range already written to disk
+    pub written: u64,
+}
+
+impl Range {
+    /// Length of the range in bytes.
+    pub fn len(&self) -> u64 {
+        self.end + 1 - self.start
+    }
+
+    /// Whether or not the range is fully written.
+    pub fn is_complete(&self) -> bool {
+        self.written >= self.len()
+    }
+}
+
+impl State {
+    /// Splits a file of `size` bytes into (at most) `n` equal ranges.
+    pub fn new(size: u64, n: u64) -> Self {
+        let n = n.clamp(1, size.max(1));
+        let step = size.div_ceil(n);
+
+        let ranges = (0..n)
+            .map(|i| i * step)
+            .take_while(|start| *start < size)
+            .map(|start| Range {
+                start,
+                end: (start + step).min(size) - 1,
+                written: 0,
+            })
+            .collect();
+
+        Self { size, ranges }
+    }
+
+    /// Total bytes written to disk.
+    pub fn downloaded(&self) -> u64 {
+        self.ranges.iter().map(|r| r.written.min(r.len())).sum()
+    }
+
+    /// Whether or not every range is fully written.
+    pub fn is_complete(&self) -> bool {
+        self.ranges.iter().all(Range::is_complete)
+    }
+
+    /// Loads the state from disk, if present and readable.
+    pub async fn load(path: &Path) -> Option<Self> {
+        let contents = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&contents).ok()
+    }
+
+    /// Persists the state to disk.
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_vec(self).map_err(Error::Json)?;
+        tokio::fs::write(path, contents).await.map_err(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ranges() {
+        let state = State::new(10, 3);
+
+        assert_eq!(
+            state
+                .ranges
+                .iter()
+                .map(|r| (r.start, r.end))
+                .collect::<Vec<_>>(),
+            vec![(0, 3), (4, 7), (8, 9)]
+        );
+        assert_eq!(state.ranges.iter().map(Range::len).sum::<u64>(), 10);
+    }
+
+    #[test]
+    fn test_split_more_ranges_than_bytes() {
+        let state = State::new(2, 32);
+
+        assert_eq!(state.ranges.len(), 2);
+        assert_eq!(state.downloaded(), 0);
+        assert!(!state.is_complete());
+    }
+}
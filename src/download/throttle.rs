@@ -0,0 +1,81 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Token bucket limiting the bandwidth shared by concurrent downloads.
+#[derive(Debug)]
+pub struct Throttle {
+    bytes_per_second: f64,
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available bytes, negative when in debt
+    tokens: f64,
+    /// Last time the bucket was refilled
+    refilled: Instant,
+}
+
+impl Throttle {
+    /// Creates a throttle allowing `bytes_per_second` on average, with bursts of up to one second.
+    pub fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1) as f64;
+
+        Self {
+            bytes_per_second,
+            state: Mutex::new(Bucket {
+                tokens: bytes_per_second,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// Bandwidth limit in bytes per second.
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second as u64
+    }
+
+    /// Accounts for `bytes` transferred, sleeping as long as needed to stay under the limit.
+    pub async fn acquire(&self, bytes: u64) {
+        let wait = {
+            let mut bucket = self.state.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+
+            bucket.tokens =
+                (bucket.tokens + elapsed * self.bytes_per_second).min(self.bytes_per_second);
+            bucket.refilled = now;
+            bucket.tokens -= bytes as f64;
+
+            if bucket.tokens < 0.0 {
+                Duration::from_secs_f64(-bucket.tokens / self.bytes_per_second)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_throttle_limits_bandwidth() {
+        let throttle = Throttle::new(10_000);
+        let start = Instant::now();
+
+        // The first second is available as a burst
+        throttle.acquire(10_000).await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+
+        throttle.acquire(2_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(190));
+    }
+}
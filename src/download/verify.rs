@@ -0,0 +1,484 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    ops::RangeInclusive,
+    path::{Component, Path, PathBuf},
+};
+
+use bon::bon;
+use sha1::{Digest, Sha1};
+
+use super::{
+    bencode::{self, Value},
+    state::{Range, State},
+    with_suffix, Downloader,
+};
+use crate::{
+    models::{ids::InfoHash, torrents::TorrentInfo},
+    Error, Result,
+};
+
+/// Size of the buffer files are hashed through.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Piece layout and hashes of a torrent, used to verify downloaded files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metainfo {
+    /// SHA1 info hash of the torrent
+    pub info_hash: InfoHash,
+    /// Torrent name
+    pub name: String,
+    /// Size of a piece in bytes
+    pub piece_length: u64,
+    /// SHA-1 hash of each piece
+    pub pieces: Vec<[u8; 20]>,
+    /// Files of the torrent, in piece order
+    pub files: Vec<MetaFile>,
+}
+
+/// Represents a file of a torrent's piece layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetaFile {
+    /// Path to the file inside the torrent, starting with "/" like `TorrentFile.path`
+    pub path: String,
+    /// Size of the file in bytes
+    pub length: u64,
+}
+
+impl Metainfo {
+    /// Parses the contents of a `.torrent` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidTorrent(reason.to_string());
+
+        let root = bencode::decode(bytes).ok_or_else(|| invalid("malformed bencode"))?;
+        let info = root.get("info").ok_or_else(|| invalid("missing info"))?;
+        let raw = info
+            .raw()
+            .ok_or_else(|| invalid("info is not a dictionary"))?;
+
+        let name = info
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid("missing name"))?
+            .to_string();
+
+        let piece_length = info
+            .get("piece length")
+            .and_then(Value::as_int)
+            .filter(|n| *n > 0)
+            .ok_or_else(|| invalid("missing piece length"))? as u64;
+
+        let files = match (info.get("length"), info.get("files")) {
+            (Some(length), None) => vec![MetaFile {
+                path: format!("/{}", name),
+                length: length
+                    .as_int()
+                    .and_then(|n| u64::try_from(n).ok())
+                    .ok_or_else(|| invalid("invalid length"))?,
+            }],
+            (None, Some(files)) => files
+                .as_list()
+                .ok_or_else(|| invalid("invalid files"))?
+                .iter()
+                .map(|file| {
+                    let length = file
+                        .get("length")
+                        .and_then(Value::as_int)
+                        .and_then(|n| u64::try_from(n).ok());
+                    let path = file
+                        .get("path")
+                        .and_then(Value::as_list)
+                        .map(|parts| parts.iter().map(Value::as_str).collect::<Option<Vec<_>>>());
+
+                    match (length, path) {
+                        (Some(length), Some(Some(parts))) if !parts.is_empty() => Ok(MetaFile {
+                            path: format!("/{}", parts.join("/")),
+                            length,
+                        }),
+                        _ => Err(invalid("invalid file entry")),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?,
+            _ => return Err(invalid("expected either length or files")),
+        };
+
+        let pieces = info
+            .get("pieces")
+            .and_then(Value::as_bytes)
+            .filter(|p| p.len() % 20 == 0)
+            .ok_or_else(|| invalid("invalid pieces"))?
+            .chunks_exact(20)
+            .map(|hash| hash.try_into().expect("20 bytes chunk"))
+            .collect();
+
+        let info_hash = Sha1::digest(raw)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        let metainfo = Self {
+            info_hash: InfoHash::from(info_hash),
+            name,
+            piece_length,
+            pieces,
+            files,
+        };
+        metainfo.validate()?;
+
+        Ok(metainfo)
+    }
+
+    /// Builds the piece layout of a RealDebrid torrent from known piece hashes,
+    /// with file sizes taken from `TorrentFile.bytes`.
+    pub fn from_pieces(
+        torrent: &TorrentInfo,
+        piece_length: u64,
+        pieces: Vec<[u8; 20]>,
+    ) -> Result<Self> {
+        let mut files = torrent.files.iter().collect::<Vec<_>>();
+        files.sort_by_key(|file| file.id);
+
+        let metainfo = Self {
+            info_hash: torrent.hash.clone(),
+            name: torrent.original_filename.clone(),
+            piece_length,
+            pieces,
+            files: files
+                .into_iter()
+                .map(|file| MetaFile {
+                    path: file.path.clone(),
+                    length: file.bytes,
+                })
+                .collect(),
+        };
+        metainfo.validate()?;
+
+        Ok(metainfo)
+    }
+
+    /// Total size of the torrent in bytes, saturating at `u64::MAX`.
+    pub fn len(&self) -> u64 {
+        self.total().unwrap_or(u64::MAX)
+    }
+
+    /// Whether or not the torrent holds no data.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn total(&self) -> Option<u64> {
+        self.files
+            .iter()
+            .try_fold(0u64, |total, file| total.checked_add(file.length))
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |reason: &str| Error::InvalidTorrent(reason.to_string());
+
+        if let Some(file) = self.files.iter().find(|file| !is_safe(&file.path)) {
+            return Err(invalid(&format!("unsafe file path {}", file.path)));
+        }
+
+        let total = self
+            .total()
+            .ok_or_else(|| invalid("torrent size overflows"))?;
+
+        if self.piece_length == 0 || self.pieces.len() as u64 != total.div_ceil(self.piece_length) {
+            return Err(invalid("piece count does not match the torrent size"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether or not a torrent file path stays inside the download directory, that is
+/// a "/" followed by non-empty names, none being a "." or ".." component.
+fn is_safe(path: &str) -> bool {
+    path.strip_prefix('/').is_some_and(|relative| {
+        relative.split('/').all(|name| {
+            !name.contains('\\')
+                && matches!(
+                    Path::new(name).components().collect::<Vec<_>>()[..],
+                    [Component::Normal(_)]
+                )
+        })
+    })
+}
+
+/// Result of verifying downloaded files against a torrent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    /// Report of each file, in torrent order
+    pub files: Vec<FileReport>,
+    /// Pieces which could not be checked because they span a missing file
+    pub unverified_pieces: Vec<u64>,
+}
+
+impl Verification {
+    /// Whether or not every file is present, of the right size and uncorrupted.
+    pub fn is_valid(&self) -> bool {
+        self.files.iter().all(FileReport::is_valid)
+    }
+}
+
+/// Verification report of a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    /// Path to the file inside the torrent
+    pub path: String,
+    /// Path to the file on disk
+    pub local: PathBuf,
+    /// Expected size of the file in bytes
+    pub expected: u64,
+    /// Actual size of the file in bytes, `None` if missing
+    pub actual: Option<u64>,
+    /// Byte ranges of the file failing verification, merged when adjacent
+    pub corrupt: Vec<RangeInclusive<u64>>,
+}
+
+impl FileReport {
+    /// Whether or not the file is present, of the right size and uncorrupted.
+    pub fn is_valid(&self) -> bool {
+        self.actual == Some(self.expected) && self.corrupt.is_empty()
+    }
+
+    /// Prepare the file so that [`Downloader::file`] re-fetches only its corrupt ranges.
+    ///
+    /// The file is moved back to `<local>.part`, with a sidecar state marking every
+    /// range but the corrupt ones as downloaded. Missing files and files of the wrong
+    /// size are left alone, and are downloaded again from scratch.
+    /// Returns whether or not the file was prepared.
+    pub async fn prepare_refetch(&self) -> Result<bool> {
+        if self.actual != Some(self.expected) || self.corrupt.is_empty() {
+            return Ok(false);
+        }
+
+        let mut ranges = Vec::new();
+        let mut next = 0;
+
+        for corrupt in &self.corrupt {
+            if *corrupt.start() > next {
+                ranges.push(Range {
+                    start: next,
+                    end: corrupt.start() - 1,
+                    written: corrupt.start() - next,
+                });
+            }
+
+            ranges.push(Range {
+                start: *corrupt.start(),
+                end: *corrupt.end(),
+                written: 0,
+            });
+            next = corrupt.end() + 1;
+        }
+
+        if next < self.expected {
+            ranges.push(Range {
+                start: next,
+                end: self.expected - 1,
+                written: self.expected - next,
+            });
+        }
+
+        let state = State {
+            size: self.expected,
+            ranges,
+        };
+
+        let part = with_suffix(&self.local, ".part");
+        tokio::fs::rename(&self.local, &part)
+            .await
+            .map_err(Error::Io)?;
+        state.save(&with_suffix(&self.local, ".part.json")).await?;
+
+        Ok(true)
+    }
+}
+
+#[bon]
+impl<'rd> Downloader<'rd> {
+    /// Verify downloaded files piece by piece against a torrent's SHA-1 hashes.
+    ///
+    /// Files are looked up under `directory` by their path inside the torrent, or by
+    /// filename only when `flatten` is set, as laid out by
+    /// [`DownloadManager::add_torrent`](super::DownloadManager::add_torrent).
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{download::Metainfo, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     if let Ok(bytes) = std::fs::read("release.torrent") {
+    ///         let metainfo = Metainfo::from_bytes(&bytes)?;
+    ///
+    ///         let verification = client.downloader().verify()
+    ///             .metainfo(&metainfo)
+    ///             .directory("downloads")
+    ///             .send()
+    ///             .await?;
+    ///
+    ///         for file in verification.files.iter().filter(|f| !f.is_valid()) {
+    ///             println!("{} is corrupt: {:?}", file.path, file.corrupt);
+    ///             file.prepare_refetch().await?;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn verify(
+        &self,
+        /// Piece layout and hashes of the torrent
+        metainfo: &Metainfo,
+        /// Directory holding the downloaded files
+        #[builder(into)]
+        directory: PathBuf,
+        /// Files are stored by filename only, default: false
+        flatten: Option<bool>,
+    ) -> Result<Verification> {
+        let metainfo = metainfo.clone();
+        let flatten = flatten.unwrap_or(false);
+
+        tokio::task::spawn_blocking(move || verify(&metainfo, &directory, flatten))
+            .await
+            .map_err(|e| Error::Io(std::io::Error::other(e)))?
+    }
+}
+
+/// File opened for verification.
+struct Local {
+    file: Option<File>,
+    len: u64,
+}
+
+fn verify(metainfo: &Metainfo, directory: &Path, flatten: bool) -> Result<Verification> {
+    // Fields are public, so paths and sizes are checked again before being used
+    metainfo.validate()?;
+
+    let mut files = Vec::with_capacity(metainfo.files.len());
+    let mut locals = Vec::with_capacity(metainfo.files.len());
+    let mut offsets = Vec::with_capacity(metainfo.files.len());
+    let mut offset = 0;
+
+    for file in &metainfo.files {
+        let relative = match flatten {
+            true => file.path.rsplit('/').next().unwrap_or_default(),
+            false => file.path.trim_start_matches('/'),
+        };
+        let local = directory.join(relative);
+
+        let opened = File::open(&local).ok();
+        let actual = opened
+            .as_ref()
+            .and_then(|f| f.metadata().ok())
+            .map(|m| m.len());
+
+        locals.push(Local {
+            file: opened,
+            len: actual.unwrap_or(0),
+        });
+        files.push(FileReport {
+            path: file.path.clone(),
+            local,
+            expected: file.length,
+            actual,
+            corrupt: Vec::new(),
+        });
+        offsets.push(offset);
+        offset += file.length;
+    }
+
+    let total = offset;
+    let mut unverified_pieces = Vec::new();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+
+    for (piece, hash) in metainfo.pieces.iter().enumerate() {
+        let start = piece as u64 * metainfo.piece_length;
+        let end = start.saturating_add(metainfo.piece_length).min(total);
+
+        // (file index, first byte in file, length) of each file overlapping the piece
+        let spans = metainfo
+            .files
+            .iter()
+            .enumerate()
+            .filter_map(|(i, file)| {
+                let from = start.max(offsets[i]);
+                let to = end.min(offsets[i] + file.length);
+                (from < to).then(|| (i, from - offsets[i], to - from))
+            })
+            .collect::<Vec<_>>();
+
+        if spans.iter().any(|(i, _, _)| locals[*i].file.is_none()) {
+            unverified_pieces.push(piece as u64);
+            continue;
+        }
+
+        let mut hasher = Sha1::new();
+        let mut valid = true;
+
+        for (i, from, len) in &spans {
+            let local = &mut locals[*i];
+
+            let read = match (&mut local.file, from + len <= local.len) {
+                (Some(file), true) => {
+                    hash_span(file, *from, *len, &mut buffer, &mut hasher).is_ok()
+                }
+                _ => false,
+            };
+
+            if !read {
+                valid = false;
+                break;
+            }
+        }
+
+        if valid && hasher.finalize().as_slice() == &hash[..] {
+            continue;
+        }
+
+        for (i, from, len) in spans {
+            let corrupt = &mut files[i].corrupt;
+            let range = from..=from + len - 1;
+
+            match corrupt.last_mut() {
+                Some(last) if *last.end() + 1 == from => *last = *last.start()..=*range.end(),
+                _ => corrupt.push(range),
+            }
+        }
+    }
+
+    Ok(Verification {
+        files,
+        unverified_pieces,
+    })
+}
+
+/// Hashes `len` bytes of a file from `from`, reading through `buffer` since pieces
+/// declared by a torrent can be of any size.
+fn hash_span(
+    file: &mut File,
+    from: u64,
+    len: u64,
+    buffer: &mut [u8],
+    hasher: &mut Sha1,
+) -> std::io::Result<()> {
+    file.seek(SeekFrom::Start(from))?;
+
+    let mut left = len;
+    while left > 0 {
+        let size = left.min(buffer.len() as u64) as usize;
+        let chunk = &mut buffer[..size];
+        file.read_exact(chunk)?;
+        hasher.update(&*chunk);
+        left -= chunk.len() as u64;
+    }
+
+    Ok(())
+}
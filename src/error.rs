@@ -1,4 +1,4 @@
-use std::{fmt, num::ParseIntError};
+use std::{fmt, io, num::ParseIntError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -19,6 +19,60 @@ pub enum Error {
 
     #[error("Regex error: {0}")]
     Regex(regex::Error),
+
+    #[error("Invalid identifier: {0}")]
+    InvalidIdentifier(String),
+
+    #[error("IO error: {0}")]
+    Io(io::Error),
+
+    #[error("JSON error: {0}")]
+    Json(serde_json::Error),
+
+    #[error("Size mismatch: expected {expected} bytes, got {actual} bytes")]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    #[error("Range requests are not supported by the server")]
+    RangeNotSupported,
+
+    #[error("Invalid torrent file: {0}")]
+    InvalidTorrent(String),
+
+    #[error("Unsafe path in archive: {0}")]
+    UnsafePath(String),
+
+    #[error("Invalid container file: {0}")]
+    InvalidContainer(String),
+
+    #[error("Invalid resolution: {0}")]
+    InvalidResolution(String),
+
+    #[error("Invalid value \"{value}\" for setting {name}")]
+    InvalidSetting { name: String, value: String },
+
+    #[error("Invalid profile: {0}")]
+    InvalidProfile(String),
+
+    #[error("Stream not available: {0}")]
+    UnavailableStream(String),
+
+    #[error(
+        "Quota of {host} would be exceeded: {needed} needed with a margin of {margin}, {left} left"
+    )]
+    QuotaWouldBeExceeded {
+        host: String,
+        needed: u64,
+        margin: u64,
+        left: u64,
+    },
+
+    #[cfg(feature = "extract")]
+    #[error("ZIP error: {0}")]
+    Zip(zip::result::ZipError),
+
+    #[cfg(feature = "extract")]
+    #[error("7z error: {0}")]
+    SevenZip(sevenz_rust::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,220 @@
+use std::{collections::HashSet, sync::LazyLock};
+
+use regex::{Regex, RegexSet};
+
+use crate::{Error, Result};
+
+/// Loose URL pattern, narrowed down by the host regexes.
+static CANDIDATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)https?://[^\s"'<>\[\]{}|\\^`]+"#).expect("valid URL regex"));
+
+/// Kind of a supported link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// Single file, unrestricted with `unrestrict().link()`
+    File,
+    /// Folder of files, expanded with `unrestrict().folder()`
+    Folder,
+}
+
+/// Represents a supported link found in a text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExtractedLink {
+    /// Supported link
+    pub url: String,
+    /// Whether the link points to a file or a folder
+    pub kind: LinkKind,
+    /// Host main domain, if it is a supported domain
+    pub host: Option<String>,
+}
+
+/// Finds supported hoster links in arbitrary text, such as pasted links, HTML or forum posts.
+///
+/// Created from the host regexes with [`HostsApi::extractor`](crate::apis::hosts::HostsApi::extractor),
+/// or from already fetched regexes with [`LinkExtractor::new`].
+#[derive(Debug, Clone)]
+pub struct LinkExtractor {
+    files: Matcher,
+    folders: Matcher,
+    domains: Vec<String>,
+}
+
+/// Host regexes of one kind, compiled once into a set.
+#[derive(Debug, Clone)]
+struct Matcher {
+    set: RegexSet,
+    regexes: Vec<Regex>,
+}
+
+impl Matcher {
+    fn new(regexes: &[Regex]) -> Result<Self> {
+        Ok(Self {
+            set: RegexSet::new(regexes.iter().map(Regex::as_str)).map_err(Error::Regex)?,
+            regexes: regexes.to_vec(),
+        })
+    }
+
+    /// Returns the first supported link inside `candidate`, if any.
+    fn find<'c>(&self, candidate: &'c str) -> Option<&'c str> {
+        self.set
+            .matches(candidate)
+            .iter()
+            .filter_map(|i| self.regexes[i].find(candidate))
+            .min_by_key(|m| (m.start(), usize::MAX - m.end()))
+            .map(|m| m.as_str())
+    }
+}
+
+impl LinkExtractor {
+    /// Creates a link extractor from file and folder regexes and supported domains,
+    /// as returned by `hosts().regex()`, `hosts().regex_folder()` and `hosts().domains()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{hosts::{LinkExtractor, LinkKind}, Result};
+    /// use regex::Regex;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let extractor = LinkExtractor::new(
+    ///         &[Regex::new(r"https?://(\w+\.)?example\.com/[0-9a-z]{12}").unwrap()],
+    ///         &[],
+    ///         vec!["example.com".to_string()],
+    ///     )?;
+    ///
+    ///     let links = extractor.extract("Mirror: https://www.example.com/abcdefghijkl.");
+    ///
+    ///     assert_eq!(links[0].url, "https://www.example.com/abcdefghijkl");
+    ///     assert_eq!(links[0].kind, LinkKind::File);
+    ///     assert_eq!(links[0].host.as_deref(), Some("example.com"));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn new(files: &[Regex], folders: &[Regex], domains: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            files: Matcher::new(files)?,
+            folders: Matcher::new(folders)?,
+            domains: domains
+                .into_iter()
+                .map(|domain| domain.to_ascii_lowercase())
+                .collect(),
+        })
+    }
+
+    /// Extract deduplicated supported links from a text, in order of appearance.
+    pub fn extract(&self, text: &str) -> Vec<ExtractedLink> {
+        let mut seen = HashSet::new();
+
+        CANDIDATE
+            .find_iter(text)
+            .filter_map(|candidate| {
+                let candidate = candidate.as_str().replace("&amp;", "&");
+                self.classify(&candidate)
+            })
+            .filter(|link| seen.insert(link.url.clone()))
+            .collect()
+    }
+
+    /// Classify a single link, returning `None` if it is not supported.
+    /// Folder patterns take precedence, being more specific than file ones.
+    pub fn classify(&self, url: &str) -> Option<ExtractedLink> {
+        let (url, kind) = match self.folders.find(url) {
+            Some(url) => (url, LinkKind::Folder),
+            None => (self.files.find(url)?, LinkKind::File),
+        };
+
+        Some(ExtractedLink {
+            url: url.to_string(),
+            kind,
+            host: self.host(url),
+        })
+    }
+
+    /// Supported domain of a link, matching subdomains too.
+    pub fn host(&self, url: &str) -> Option<String> {
+        let host = host_of(url)?;
+
+        self.domains
+            .iter()
+            .filter(|domain| is_same_site(&host, domain))
+            .max_by_key(|domain| domain.len())
+            .cloned()
+    }
+}
+
+/// Lowercase host of a URL, without credentials nor port.
+pub(crate) fn host_of(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// Whether or not a lowercase host is a domain or one of its subdomains.
+pub(crate) fn is_same_site(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|sub| sub.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> LinkExtractor {
+        LinkExtractor::new(
+            &[Regex::new(r"(http|https)://(\w+\.)?example\.com/[0-9a-z]{12}").unwrap()],
+            &[Regex::new(r"(http|https)://(\w+\.)?example\.com/folder/[0-9a-z]+").unwrap()],
+            vec!["example.com".to_string(), "other.com".to_string()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extract_links_from_html() {
+        let html = r#"
+            <a href="https://example.com/abcdefghijkl">file</a>
+            <a href='https://dl.example.com/folder/abc123?x=1&amp;y=2'>folder</a>
+            (https://example.com/abcdefghijkl), https://other.com/abcdefghijkl
+        "#;
+
+        assert_eq!(
+            extractor().extract(html),
+            vec![
+                ExtractedLink {
+                    url: "https://example.com/abcdefghijkl".to_string(),
+                    kind: LinkKind::File,
+                    host: Some("example.com".to_string()),
+                },
+                ExtractedLink {
+                    url: "https://dl.example.com/folder/abc123".to_string(),
+                    kind: LinkKind::Folder,
+                    host: Some("example.com".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_of() {
+        assert_eq!(
+            host_of("https://user:pw@Sub.Example.com:8080/a?b").as_deref(),
+            Some("sub.example.com")
+        );
+        assert_eq!(host_of("example.com/file").as_deref(), Some("example.com"));
+        assert_eq!(host_of("https:///file"), None);
+    }
+
+    #[test]
+    fn test_host_matches_subdomains_only() {
+        let extractor = extractor();
+
+        assert_eq!(
+            extractor.host("https://www.example.com/x").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(extractor.host("https://notexample.com/x"), None);
+    }
+}
@@ -0,0 +1,9 @@
+mod extractor;
+mod monitor;
+mod passwords;
+mod registry;
+
+pub use extractor::*;
+pub use monitor::*;
+pub use passwords::*;
+pub use registry::*;
@@ -0,0 +1,309 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use bon::bon;
+use futures::Stream;
+
+use crate::{
+    models::hosts::{HostInfo, HostStatus},
+    Debrid, Result,
+};
+
+/// Default time between two polls of the hosts status.
+pub const DEFAULT_MONITOR_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Change of a host status between two polls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostEvent {
+    /// Host went down on RealDebrid
+    Down { domain: String, check_time: String },
+    /// Host is back up on RealDebrid
+    Up { domain: String, check_time: String },
+    /// Host is no longer supported by RealDebrid
+    Unsupported { domain: String, check_time: String },
+    /// Host is down on RealDebrid while up on some competitors
+    CompetitorsUp {
+        domain: String,
+        /// Competitors the host is up on, sorted
+        competitors: Vec<String>,
+        check_time: String,
+    },
+}
+
+impl HostEvent {
+    /// Main domain of the host the event is about.
+    pub fn domain(&self) -> &str {
+        match self {
+            HostEvent::Down { domain, .. }
+            | HostEvent::Up { domain, .. }
+            | HostEvent::Unsupported { domain, .. }
+            | HostEvent::CompetitorsUp { domain, .. } => domain,
+        }
+    }
+
+    /// When RealDebrid checked the host status, as returned by the API.
+    pub fn check_time(&self) -> &str {
+        match self {
+            HostEvent::Down { check_time, .. }
+            | HostEvent::Up { check_time, .. }
+            | HostEvent::Unsupported { check_time, .. }
+            | HostEvent::CompetitorsUp { check_time, .. } => check_time,
+        }
+    }
+}
+
+/// Diff two consecutive hosts status, sorted by domain.
+///
+/// Hosts missing from `previous` are considered new and only emit events on later changes.
+pub fn diff_status(
+    previous: &HashMap<String, HostInfo>,
+    current: &HashMap<String, HostInfo>,
+) -> Vec<HostEvent> {
+    let mut domains = current.keys().collect::<Vec<_>>();
+    domains.sort();
+
+    let mut events = Vec::new();
+
+    for domain in domains {
+        let (Some(before), after) = (previous.get(domain), &current[domain]) else {
+            continue;
+        };
+
+        let domain = domain.clone();
+        let check_time = after.check_time.clone();
+
+        if is_supported(before) && !is_supported(after) {
+            events.push(HostEvent::Unsupported { domain, check_time });
+            continue;
+        }
+
+        match (&before.status, &after.status) {
+            (HostStatus::Down, HostStatus::Down) => {}
+            (_, HostStatus::Down) => events.push(HostEvent::Down {
+                domain: domain.clone(),
+                check_time: check_time.clone(),
+            }),
+            (HostStatus::Down, HostStatus::Up) => events.push(HostEvent::Up {
+                domain: domain.clone(),
+                check_time: check_time.clone(),
+            }),
+            _ => {}
+        }
+
+        let competitors = competitors_up(after);
+
+        if !competitors.is_empty() && competitors != competitors_up(before) {
+            events.push(HostEvent::CompetitorsUp {
+                domain,
+                competitors,
+                check_time,
+            });
+        }
+    }
+
+    events
+}
+
+fn is_supported(info: &HostInfo) -> bool {
+    info.supported && info.status != HostStatus::Unsupported
+}
+
+/// Competitors a host is up on while down on RealDebrid, sorted.
+fn competitors_up(info: &HostInfo) -> Vec<String> {
+    if info.status != HostStatus::Down {
+        return Vec::new();
+    }
+
+    let mut competitors = info
+        .competitors_status
+        .iter()
+        .filter(|(_, competitor)| competitor.status == HostStatus::Up)
+        .map(|(domain, _)| domain.clone())
+        .collect::<Vec<_>>();
+    competitors.sort();
+
+    competitors
+}
+
+/// Polls the hosts status, diffing consecutive snapshots into [`HostEvent`]s.
+pub struct HostMonitor {
+    debrid: Debrid,
+    interval: Duration,
+}
+
+#[bon]
+impl HostMonitor {
+    /// Creates a new host status monitor.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use debrid::{hosts::{HostEvent, HostMonitor}, Debrid, Error, Result};
+    /// use futures::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let events = HostMonitor::builder()
+    ///         .debrid(client)
+    ///         .build()
+    ///         .events();
+    ///     let mut events = std::pin::pin!(events);
+    ///
+    ///     while let Some(event) = events.next().await {
+    ///         match event {
+    ///             Ok(HostEvent::Down { domain, check_time }) => {
+    ///                 println!("{} went down at {}", domain, check_time)
+    ///             }
+    ///             Ok(event) => println!("{:?}", event),
+    ///             Err(err) => eprintln!("Failed to poll hosts status: {}", err),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// RealDebrid client
+        debrid: Debrid,
+        /// Time between two polls, default: 5 minutes
+        interval: Option<Duration>,
+    ) -> Self {
+        Self {
+            debrid,
+            interval: interval.unwrap_or(DEFAULT_MONITOR_INTERVAL),
+        }
+    }
+}
+
+impl HostMonitor {
+    /// Poll the hosts status forever, yielding changes as they are seen.
+    ///
+    /// The first poll only sets the baseline. Failed polls are yielded as errors
+    /// without ending the stream, and the next poll diffs against the last successful one.
+    pub fn events(self) -> impl Stream<Item = Result<HostEvent>> {
+        struct State {
+            monitor: HostMonitor,
+            previous: Option<HashMap<String, HostInfo>>,
+            pending: VecDeque<HostEvent>,
+            polled: bool,
+        }
+
+        let state = State {
+            monitor: self,
+            previous: None,
+            pending: VecDeque::new(),
+            polled: false,
+        };
+
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((Ok(event), state));
+                }
+
+                if state.polled {
+                    tokio::time::sleep(state.monitor.interval).await;
+                }
+                state.polled = true;
+
+                let current = match state.monitor.debrid.hosts().status().send().await {
+                    Ok(current) => current,
+                    Err(err) => return Some((Err(err), state)),
+                };
+
+                if let Some(previous) = &state.previous {
+                    state.pending.extend(diff_status(previous, &current));
+                }
+                state.previous = Some(current);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::hosts::CompetitorInfo;
+
+    fn info(status: HostStatus, competitors: &[(&str, HostStatus)]) -> HostInfo {
+        HostInfo {
+            id: "e0".to_string(),
+            name: "example0".to_string(),
+            image: String::new(),
+            image_big: None,
+            supported: status != HostStatus::Unsupported,
+            status,
+            check_time: "2024-09-27T15:53:09.000Z".to_string(),
+            competitors_status: competitors
+                .iter()
+                .map(|(domain, status)| {
+                    (
+                        domain.to_string(),
+                        CompetitorInfo {
+                            status: status.clone(),
+                            check_time: String::new(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn status(info: HostInfo) -> HashMap<String, HostInfo> {
+        HashMap::from([("example0.com".to_string(), info)])
+    }
+
+    #[test]
+    fn test_diff_status_transitions() {
+        let up = status(info(HostStatus::Up, &[]));
+        let down = status(info(HostStatus::Down, &[]));
+        let unsupported = status(info(HostStatus::Unsupported, &[]));
+        let check_time = "2024-09-27T15:53:09.000Z".to_string();
+
+        assert_eq!(diff_status(&up, &up), vec![]);
+        assert_eq!(
+            diff_status(&up, &down),
+            vec![HostEvent::Down {
+                domain: "example0.com".to_string(),
+                check_time: check_time.clone(),
+            }]
+        );
+        assert_eq!(
+            diff_status(&down, &up),
+            vec![HostEvent::Up {
+                domain: "example0.com".to_string(),
+                check_time: check_time.clone(),
+            }]
+        );
+        assert_eq!(
+            diff_status(&down, &unsupported),
+            vec![HostEvent::Unsupported {
+                domain: "example0.com".to_string(),
+                check_time,
+            }]
+        );
+        assert_eq!(diff_status(&HashMap::new(), &down), vec![]);
+    }
+
+    #[test]
+    fn test_diff_status_competitors() {
+        let down = status(info(HostStatus::Down, &[("b.com", HostStatus::Down)]));
+        let diverged = status(info(
+            HostStatus::Down,
+            &[("b.com", HostStatus::Up), ("a.com", HostStatus::Up)],
+        ));
+
+        let events = diff_status(&down, &diverged);
+        assert!(matches!(
+            &events[..],
+            [HostEvent::CompetitorsUp { competitors, .. }] if competitors == &["a.com", "b.com"]
+        ));
+        assert_eq!(diff_status(&diverged, &diverged), vec![]);
+    }
+}
@@ -0,0 +1,74 @@
+use regex::Regex;
+
+use super::extractor::{host_of, is_same_site};
+
+/// Passwords of hoster files, by host or link pattern.
+///
+/// Used by [`UnrestrictApi::resolve`](crate::apis::unrestrict::UnrestrictApi::resolve)
+/// when a hoster asks for a password.
+///
+/// # Examples
+/// ```
+/// use debrid::hosts::Passwords;
+/// use regex::Regex;
+///
+/// let passwords = Passwords::new()
+///     .host("example.com", "secret")
+///     .pattern(Regex::new(r"/folder/private/").unwrap(), "private");
+///
+/// assert_eq!(
+///     passwords.for_url("https://dl.example.com/folder/private/abc"),
+///     vec!["secret", "private"]
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Passwords {
+    rules: Vec<(Rule, String)>,
+}
+
+#[derive(Debug, Clone)]
+enum Rule {
+    /// Host domain, matching subdomains too
+    Host(String),
+    /// Pattern matched against the whole link
+    Pattern(Regex),
+}
+
+impl Passwords {
+    /// Creates an empty password list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a password for the links of a host, subdomains included.
+    pub fn host(mut self, domain: impl Into<String>, password: impl Into<String>) -> Self {
+        let domain = domain.into().to_ascii_lowercase();
+        self.rules.push((Rule::Host(domain), password.into()));
+        self
+    }
+
+    /// Add a password for the links matching a pattern.
+    pub fn pattern(mut self, pattern: Regex, password: impl Into<String>) -> Self {
+        self.rules.push((Rule::Pattern(pattern), password.into()));
+        self
+    }
+
+    /// Passwords to try for a link, deduplicated, in the order they were added.
+    pub fn for_url(&self, url: &str) -> Vec<&str> {
+        let host = host_of(url);
+        let mut passwords = Vec::new();
+
+        for (rule, password) in &self.rules {
+            let matches = match rule {
+                Rule::Host(domain) => host.as_ref().is_some_and(|host| is_same_site(host, domain)),
+                Rule::Pattern(pattern) => pattern.is_match(url),
+            };
+
+            if matches && !passwords.contains(&password.as_str()) {
+                passwords.push(password.as_str());
+            }
+        }
+
+        passwords
+    }
+}
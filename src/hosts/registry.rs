@@ -0,0 +1,258 @@
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bon::bon;
+use regex::Regex;
+
+use super::LinkExtractor;
+use crate::{
+    models::hosts::{Host, HostInfo, HostStatus},
+    Debrid, Error, Result,
+};
+
+/// Default time host data is trusted for.
+pub const DEFAULT_HOSTS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Host data fetched together, serializable for offline use.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HostSnapshot {
+    /// When the data was fetched, in seconds since the Unix epoch
+    pub fetched_at: u64,
+    /// Supported hosts, by main domain
+    pub hosts: HashMap<String, Host>,
+    /// Status of the hosts, by main domain, if fetched
+    pub status: Option<HashMap<String, HostInfo>>,
+    /// Supported links regexes
+    pub regex: Vec<String>,
+    /// Supported folders regexes
+    pub regex_folder: Vec<String>,
+    /// Supported domains
+    pub domains: Vec<String>,
+}
+
+/// Cache of the hosts API, answering lookups without a request.
+///
+/// Cloning the registry returns a handle to the same cache, so that it can be
+/// refreshed in the background while lookups are served from the previous data.
+#[derive(Clone)]
+pub struct HostRegistry {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    debrid: Debrid,
+    ttl: Duration,
+    status: bool,
+    loaded: RwLock<Option<Loaded>>,
+}
+
+struct Loaded {
+    snapshot: HostSnapshot,
+    extractor: LinkExtractor,
+}
+
+#[bon]
+impl HostRegistry {
+    /// Creates a new, empty host registry.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{hosts::HostRegistry, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let registry = HostRegistry::builder()
+    ///         .debrid(client)
+    ///         .ttl(std::time::Duration::from_secs(600))
+    ///         .build();
+    ///
+    ///     // Start from the last snapshot, if any, then refresh it when stale
+    ///     registry.restore("hosts.json").await.ok();
+    ///
+    ///     if registry.refresh_if_stale().await.is_ok() {
+    ///         registry.save("hosts.json").await?;
+    ///     }
+    ///
+    ///     let url = "https://example.com/abcdefghijkl";
+    ///     println!("supported: {}, up: {:?}", registry.is_supported(url), registry.is_up(url));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// RealDebrid client
+        debrid: Debrid,
+        /// Time host data is trusted for, default: 1 hour
+        ttl: Option<Duration>,
+        /// Fetch hosts status too, which requires authentication, default: true
+        status: Option<bool>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                debrid,
+                ttl: ttl.unwrap_or(DEFAULT_HOSTS_TTL),
+                status: status.unwrap_or(true),
+                loaded: RwLock::new(None),
+            }),
+        }
+    }
+}
+
+impl HostRegistry {
+    /// Fetch every host endpoint again, replacing the cached data.
+    pub async fn refresh(&self) -> Result<()> {
+        let hosts = self.inner.debrid.hosts();
+
+        let status = async {
+            match self.inner.status {
+                true => hosts.status().send().await.map(Some),
+                false => Ok(None),
+            }
+        };
+
+        let (hosts_map, status, regex, regex_folder, domains) = tokio::try_join!(
+            hosts.get().send(),
+            status,
+            hosts.regex().send(),
+            hosts.regex_folder().send(),
+            hosts.domains().send(),
+        )?;
+
+        let as_strings =
+            |regexes: Vec<Regex>| regexes.iter().map(|r| r.as_str().to_string()).collect();
+
+        self.load(HostSnapshot {
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            hosts: hosts_map,
+            status,
+            regex: as_strings(regex),
+            regex_folder: as_strings(regex_folder),
+            domains,
+        })
+    }
+
+    /// Refresh the cached data if missing or older than the TTL.
+    /// Returns whether or not it was refreshed.
+    pub async fn refresh_if_stale(&self) -> Result<bool> {
+        if !self.is_stale() {
+            return Ok(false);
+        }
+
+        self.refresh().await?;
+        Ok(true)
+    }
+
+    /// Whether or not the cached data is missing or older than the TTL.
+    pub fn is_stale(&self) -> bool {
+        let loaded = self.inner.loaded.read().unwrap();
+
+        loaded.as_ref().map_or(true, |loaded| {
+            let fetched = UNIX_EPOCH + Duration::from_secs(loaded.snapshot.fetched_at);
+
+            SystemTime::now()
+                .duration_since(fetched)
+                .is_ok_and(|age| age >= self.inner.ttl)
+        })
+    }
+
+    /// Replace the cached data with a snapshot.
+    pub fn load(&self, snapshot: HostSnapshot) -> Result<()> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Regex::new(p))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(Error::Regex)
+        };
+
+        let extractor = LinkExtractor::new(
+            &compile(&snapshot.regex)?,
+            &compile(&snapshot.regex_folder)?,
+            snapshot.domains.clone(),
+        )?;
+
+        *self.inner.loaded.write().unwrap() = Some(Loaded {
+            snapshot,
+            extractor,
+        });
+
+        Ok(())
+    }
+
+    /// Get a copy of the cached data, if any.
+    pub fn snapshot(&self) -> Option<HostSnapshot> {
+        let loaded = self.inner.loaded.read().unwrap();
+        loaded.as_ref().map(|loaded| loaded.snapshot.clone())
+    }
+
+    /// Save the cached data to a JSON file, if any.
+    pub async fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let Some(snapshot) = self.snapshot() else {
+            return Ok(());
+        };
+
+        let contents = serde_json::to_vec_pretty(&snapshot).map_err(Error::Json)?;
+        tokio::fs::write(path, contents).await.map_err(Error::Io)
+    }
+
+    /// Replace the cached data with a JSON file written by [`HostRegistry::save`].
+    /// The snapshot keeps its original age, so that it is refreshed when stale.
+    pub async fn restore(&self, path: impl AsRef<Path>) -> Result<()> {
+        let contents = tokio::fs::read(path).await.map_err(Error::Io)?;
+        let snapshot = serde_json::from_slice(&contents).map_err(Error::Json)?;
+
+        self.load(snapshot)
+    }
+
+    /// Get a link extractor built from the cached regexes, if any.
+    pub fn extractor(&self) -> Option<LinkExtractor> {
+        let loaded = self.inner.loaded.read().unwrap();
+        loaded.as_ref().map(|loaded| loaded.extractor.clone())
+    }
+
+    /// Get the main domain of the host serving a link, if supported.
+    pub fn domain_for_url(&self, url: &str) -> Option<String> {
+        let loaded = self.inner.loaded.read().unwrap();
+        loaded.as_ref()?.extractor.host(url)
+    }
+
+    /// Get the host serving a link, if supported.
+    pub fn host_for_url(&self, url: &str) -> Option<Host> {
+        let loaded = self.inner.loaded.read().unwrap();
+        let loaded = loaded.as_ref()?;
+        let domain = loaded.extractor.host(url)?;
+
+        loaded.snapshot.hosts.get(&domain).cloned()
+    }
+
+    /// Whether or not a link matches a supported links or folders regex.
+    pub fn is_supported(&self, url: &str) -> bool {
+        let loaded = self.inner.loaded.read().unwrap();
+        loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.extractor.classify(url).is_some())
+    }
+
+    /// Whether or not the host serving a link, or of a domain, is up.
+    /// Returns `None` if the host or its status is unknown.
+    pub fn is_up(&self, url_or_domain: &str) -> Option<bool> {
+        let loaded = self.inner.loaded.read().unwrap();
+        let loaded = loaded.as_ref()?;
+        let domain = loaded.extractor.host(url_or_domain)?;
+        let info = loaded.snapshot.status.as_ref()?.get(&domain)?;
+
+        Some(info.status == HostStatus::Up)
+    }
+}
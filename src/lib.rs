@@ -8,8 +8,38 @@ pub mod apis;
 /// Constants
 pub mod consts;
 
+/// Link containers (RSDF, CCF, DLC) decoding
+pub mod containers;
+
+/// File downloads for unrestricted links
+pub mod download;
+
+/// Host-aware helpers built on the hosts API
+pub mod hosts;
+
+/// STRM and NFO export of the library for media servers
+pub mod library;
+
 /// RealDebrid API models
 pub mod models;
 
+/// Extended M3U playlists of downloads, torrents and streams
+pub mod playlist;
+
+/// Declarative account configuration from profiles
+pub mod profile;
+
+/// Quality selection for links of streaming hosts
+pub mod quality;
+
+/// Release name parsing for torrents and files
+pub mod release;
+
+/// Stream link resolution, templates and track selection for transcoded files
+pub mod streams;
+
+/// Traffic quotas, forecasts and usage reports
+pub mod usage;
+
 pub use debrid::Debrid;
 pub use error::*;
@@ -0,0 +1,91 @@
+use std::path::PathBuf;
+
+use crate::models::streaming::{MediaInfo, MediaType};
+
+/// Directory of movies in a library.
+pub const MOVIES_DIRECTORY: &str = "Movies";
+
+/// Directory of shows in a library.
+pub const SHOWS_DIRECTORY: &str = "Shows";
+
+/// Path of the STRM file of a file in a media server library, relative to its root,
+/// or `None` if the file is neither a movie nor an episode.
+///
+/// Movies go in `Movies/Title (Year)/Title (Year).strm` and episodes in
+/// `Shows/Title/Season NN/Title - SNNEMM.strm`, episodes without a season being in season 1.
+pub fn library_path(info: &MediaInfo) -> Option<PathBuf> {
+    let title = clean_name(&info.filename);
+
+    match info.type_ {
+        MediaType::Movie => {
+            let name = match info.year.as_deref().map(str::trim) {
+                Some(year) if !year.is_empty() => format!("{} ({})", title, clean_name(year)),
+                _ => title,
+            };
+
+            Some(
+                [MOVIES_DIRECTORY, &name, &format!("{}.strm", name)]
+                    .iter()
+                    .collect(),
+            )
+        }
+        MediaType::Show => {
+            let (season, episode) = season_episode(info)?;
+
+            Some(
+                [
+                    SHOWS_DIRECTORY,
+                    &title,
+                    &format!("Season {:02}", season),
+                    &format!("{} - S{:02}E{:02}.strm", title, season, episode),
+                ]
+                .iter()
+                .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Season and episode of a show file, season 1 by default.
+pub(crate) fn season_episode(info: &MediaInfo) -> Option<(u32, u32)> {
+    let episode = info.episode.as_deref()?.trim().parse().ok()?;
+    let season = info
+        .season
+        .as_deref()
+        .and_then(|season| season.trim().parse().ok())
+        .unwrap_or(1);
+
+    Some((season, episode))
+}
+
+/// Removes the characters media servers or filesystems do not allow in a name.
+pub(crate) fn clean_name(name: &str) -> String {
+    let cleaned = name
+        .chars()
+        .filter(|c| {
+            !c.is_control() && !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*')
+        })
+        .collect::<String>();
+    let cleaned = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let cleaned = cleaned.trim_end_matches('.');
+
+    match cleaned {
+        "" | "." | ".." => "Unknown".to_string(),
+        cleaned => cleaned.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_name() {
+        assert_eq!(clean_name("Mission: Impossible"), "Mission Impossible");
+        assert_eq!(clean_name("AC/DC  Live?"), "ACDC Live");
+        assert_eq!(clean_name("Etc..."), "Etc");
+        assert_eq!(clean_name(".."), "Unknown");
+        assert_eq!(clean_name(""), "Unknown");
+    }
+}
@@ -0,0 +1,56 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{models::ids::LinkId, Error, Result};
+
+/// Name of the manifest file at the root of a library.
+pub const MANIFEST_FILE: &str = ".debrid-library.json";
+
+/// Sources of the STRM files of a library, to refresh their links.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Manifest {
+    /// Entries by STRM path relative to the library root, with `/` separators
+    pub entries: BTreeMap<String, Entry>,
+}
+
+/// Source of a STRM file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Entry {
+    /// RealDebrid file ID
+    pub id: LinkId,
+    /// Original hoster link
+    pub link: String,
+    /// Whether or not the STRM points at a stream rather than a download
+    pub stream: bool,
+    /// Time the link was generated, in seconds since the Unix epoch
+    pub generated: u64,
+}
+
+impl Manifest {
+    /// Loads the manifest of a library, empty if there is none yet.
+    pub async fn load(root: &Path) -> Result<Self> {
+        match tokio::fs::read(root.join(MANIFEST_FILE)).await {
+            Ok(contents) => serde_json::from_slice(&contents).map_err(Error::Json),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    /// Persists the manifest at the root of a library.
+    pub async fn save(&self, root: &Path) -> Result<()> {
+        let contents = serde_json::to_vec_pretty(self).map_err(Error::Json)?;
+        tokio::fs::write(root.join(MANIFEST_FILE), contents)
+            .await
+            .map_err(Error::Io)
+    }
+}
+
+/// Current time in seconds since the Unix epoch.
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
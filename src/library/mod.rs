@@ -0,0 +1,438 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bon::bon;
+
+use crate::{
+    models::{downloads::Download, ids::LinkId, streaming::MediaType, torrents::TorrentStatus},
+    streams::StreamPreference,
+    Debrid, Error, Result,
+};
+use manifest::{now, Entry, Manifest};
+
+mod layout;
+mod manifest;
+mod nfo;
+
+pub use layout::*;
+pub use manifest::MANIFEST_FILE;
+pub use nfo::*;
+
+/// Default age after which [`Library::refresh`] regenerates the link of a STRM file.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Max entries of the downloads and torrents lists exported.
+const LIST_LIMIT: u64 = 5000;
+
+/// Outcome of a library export or refresh.
+#[derive(Debug, Default)]
+pub struct LibraryReport {
+    /// STRM files written
+    pub written: Vec<PathBuf>,
+    /// Files left out, neither movies nor episodes nor sharing the path of a file already
+    /// exported, or STRM files removed since the export
+    pub skipped: Vec<String>,
+    /// Files which failed, with their error
+    pub failed: Vec<(String, Error)>,
+}
+
+/// A file of the user's RealDebrid library.
+struct Source {
+    id: LinkId,
+    link: String,
+    name: String,
+    download: String,
+}
+
+impl Source {
+    fn from_download(download: &Download) -> Self {
+        Self {
+            id: download.id.clone().into(),
+            link: download.link.clone(),
+            name: download.filename.clone(),
+            download: download.download.clone(),
+        }
+    }
+}
+
+/// Exports the RealDebrid library to a media server (Jellyfin, Plex, Kodi) as STRM files,
+/// without downloading anything.
+///
+/// Each STRM file holds the download or stream link of a file, in a `Movies/Title (Year)/`
+/// or `Shows/Title/Season NN/` layout, see [`library_path`]. Their sources are kept in a
+/// [`MANIFEST_FILE`] at the root of the library so that [`refresh`](Self::refresh) can
+/// regenerate links as they expire.
+pub struct Library<'rd>(pub(crate) &'rd Debrid);
+
+#[bon]
+impl<'rd> Library<'rd> {
+    /// Write the STRM files of the finished torrents and downloads.
+    ///
+    /// The layout comes from the media info of each file, files which are neither movies
+    /// nor episodes being skipped. Up to 5000 torrents and downloads are exported, and
+    /// files found in both are exported once. Torrent links are only unrestricted when
+    /// missing from the downloads, and files sharing a path are only exported once.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{streams::StreamPreference, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let preference = StreamPreference::builder().max_height(1080).build();
+    ///
+    ///     let report = client.library().export()
+    ///         .root("/srv/media/realdebrid")
+    ///         .nfo(true)
+    ///         .stream(&preference)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(report) = report {
+    ///         println!("{} files exported", report.written.len());
+    ///
+    ///         for (name, e) in report.failed {
+    ///             println!("{} failed: {}", name, e);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn export(
+        &self,
+        /// Root directory of the library
+        #[builder(into)]
+        root: PathBuf,
+        /// Write NFO metadata next to STRM files, default: false
+        nfo: Option<bool>,
+        /// Point at stream links picked with these preferences rather than at downloads,
+        /// falling back to downloads when no stream matches
+        stream: Option<&StreamPreference>,
+        /// Export the finished torrents, default: true
+        torrents: Option<bool>,
+        /// Export the downloads, default: true
+        downloads: Option<bool>,
+    ) -> Result<LibraryReport> {
+        let mut report = LibraryReport::default();
+        let sources = self
+            .sources(
+                torrents.unwrap_or(true),
+                downloads.unwrap_or(true),
+                &mut report,
+            )
+            .await?;
+        let mut manifest = Manifest::load(&root).await?;
+        let mut exported = HashSet::new();
+
+        for source in sources {
+            match self
+                .export_source(&root, &source, nfo.unwrap_or(false), stream, &mut exported)
+                .await
+            {
+                Ok(Some((relative, entry))) => {
+                    report.written.push(root.join(&relative));
+                    manifest.entries.insert(relative, entry);
+                }
+                Ok(None) => report.skipped.push(source.name),
+                Err(e) => report.failed.push((source.name, e)),
+            }
+        }
+
+        manifest.save(&root).await?;
+
+        Ok(report)
+    }
+
+    /// Regenerate the links of the STRM files older than `max_age`.
+    ///
+    /// Download links are unrestricted again from their original link, and stream links
+    /// are picked again with `stream`, or the default preferences.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use debrid::{Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let report = client.library().refresh()
+    ///         .root("/srv/media/realdebrid")
+    ///         .max_age(Duration::from_secs(6 * 60 * 60))
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(report) = report {
+    ///         println!("{} links refreshed", report.written.len());
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn refresh(
+        &self,
+        /// Root directory of the library
+        #[builder(into)]
+        root: PathBuf,
+        /// Age after which a link is regenerated, default: 24 hours
+        max_age: Option<Duration>,
+        /// Preferences picking stream links, default: all formats, HLS first, best quality
+        stream: Option<&StreamPreference>,
+    ) -> Result<LibraryReport> {
+        let max_age = max_age.unwrap_or(DEFAULT_MAX_AGE).as_secs();
+        let default = StreamPreference::default();
+        let preference = stream.unwrap_or(&default);
+
+        let mut report = LibraryReport::default();
+        let mut manifest = Manifest::load(&root).await?;
+        let now = now();
+
+        let mut removed = Vec::new();
+
+        for (relative, entry) in manifest.entries.iter_mut() {
+            let path = root.join(relative);
+
+            if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                removed.push(relative.clone());
+                continue;
+            }
+
+            if now.saturating_sub(entry.generated) < max_age {
+                continue;
+            }
+
+            let url = if entry.stream {
+                self.stream_url(&entry.id, preference)
+                    .await
+                    .and_then(|url| {
+                        url.ok_or_else(|| Error::UnavailableStream(entry.id.to_string()))
+                    })
+            } else {
+                self.0
+                    .unrestrict()
+                    .link()
+                    .link(entry.link.clone())
+                    .send()
+                    .await
+                    .map(|link| link.download)
+            };
+
+            match url {
+                Ok(url) => match write_strm(&path, &url).await {
+                    Ok(()) => {
+                        entry.generated = now;
+                        report.written.push(path);
+                    }
+                    Err(e) => report.failed.push((relative.clone(), e)),
+                },
+                Err(e) => report.failed.push((relative.clone(), e)),
+            }
+        }
+
+        for relative in removed {
+            manifest.entries.remove(&relative);
+            report.skipped.push(relative);
+        }
+
+        if !report.written.is_empty() || !report.skipped.is_empty() {
+            manifest.save(&root).await?;
+        }
+
+        Ok(report)
+    }
+}
+
+impl Library<'_> {
+    /// Files of the finished torrents and of the downloads, deduplicated.
+    async fn sources(
+        &self,
+        torrents: bool,
+        downloads: bool,
+        report: &mut LibraryReport,
+    ) -> Result<Vec<Source>> {
+        let mut sources = Vec::new();
+
+        // Also listed for torrents, so that their links already unrestricted are reused
+        let list = if downloads || torrents {
+            self.0
+                .downloads()
+                .get()
+                .limit(LIST_LIMIT as usize)
+                .send()
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        if downloads {
+            sources.extend(list.iter().map(Source::from_download));
+        }
+
+        let known = list
+            .iter()
+            .map(|download| (download.link.as_str(), download))
+            .collect::<HashMap<_, _>>();
+
+        if torrents {
+            let list = self.0.torrents().get().limit(LIST_LIMIT).send().await?;
+
+            for torrent in list
+                .into_iter()
+                .filter(|torrent| torrent.status == TorrentStatus::Downloaded)
+            {
+                let info = match self.0.torrents().info().id(torrent.id).send().await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        report.failed.push((torrent.filename, e));
+                        continue;
+                    }
+                };
+
+                let (reused, links) = info
+                    .links
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|link| known.contains_key(link.as_str()));
+
+                sources.extend(
+                    reused
+                        .iter()
+                        .map(|link| Source::from_download(known[link.as_str()])),
+                );
+
+                let unrestricted = self
+                    .0
+                    .unrestrict()
+                    .links()
+                    .links(links.clone())
+                    .send()
+                    .await;
+
+                for (original, link) in links.iter().zip(unrestricted) {
+                    match link {
+                        Ok(link) => sources.push(Source {
+                            id: link.id,
+                            link: link.link,
+                            name: link.filename,
+                            download: link.download,
+                        }),
+                        Err(e) => report.failed.push((original.clone(), e)),
+                    }
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        sources.retain(|source| seen.insert(source.id.clone()));
+
+        Ok(sources)
+    }
+
+    /// Write the STRM file of a file, and its NFO files, returning its manifest entry.
+    ///
+    /// Files whose path was already `exported` are skipped rather than overwritten
+    /// (e.g. the same movie in 1080p and 2160p).
+    async fn export_source(
+        &self,
+        root: &Path,
+        source: &Source,
+        nfo: bool,
+        stream: Option<&StreamPreference>,
+        exported: &mut HashSet<PathBuf>,
+    ) -> Result<Option<(String, Entry)>> {
+        let info = self
+            .0
+            .streaming()
+            .media_info()
+            .id(source.id.clone())
+            .send()
+            .await?;
+
+        let Some(relative) = library_path(&info) else {
+            return Ok(None);
+        };
+
+        if !exported.insert(relative.clone()) {
+            return Ok(None);
+        }
+
+        let streamed = match stream {
+            Some(preference) => self.stream_url(&source.id, preference).await?,
+            None => None,
+        };
+
+        let path = root.join(&relative);
+        write_strm(&path, streamed.as_deref().unwrap_or(&source.download)).await?;
+
+        if nfo {
+            if let Some(metadata) = self::nfo(&info) {
+                write(&path.with_extension("nfo"), &metadata).await?;
+            }
+
+            // Shows/Title/tvshow.nfo, written once
+            let show = path.parent().and_then(Path::parent);
+
+            if let (MediaType::Show, Some(show), Some(metadata)) =
+                (&info.type_, show, tvshow_nfo(&info))
+            {
+                let tvshow = show.join("tvshow.nfo");
+
+                if !tokio::fs::try_exists(&tvshow).await.unwrap_or(false) {
+                    write(&tvshow, &metadata).await?;
+                }
+            }
+        }
+
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Ok(Some((
+            relative,
+            Entry {
+                id: source.id.clone(),
+                link: source.link.clone(),
+                stream: streamed.is_some(),
+                generated: now(),
+            },
+        )))
+    }
+
+    /// Stream link of a file picked with preferences, if any matches.
+    async fn stream_url(
+        &self,
+        id: &LinkId,
+        preference: &StreamPreference,
+    ) -> Result<Option<String>> {
+        let transcode = self.0.streaming().transcode().id(id.clone()).send().await?;
+        Ok(preference.select(&transcode).map(|stream| stream.url))
+    }
+}
+
+/// Writes a STRM file, holding a single link.
+async fn write_strm(path: &Path, url: &str) -> Result<()> {
+    write(path, &format!("{}\n", url)).await
+}
+
+/// Writes a file, creating its directories.
+async fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(Error::Io)?;
+    }
+
+    tokio::fs::write(path, contents).await.map_err(Error::Io)
+}
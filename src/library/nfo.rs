@@ -0,0 +1,121 @@
+use super::layout::season_episode;
+use crate::models::streaming::{MediaInfo, MediaType};
+
+/// NFO metadata of a file, as read by Kodi, Jellyfin and Plex (with an agent),
+/// or `None` if the file is neither a movie nor an episode.
+pub fn nfo(info: &MediaInfo) -> Option<String> {
+    let mut fields = vec![("title", info.filename.clone())];
+
+    let root = match info.type_ {
+        MediaType::Movie => {
+            if let Some(year) = info.year.as_deref().filter(|year| !year.trim().is_empty()) {
+                fields.push(("year", year.trim().to_string()));
+            }
+            "movie"
+        }
+        MediaType::Show => {
+            let (season, episode) = season_episode(info)?;
+            fields.push(("showtitle", info.filename.clone()));
+            fields.push(("season", season.to_string()));
+            fields.push(("episode", episode.to_string()));
+            "episodedetails"
+        }
+        _ => return None,
+    };
+
+    if info.duration > 0.0 {
+        fields.push((
+            "runtime",
+            ((info.duration / 60.0).round() as u64).to_string(),
+        ));
+    }
+
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<{}>\n",
+        root
+    );
+
+    for (name, value) in fields {
+        xml.push_str(&format!("  <{0}>{1}</{0}>\n", name, escape_xml(&value)));
+    }
+
+    if let Some(poster) = image(info.poster_path.as_deref()) {
+        xml.push_str(&format!(
+            "  <thumb aspect=\"poster\">{}</thumb>\n",
+            escape_xml(poster)
+        ));
+    }
+
+    if let Some(backdrop) = image(info.backdrop_path.as_deref()) {
+        xml.push_str(&format!(
+            "  <fanart>\n    <thumb>{}</thumb>\n  </fanart>\n",
+            escape_xml(backdrop)
+        ));
+    }
+
+    xml.push_str(&format!("</{}>\n", root));
+
+    Some(xml)
+}
+
+/// NFO metadata of the show of an episode, or `None` if the file is not an episode.
+pub fn tvshow_nfo(info: &MediaInfo) -> Option<String> {
+    if info.type_ != MediaType::Show {
+        return None;
+    }
+
+    let mut xml =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<tvshow>\n");
+    xml.push_str(&format!(
+        "  <title>{}</title>\n",
+        escape_xml(&info.filename)
+    ));
+
+    if let Some(poster) = image(info.poster_path.as_deref()) {
+        xml.push_str(&format!(
+            "  <thumb aspect=\"poster\">{}</thumb>\n",
+            escape_xml(poster)
+        ));
+    }
+
+    xml.push_str("</tvshow>\n");
+
+    Some(xml)
+}
+
+/// Absolute image links only, RealDebrid placeholders being relative.
+fn image(path: Option<&str>) -> Option<&str> {
+    path.filter(|path| path.starts_with("http://") || path.starts_with("https://"))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("Tom & Jerry <\"'>"),
+            "Tom &amp; Jerry &lt;&quot;&apos;&gt;"
+        );
+    }
+
+    #[test]
+    fn test_image() {
+        assert_eq!(
+            image(Some("https://example.com/poster.jpg")),
+            Some("https://example.com/poster.jpg")
+        );
+        assert_eq!(image(Some("/static/images/dummy.png")), None);
+        assert_eq!(image(None), None);
+    }
+}
@@ -1,9 +1,12 @@
+use super::ids::DownloadId;
+use crate::release::Release;
+
 /// Represents a download from RealDebrid.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Download {
     /// Download ID
-    pub id: String,
+    pub id: DownloadId,
     /// Filename
     pub filename: String,
     /// MIME type of the file, guessed by the file extension
@@ -23,6 +26,13 @@ pub struct Download {
     pub generated: String,
 }
 
+impl Download {
+    /// Release parsed from the filename.
+    pub fn release(&self) -> Release {
+        Release::parse(&self.filename)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
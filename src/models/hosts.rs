@@ -48,13 +48,16 @@ pub struct CompetitorInfo {
 }
 
 /// Represents the current status of a RealDebrid host.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum HostStatus {
     Up,
     Down,
     Unsupported,
+    /// Status not known to this crate, holding the raw value
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(test)]
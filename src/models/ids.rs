@@ -0,0 +1,155 @@
+use std::{fmt, str::FromStr};
+
+use crate::{Error, Result};
+
+macro_rules! id {
+    ($(#[$meta:meta])* $name:ident, $validate:path) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+        #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Creates a new identifier, validating its format.
+            pub fn new<S: Into<String>>(value: S) -> Result<Self> {
+                let value = value.into();
+
+                if $validate(&value) {
+                    Ok(Self(value))
+                } else {
+                    Err(Error::InvalidIdentifier(value))
+                }
+            }
+
+            /// Returns the identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Consumes the identifier, returning the inner string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                Self::new(s)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            /// Wraps the string as-is, without validation.
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&str> for $name {
+            /// Wraps the string as-is, without validation.
+            fn from(value: &str) -> Self {
+                Self(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+id!(
+    /// RealDebrid torrent ID (e.g. "ABCDEFGHIJKLM").
+    TorrentId,
+    is_alphanumeric
+);
+
+id!(
+    /// RealDebrid download ID (e.g. "ABCDEFGHIJKLMNO").
+    DownloadId,
+    is_alphanumeric
+);
+
+id!(
+    /// RealDebrid unrestricted link ID (e.g. "ABCDEFGHIJKLMNO").
+    LinkId,
+    is_alphanumeric
+);
+
+id!(
+    /// SHA1 info hash of a torrent, as 40 hexadecimal characters.
+    InfoHash,
+    is_sha1_hex
+);
+
+impl From<DownloadId> for LinkId {
+    /// Downloads are previously unrestricted links and share their ID.
+    fn from(value: DownloadId) -> Self {
+        Self(value.0)
+    }
+}
+
+fn is_alphanumeric(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_sha1_hex(value: &str) -> bool {
+    value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_parse_ids() -> Result<()> {
+        let id = "ABCDEFGHIJKLM".parse::<TorrentId>()?;
+        assert_eq!(id.to_string(), "ABCDEFGHIJKLM");
+
+        assert!("".parse::<TorrentId>().is_err());
+        assert!("ABC/DEF".parse::<DownloadId>().is_err());
+        assert!("ABC DEF".parse::<LinkId>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_info_hash() -> Result<()> {
+        let hash = "c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab".parse::<InfoHash>()?;
+        assert_eq!(hash.as_str(), "c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab");
+
+        assert!("ABCDEFGHIJKLM".parse::<InfoHash>().is_err());
+        assert!("z39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab"
+            .parse::<InfoHash>()
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_ids() -> Result<()> {
+        let id = serde_json::from_str::<LinkId>(r#""ABCDEFGHIJKLMNO""#)?;
+        assert_eq!(id, LinkId::from("ABCDEFGHIJKLMNO"));
+        assert_eq!(serde_json::to_string(&id)?, r#""ABCDEFGHIJKLMNO""#);
+
+        Ok(())
+    }
+}
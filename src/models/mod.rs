@@ -1,6 +1,7 @@
 pub mod downloads;
 pub mod error;
 pub mod hosts;
+pub mod ids;
 pub mod streaming;
 pub mod torrents;
 pub mod traffic;
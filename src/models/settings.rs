@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
+
+use crate::{Error, Result};
 
 /// Represents a RealDebrid user's settings.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -8,6 +10,12 @@ pub struct Settings {
     pub download_ports: Vec<String>,
     /// Current user download port
     pub download_port: String,
+    /// Possible "download_protocol" value to update settings, if advertised
+    #[serde(default)]
+    pub download_protocols: Option<Vec<String>>,
+    /// Current user download protocol, if known
+    #[serde(default)]
+    pub download_protocol: Option<String>,
     /// Possible "locale" value to update settings
     pub locales: HashMap<String, String>,
     /// Current user locale
@@ -28,6 +36,203 @@ pub struct Settings {
     pub streaming_cast_audio_preference: String,
 }
 
+impl Settings {
+    /// Current value of a setting, if known.
+    pub fn current(&self, name: SettingName) -> Option<&str> {
+        match name {
+            SettingName::DownloadPort => Some(&self.download_port),
+            SettingName::Locale => Some(&self.locale),
+            SettingName::StreamingQuality => Some(&self.streaming_quality),
+            SettingName::MobileStreamingQuality => Some(&self.mobile_streaming_quality),
+            SettingName::StreamingLanguagePreference => Some(&self.streaming_language_preference),
+            SettingName::StreamingCastAudioPreference => {
+                Some(&self.streaming_cast_audio_preference)
+            }
+            SettingName::DownloadProtocol => self.download_protocol.as_deref(),
+        }
+    }
+
+    /// Values a setting may be updated to, sorted when advertised as a map,
+    /// `None` if RealDebrid does not advertise them.
+    pub fn allowed(&self, name: SettingName) -> Option<Vec<&str>> {
+        match name {
+            SettingName::DownloadPort => Some(list(&self.download_ports)),
+            SettingName::Locale => Some(sorted_keys(&self.locales)),
+            SettingName::StreamingQuality | SettingName::MobileStreamingQuality => {
+                Some(list(&self.streaming_qualities))
+            }
+            SettingName::StreamingLanguagePreference => {
+                Some(sorted_keys(&self.streaming_languages))
+            }
+            SettingName::StreamingCastAudioPreference => Some(list(&self.streaming_cast_audio)),
+            SettingName::DownloadProtocol => self.download_protocols.as_deref().map(list),
+        }
+    }
+
+    /// Check a setting against the values advertised by RealDebrid.
+    ///
+    /// Settings whose values are not advertised are left for the server to check.
+    pub fn validate(&self, setting: &Setting) -> Result<()> {
+        let name = setting.name();
+
+        if self
+            .allowed(name)
+            .map_or(true, |allowed| allowed.contains(&setting.value()))
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidSetting {
+                name: name.to_string(),
+                value: setting.value().to_string(),
+            })
+        }
+    }
+
+    /// Settings among `desired` which differ from the current ones, validated first.
+    /// The last value of a setting given several times wins.
+    pub fn diff(&self, desired: &[Setting]) -> Result<Vec<Setting>> {
+        let mut changes = Vec::<Setting>::new();
+
+        for setting in desired {
+            self.validate(setting)?;
+            changes.retain(|change| change.name() != setting.name());
+
+            if self.current(setting.name()) != Some(setting.value()) {
+                changes.push(setting.clone());
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+/// Name of a user setting which can be updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingName {
+    DownloadPort,
+    Locale,
+    StreamingQuality,
+    MobileStreamingQuality,
+    StreamingLanguagePreference,
+    StreamingCastAudioPreference,
+    DownloadProtocol,
+}
+
+impl SettingName {
+    /// Every setting which can be updated.
+    pub const ALL: [SettingName; 7] = [
+        SettingName::DownloadPort,
+        SettingName::Locale,
+        SettingName::StreamingQuality,
+        SettingName::MobileStreamingQuality,
+        SettingName::StreamingLanguagePreference,
+        SettingName::StreamingCastAudioPreference,
+        SettingName::DownloadProtocol,
+    ];
+
+    /// Name of the setting in the RealDebrid API (e.g. "download_port").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingName::DownloadPort => "download_port",
+            SettingName::Locale => "locale",
+            SettingName::StreamingQuality => "streaming_quality",
+            SettingName::MobileStreamingQuality => "mobile_streaming_quality",
+            SettingName::StreamingLanguagePreference => "streaming_language_preference",
+            SettingName::StreamingCastAudioPreference => "streaming_cast_audio_preference",
+            SettingName::DownloadProtocol => "download_protocol",
+        }
+    }
+
+    /// Setting of a name in the RealDebrid API, if it can be updated.
+    pub fn from_name(name: &str) -> Option<Self> {
+        SettingName::ALL
+            .into_iter()
+            .find(|setting| setting.as_str() == name)
+    }
+}
+
+impl fmt::Display for SettingName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents a user setting update, checked against [`Settings`] before being sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Setting {
+    /// Download port, one of `Settings.download_ports` (e.g. "secured")
+    DownloadPort(String),
+    /// Locale, one of the keys of `Settings.locales` (e.g. "en")
+    Locale(String),
+    /// Streaming quality, one of `Settings.streaming_qualities` (e.g. "original")
+    StreamingQuality(String),
+    /// Streaming quality on mobile devices, one of `Settings.streaming_qualities`
+    MobileStreamingQuality(String),
+    /// Streaming language, one of the keys of `Settings.streaming_languages` (e.g. "eng")
+    StreamingLanguagePreference(String),
+    /// Audio on Google Cast devices, one of `Settings.streaming_cast_audio` (e.g. "aac")
+    StreamingCastAudioPreference(String),
+    /// Download protocol, one of `Settings.download_protocols` (e.g. "IPv4")
+    DownloadProtocol(String),
+}
+
+impl Setting {
+    /// Creates a setting update from its name and value.
+    pub fn new(name: SettingName, value: impl Into<String>) -> Self {
+        let value = value.into();
+
+        match name {
+            SettingName::DownloadPort => Setting::DownloadPort(value),
+            SettingName::Locale => Setting::Locale(value),
+            SettingName::StreamingQuality => Setting::StreamingQuality(value),
+            SettingName::MobileStreamingQuality => Setting::MobileStreamingQuality(value),
+            SettingName::StreamingLanguagePreference => Setting::StreamingLanguagePreference(value),
+            SettingName::StreamingCastAudioPreference => {
+                Setting::StreamingCastAudioPreference(value)
+            }
+            SettingName::DownloadProtocol => Setting::DownloadProtocol(value),
+        }
+    }
+
+    /// Name of the setting.
+    pub fn name(&self) -> SettingName {
+        match self {
+            Setting::DownloadPort(_) => SettingName::DownloadPort,
+            Setting::Locale(_) => SettingName::Locale,
+            Setting::StreamingQuality(_) => SettingName::StreamingQuality,
+            Setting::MobileStreamingQuality(_) => SettingName::MobileStreamingQuality,
+            Setting::StreamingLanguagePreference(_) => SettingName::StreamingLanguagePreference,
+            Setting::StreamingCastAudioPreference(_) => SettingName::StreamingCastAudioPreference,
+            Setting::DownloadProtocol(_) => SettingName::DownloadProtocol,
+        }
+    }
+
+    /// Value of the setting.
+    pub fn value(&self) -> &str {
+        match self {
+            Setting::DownloadPort(value)
+            | Setting::Locale(value)
+            | Setting::StreamingQuality(value)
+            | Setting::MobileStreamingQuality(value)
+            | Setting::StreamingLanguagePreference(value)
+            | Setting::StreamingCastAudioPreference(value)
+            | Setting::DownloadProtocol(value) => value,
+        }
+    }
+}
+
+/// Values of a list of advertised values.
+fn list(values: &[String]) -> Vec<&str> {
+    values.iter().map(String::as_str).collect()
+}
+
+/// Keys of a map of advertised values, sorted.
+fn sorted_keys(map: &HashMap<String, String>) -> Vec<&str> {
+    let mut keys = map.keys().map(String::as_str).collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +303,88 @@ mod tests {
 
         Ok(())
     }
+
+    fn settings() -> Settings {
+        serde_json::from_str(
+            r#"
+            {
+                "download_ports": ["normal", "secured"],
+                "download_port": "secured",
+                "locales": {"fr": "Français (France)", "en": "English (US)"},
+                "locale": "en",
+                "streaming_qualities": ["original", "high"],
+                "streaming_quality": "original",
+                "mobile_streaming_quality": "high",
+                "streaming_languages": {"fre": "Français", "eng": "English"},
+                "streaming_language_preference": "eng",
+                "streaming_cast_audio": ["aac", "dolby"],
+                "streaming_cast_audio_preference": "aac"
+            }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        let settings = settings();
+
+        assert!(settings
+            .validate(&Setting::Locale("fr".to_string()))
+            .is_ok());
+        assert!(settings
+            .validate(&Setting::MobileStreamingQuality("original".to_string()))
+            .is_ok());
+        assert!(matches!(
+            settings.validate(&Setting::Locale("xx".to_string())),
+            Err(Error::InvalidSetting { .. })
+        ));
+        assert_eq!(
+            settings.allowed(SettingName::Locale),
+            Some(vec!["en", "fr"])
+        );
+    }
+
+    #[test]
+    fn test_validate_unadvertised_setting() {
+        let mut settings = settings();
+
+        // Not advertised by every response, left for the server to check
+        assert_eq!(settings.allowed(SettingName::DownloadProtocol), None);
+        assert!(settings
+            .validate(&Setting::DownloadProtocol("IPv4".to_string()))
+            .is_ok());
+
+        settings.download_protocols = Some(vec!["IPv4".to_string(), "IPv6".to_string()]);
+        assert!(settings
+            .validate(&Setting::DownloadProtocol("IPv4".to_string()))
+            .is_ok());
+        assert!(matches!(
+            settings.validate(&Setting::DownloadProtocol("IPX".to_string())),
+            Err(Error::InvalidSetting { .. })
+        ));
+    }
+
+    #[test]
+    fn test_diff_settings() {
+        let settings = settings();
+
+        let changes = settings
+            .diff(&[
+                Setting::Locale("en".to_string()),
+                Setting::StreamingQuality("high".to_string()),
+                Setting::StreamingQuality("original".to_string()),
+                Setting::MobileStreamingQuality("original".to_string()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![Setting::MobileStreamingQuality("original".to_string())]
+        );
+        assert_eq!(
+            SettingName::from_name("download_port"),
+            Some(SettingName::DownloadPort)
+        );
+    }
 }
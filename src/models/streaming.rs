@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use crate::de::*;
 
@@ -151,14 +151,129 @@ pub struct AvailableFormats {
     pub h264_webm: String,
 }
 
+impl Transcode {
+    /// Links of a format, keyed by quality.
+    pub fn urls(&self, format: StreamFormat) -> &HashMap<String, String> {
+        match format {
+            StreamFormat::Hls => &self.apple,
+            StreamFormat::Dash => &self.dash,
+            StreamFormat::LiveMp4 => &self.live_mp4,
+            StreamFormat::WebM => &self.h264_webm,
+        }
+    }
+}
+
+impl AvailableFormats {
+    /// Extension of a format in stream links (e.g. "m3u8").
+    pub fn extension(&self, format: StreamFormat) -> &str {
+        match format {
+            StreamFormat::Hls => &self.apple,
+            StreamFormat::Dash => &self.dash,
+            StreamFormat::LiveMp4 => &self.live_mp4,
+            StreamFormat::WebM => &self.h264_webm,
+        }
+    }
+}
+
+/// Enum representing a media stream format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum StreamFormat {
+    /// M3U8 Live Streaming (HLS)
+    #[serde(rename = "apple")]
+    Hls,
+    /// MPD Live Streaming (DASH)
+    #[serde(rename = "dash")]
+    Dash,
+    /// Live MP4
+    #[serde(rename = "liveMP4")]
+    LiveMp4,
+    /// Live H264 WebM
+    #[serde(rename = "h264WebM")]
+    WebM,
+}
+
+impl StreamFormat {
+    /// Every format, adaptive streams first.
+    pub const ALL: [StreamFormat; 4] = [
+        StreamFormat::Hls,
+        StreamFormat::Dash,
+        StreamFormat::LiveMp4,
+        StreamFormat::WebM,
+    ];
+
+    /// Default extension of the format in stream links.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            StreamFormat::Hls => "m3u8",
+            StreamFormat::Dash => "mpd",
+            StreamFormat::LiveMp4 => "mp4",
+            StreamFormat::WebM => "webm",
+        }
+    }
+}
+
+/// Enum representing the quality of a media stream, parsed from its quality key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StreamQuality {
+    /// Original quality of the file, keyed `full`
+    Original,
+    /// Transcoded to a video height (e.g. `720`, `1080p`)
+    Height(u32),
+    /// Quality not known to this crate, holding the raw key
+    Other(String),
+}
+
+impl StreamQuality {
+    /// Video height of the quality, if known.
+    pub fn height(&self) -> Option<u32> {
+        match self {
+            StreamQuality::Height(height) => Some(*height),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for StreamQuality {
+    fn from(key: &str) -> Self {
+        let trimmed = key.trim();
+
+        if trimmed.eq_ignore_ascii_case("full") || trimmed.eq_ignore_ascii_case("original") {
+            return StreamQuality::Original;
+        }
+
+        trimmed
+            .parse()
+            .ok()
+            .or_else(|| crate::quality::parse_height(trimmed))
+            .map_or_else(
+                || StreamQuality::Other(key.to_string()),
+                StreamQuality::Height,
+            )
+    }
+}
+
+impl fmt::Display for StreamQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamQuality::Original => write!(f, "Original"),
+            StreamQuality::Height(height) => write!(f, "{}p", height),
+            StreamQuality::Other(key) => write!(f, "{}", key),
+        }
+    }
+}
+
 /// Enum representing the type of a media file.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum MediaType {
     Movie,
     Show,
     Audio,
+    /// Media type not known to this crate, holding the raw value
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(test)]
@@ -244,4 +359,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_stream_quality() {
+        assert_eq!(StreamQuality::from("full"), StreamQuality::Original);
+        assert_eq!(StreamQuality::from("720"), StreamQuality::Height(720));
+        assert_eq!(StreamQuality::from("1080p"), StreamQuality::Height(1080));
+        assert_eq!(
+            StreamQuality::from("best"),
+            StreamQuality::Other("best".to_string())
+        );
+        assert_eq!(StreamQuality::Height(720).to_string(), "720p");
+    }
 }
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 
+use super::ids::{InfoHash, TorrentId};
+use crate::release::Release;
+
 /// Represents a RealDebrid torrent.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Torrent {
     /// RealDebrid torrent ID
-    pub id: String,
+    pub id: TorrentId,
     /// Filename
     pub filename: String,
     /// SHA1 Hash of the torrent
-    pub hash: String,
+    pub hash: InfoHash,
     /// Size of selected files only
     pub bytes: u64,
     /// Host main domain
@@ -32,8 +35,15 @@ pub struct Torrent {
     pub seeders: Option<u64>,
 }
 
+impl Torrent {
+    /// Release parsed from the torrent name.
+    pub fn release(&self) -> Release {
+        Release::parse(&self.filename)
+    }
+}
+
 /// Enum representing the current status of a RealDebrid torrent.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum TorrentStatus {
@@ -48,6 +58,9 @@ pub enum TorrentStatus {
     Compressing,
     Uploading,
     Dead,
+    /// Status not known to this crate, holding the raw value
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Represents information of a RealDebrid torrent.
@@ -55,13 +68,13 @@ pub enum TorrentStatus {
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct TorrentInfo {
     /// RealDebrid torrent ID
-    pub id: String,
+    pub id: TorrentId,
     /// Filename
     pub filename: String,
     /// Original name of the torrent
     pub original_filename: String,
     /// SHA1 Hash of the torrent
-    pub hash: String,
+    pub hash: InfoHash,
     /// Size of selected files only
     pub bytes: u64,
     /// Total size of the torrent
@@ -88,6 +101,13 @@ pub struct TorrentInfo {
     pub seeders: Option<u64>,
 }
 
+impl TorrentInfo {
+    /// Release parsed from the torrent name.
+    pub fn release(&self) -> Release {
+        Release::parse(&self.filename)
+    }
+}
+
 /// Represents a file of a RealDebrid torrent.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -103,6 +123,13 @@ pub struct TorrentFile {
     pub selected: bool,
 }
 
+impl TorrentFile {
+    /// Release parsed from the file name, its directories left out.
+    pub fn release(&self) -> Release {
+        Release::parse(&self.path)
+    }
+}
+
 /// Represents the instant availability of a RealDebrid torrent.
 /// Hoster x File ID variants x File ID x File
 pub type InstantAvailability = HashMap<String, Vec<HashMap<String, InstantlyAvailableFile>>>;
@@ -125,8 +152,8 @@ pub struct ActiveCount {
     pub nb: u64,
     /// Maximum number of active torrents you can have
     pub limit: u64,
-    /// List of active torrents
-    pub list: Option<Vec<String>>,
+    /// Hashes of active torrents
+    pub list: Option<Vec<InfoHash>>,
 }
 
 /// Represents an availble torrent host on RealDebrid.
@@ -143,7 +170,7 @@ pub struct AvailableHost {
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct AddedTorrent {
     /// RealDebrid torrent ID
-    pub id: String,
+    pub id: TorrentId,
     /// URL of the created ressource
     pub uri: String,
 }
@@ -282,7 +309,7 @@ mod tests {
         }
         "#;
 
-        let availability = serde_json::from_str::<HashMap<String, InstantAvailability>>(s);
+        let availability = serde_json::from_str::<HashMap<InfoHash, InstantAvailability>>(s);
 
         assert!(availability.is_ok());
 
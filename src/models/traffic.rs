@@ -44,20 +44,57 @@ pub enum Traffic {
         /// Reset frequency of the limit, if applicable
         reset: Option<Reset>,
     },
+    /// Traffic limited in a way not known to this crate
+    #[serde(untagged)]
+    Unknown {
+        /// Raw traffic type
+        #[serde(rename = "type")]
+        type_: String,
+        /// Available quota to use, if applicable
+        left: Option<u64>,
+        /// Bytes downloaded, if applicable
+        bytes: Option<u64>,
+        /// Links unrestricted, if applicable
+        links: Option<u64>,
+        /// Maximum quota, if applicable
+        limit: Option<u64>,
+        /// Additional quota the user may have bought, if applicable
+        extra: Option<u64>,
+        /// Reset frequency of the limit, if applicable
+        reset: Option<Reset>,
+    },
+}
+
+/// Traffic key of the remote traffic, shared by all hosters.
+pub const REMOTE_TRAFFIC: &str = "remote";
+
+impl Traffic {
+    /// Available quota to use, in links or bytes depending on the traffic type, if known.
+    pub fn left(&self) -> Option<u64> {
+        match self {
+            Traffic::Links { left, .. }
+            | Traffic::Gigabytes { left, .. }
+            | Traffic::Bytes { left, .. } => Some(*left),
+            Traffic::Unknown { left, .. } => *left,
+        }
+    }
 }
 
 /// Enum representing the reset frequency of a hoster limit.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Reset {
     Daily,
     Weekly,
     Monthly,
+    /// Reset frequency not known to this crate, holding the raw value
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// Traffic details for a hoster for a day.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Detail {
     /// Host main domain x (host, bytes downloaded on host)
     pub host: HashMap<String, u64>,
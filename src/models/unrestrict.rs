@@ -1,3 +1,7 @@
+use std::{fmt, str::FromStr};
+
+use super::ids::LinkId;
+
 /// Represents an unrestrict/downloadable check on RealDebrid.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
@@ -19,12 +23,68 @@ pub struct Check {
     pub supported: bool,
 }
 
+/// Separator between the title and the qualities in `Check.filename` of streaming hosts.
+const QUALITIES_SEPARATOR: &str = "|-|";
+
+impl Check {
+    /// Title of the file, without the qualities listed by streaming hosts.
+    pub fn title(&self) -> &str {
+        self.filename
+            .rsplit_once(QUALITIES_SEPARATOR)
+            .map_or(&self.filename, |(title, _)| title)
+    }
+
+    /// Resolutions listed by streaming hosts in the filename, in the given order.
+    /// Empty if the host does not list any.
+    pub fn resolutions(&self) -> Vec<Resolution> {
+        self.filename
+            .rsplit_once(QUALITIES_SEPARATOR)
+            .map(|(_, qualities)| {
+                qualities
+                    .split(',')
+                    .filter_map(|quality| quality.trim().parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Represents a video resolution, such as `1920x1080`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Resolution {
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let invalid = || crate::Error::InvalidResolution(s.to_string());
+        let (width, height) = s.split_once(['x', 'X']).ok_or_else(invalid)?;
+
+        Ok(Self {
+            width: width.parse().map_err(|_| invalid())?,
+            height: height.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
 /// Represents an unrestricted link on RealDebrid.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Link {
     /// RealDebrid link ID
-    pub id: String,
+    pub id: LinkId,
     /// Original filename
     pub filename: String,
     /// MIME type of the file, if applicable
@@ -60,7 +120,7 @@ pub struct Link {
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct AlternativeLink {
     /// RealDebrid link ID
-    pub id: String,
+    pub id: LinkId,
     /// Original filename
     pub filename: String,
     /// MIME type of the file, if applicable
@@ -101,6 +161,40 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_check_qualities() -> Result<()> {
+        let mut check = Check {
+            host: "youtube.com".to_string(),
+            host_icon: None,
+            host_icon_big: None,
+            link: "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+            filename: "Title|-|1920x1080, 1280x720,bad".to_string(),
+            filesize: 0,
+            supported: true,
+        };
+
+        assert_eq!(check.title(), "Title");
+        assert_eq!(
+            check.resolutions(),
+            vec![
+                Resolution {
+                    width: 1920,
+                    height: 1080
+                },
+                Resolution {
+                    width: 1280,
+                    height: 720
+                },
+            ]
+        );
+
+        check.filename = "file.mkv".to_string();
+        assert_eq!(check.title(), "file.mkv");
+        assert!(check.resolutions().is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_deserialize_alternative_link() -> Result<()> {
         let s = r#"
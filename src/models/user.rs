@@ -24,12 +24,15 @@ pub struct User {
 }
 
 /// Enum representing the type of user.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Type {
     Free,
     Premium,
+    /// User type not known to this crate, holding the raw value
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[cfg(test)]
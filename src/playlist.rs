@@ -0,0 +1,257 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use crate::{
+    download::sanitize,
+    models::{
+        downloads::Download,
+        streaming::{MediaInfo, StreamFormat, Transcode},
+        torrents::TorrentInfo,
+        unrestrict::Link,
+    },
+    release::Release,
+    streams::StreamPreference,
+};
+
+/// Represents an entry of a [`Playlist`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistEntry {
+    /// Title shown by players
+    pub title: String,
+    /// Link of the file or stream
+    pub url: String,
+    /// Path of the file relative to the playlist once downloaded, if applicable
+    pub path: Option<String>,
+    /// Duration in seconds, if known
+    pub duration: Option<f64>,
+    /// Season number, if applicable
+    pub season: Option<u32>,
+    /// Episode number, if applicable
+    pub episode: Option<u32>,
+}
+
+impl PlaylistEntry {
+    /// Creates an entry, the season and episode being parsed from the title as a [`Release`].
+    pub fn new(title: impl Into<String>, url: impl Into<String>) -> Self {
+        let title = title.into();
+        let release = Release::parse(&title);
+
+        Self {
+            title,
+            url: url.into(),
+            path: None,
+            duration: None,
+            season: release.season(),
+            episode: release.episode(),
+        }
+    }
+
+    /// Creates an entry for a download, titled after its filename.
+    pub fn from_download(download: &Download) -> Self {
+        Self::new(stem(&download.filename), &download.download)
+            .with_path(sanitize(&download.filename))
+    }
+
+    /// Creates an entry for an unrestricted link, titled after its filename.
+    pub fn from_link(link: &Link) -> Self {
+        Self::new(stem(&link.filename), &link.download).with_path(sanitize(&link.filename))
+    }
+
+    /// Creates an entry for the best HLS stream of a transcoded file, or `None` if it has none.
+    pub fn from_transcode(title: impl Into<String>, transcode: &Transcode) -> Option<Self> {
+        let stream = StreamPreference::builder()
+            .formats(vec![StreamFormat::Hls])
+            .build()
+            .select(transcode)?;
+
+        Some(Self::new(title, stream.url))
+    }
+
+    /// Set the path of the file relative to the playlist.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Set the duration, title, season and episode from the media info of the file.
+    ///
+    /// The title is the cleaned filename, followed by the season and episode (e.g. `Show - S01E02`).
+    pub fn with_media_info(mut self, info: &MediaInfo) -> Self {
+        let season = info.season.as_deref().and_then(|s| s.trim().parse().ok());
+        let episode = info.episode.as_deref().and_then(|e| e.trim().parse().ok());
+
+        self.duration = Some(info.duration);
+        self.title = match (season, episode) {
+            (Some(season), Some(episode)) => {
+                format!("{} - S{:02}E{:02}", info.filename, season, episode)
+            }
+            (None, Some(episode)) => format!("{} - E{:02}", info.filename, episode),
+            _ => info.filename.clone(),
+        };
+        self.season = season.or(self.season);
+        self.episode = episode.or(self.episode);
+        self
+    }
+}
+
+/// Extended M3U playlist of downloads, torrent links or streams.
+///
+/// # Examples
+/// ```
+/// use debrid::{playlist::Playlist, Debrid, Error, Result};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<()> {
+///     let client = Debrid::builder()
+///         .token("LOREMIPSUM".to_string())
+///         .build()?;
+///
+///     let torrent = client.torrents().info()
+///         .id("ABCDEFGHIJKLM".to_string())
+///         .send()
+///         .await;
+///
+///     if let Ok(torrent) = torrent {
+///         let links = client.unrestrict().links()
+///             .links(torrent.links.clone())
+///             .send()
+///             .await
+///             .into_iter()
+///             .filter_map(Result::ok)
+///             .collect::<Vec<_>>();
+///
+///         let mut playlist = Playlist::from_torrent(&torrent, &links);
+///         playlist.sort_by_episode();
+///
+///         println!("{}", playlist.to_m3u());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Playlist {
+    /// Entries, in playing order
+    pub entries: Vec<PlaylistEntry>,
+}
+
+impl Playlist {
+    /// Creates an empty playlist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry at the end of the playlist.
+    pub fn push(&mut self, entry: PlaylistEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Creates a playlist of downloads.
+    pub fn from_downloads(downloads: &[Download]) -> Self {
+        downloads.iter().map(PlaylistEntry::from_download).collect()
+    }
+
+    /// Creates a playlist of the unrestricted links of a finished torrent.
+    ///
+    /// Paths follow the layout of the [`DownloadManager`](crate::download::DownloadManager),
+    /// in a directory named after the torrent when it holds more than one link.
+    pub fn from_torrent(torrent: &TorrentInfo, links: &[Link]) -> Self {
+        let directory = (torrent.links.len() > 1).then(|| sanitize(&torrent.filename));
+
+        links
+            .iter()
+            .map(|link| {
+                let entry = PlaylistEntry::from_link(link);
+
+                match &directory {
+                    Some(directory) => {
+                        entry.with_path(format!("{}/{}", directory, sanitize(&link.filename)))
+                    }
+                    None => entry,
+                }
+            })
+            .collect()
+    }
+
+    /// Sort entries by season then episode, entries without one last, then by title.
+    pub fn sort_by_episode(&mut self) {
+        self.entries.sort_by(|a, b| {
+            by_number(a.season, b.season)
+                .then_with(|| by_number(a.episode, b.episode))
+                .then_with(|| a.title.cmp(&b.title))
+        });
+    }
+
+    /// Render the playlist as extended M3U, pointing at links.
+    ///
+    /// The playlist is UTF-8 encoded, so it may be saved as `.m3u8` too.
+    pub fn to_m3u(&self) -> String {
+        self.render(|entry| &entry.url)
+    }
+
+    /// Render the playlist as extended M3U, pointing at the paths of downloaded files
+    /// relative to the playlist, and at links for entries without one.
+    pub fn to_m3u_relative(&self) -> String {
+        self.render(|entry| entry.path.as_deref().unwrap_or(&entry.url))
+    }
+
+    fn render(&self, location: impl Fn(&PlaylistEntry) -> &str) -> String {
+        let mut m3u = String::from("#EXTM3U\n");
+
+        for entry in &self.entries {
+            let duration = entry
+                .duration
+                .map_or(-1, |duration| duration.round() as i64);
+            let title = entry.title.replace(['\r', '\n'], " ");
+
+            // Writing to a String cannot fail
+            let _ = writeln!(m3u, "#EXTINF:{},{}", duration, title);
+            let _ = writeln!(m3u, "{}", location(entry));
+        }
+
+        m3u
+    }
+}
+
+impl FromIterator<PlaylistEntry> for Playlist {
+    fn from_iter<I: IntoIterator<Item = PlaylistEntry>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<PlaylistEntry> for Playlist {
+    fn extend<I: IntoIterator<Item = PlaylistEntry>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+/// Filename without its extension.
+fn stem(filename: &str) -> &str {
+    match filename.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && extension.len() <= 4 => stem,
+        _ => filename,
+    }
+}
+
+/// Orders numbers ascending, `None` last.
+fn by_number(a: Option<u32>, b: Option<u32>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem() {
+        assert_eq!(stem("Show.S01E02.mkv"), "Show.S01E02");
+        assert_eq!(stem("Show.S01E02"), "Show.S01E02");
+        assert_eq!(stem(".mkv"), ".mkv");
+    }
+}
@@ -0,0 +1,112 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    models::settings::{Setting, SettingName},
+    Error, Result,
+};
+
+/// Declarative configuration of a RealDebrid account, read from TOML or JSON.
+///
+/// Every field is optional, leaving the matching part of the account untouched.
+///
+/// # Examples
+/// ```
+/// use debrid::profile::Profile;
+///
+/// let profile = Profile::from_json(r#"
+///     {
+///         "settings": { "locale": "en", "streaming_quality": "original" },
+///         "hosts": ["1fichier.com"],
+///         "retention": { "torrents": { "max_age_days": 30, "max_count": 200 } }
+///     }
+/// "#).unwrap();
+///
+/// assert_eq!(profile.settings().unwrap().len(), 2);
+/// assert_eq!(profile.retention.torrents.unwrap().max_age_days, Some(30));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// Setting values by API name (e.g. `locale = "en"`), see [`SettingName`]
+    pub settings: BTreeMap<String, String>,
+    /// Avatar image uploaded while the account has the default avatar
+    pub avatar: Option<PathBuf>,
+    /// Hosts expected to be supported and up on RealDebrid
+    pub hosts: Vec<String>,
+    /// Retention of the torrents and downloads
+    pub retention: Retention,
+}
+
+/// Retention policies of a [`Profile`], nothing being deleted without one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Retention {
+    /// Policy of the finished torrents
+    pub torrents: Option<RetentionPolicy>,
+    /// Policy of the downloads
+    pub downloads: Option<RetentionPolicy>,
+}
+
+/// Limits after which torrents or downloads are deleted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPolicy {
+    /// Days after which an entry is deleted
+    pub max_age_days: Option<u64>,
+    /// Entries kept, the oldest ones beyond being deleted
+    pub max_count: Option<usize>,
+}
+
+impl Profile {
+    /// Parses a JSON profile.
+    pub fn from_json(s: &str) -> Result<Self> {
+        serde_json::from_str(s).map_err(Error::Json)
+    }
+
+    /// Parses a TOML profile.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self> {
+        toml::from_str(s).map_err(|e| Error::InvalidProfile(e.to_string()))
+    }
+
+    /// Reads a profile file, as TOML when its extension is `.toml` and as JSON otherwise.
+    ///
+    /// A relative avatar path is resolved against the directory of the profile.
+    pub async fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = tokio::fs::read_to_string(path).await.map_err(Error::Io)?;
+
+        let mut profile = match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml(&contents)?,
+            #[cfg(not(feature = "toml"))]
+            Some("toml") => {
+                return Err(Error::InvalidProfile(
+                    "TOML profiles need the \"toml\" feature".to_string(),
+                ))
+            }
+            _ => Self::from_json(&contents)?,
+        };
+
+        if let (Some(avatar), Some(dir)) = (&profile.avatar, path.parent()) {
+            profile.avatar = Some(dir.join(avatar));
+        }
+
+        Ok(profile)
+    }
+
+    /// Setting updates of the profile, sorted by name.
+    pub fn settings(&self) -> Result<Vec<Setting>> {
+        self.settings
+            .iter()
+            .map(|(name, value)| {
+                SettingName::from_name(name)
+                    .map(|name| Setting::new(name, value.clone()))
+                    .ok_or_else(|| Error::InvalidProfile(format!("unknown setting {}", name)))
+            })
+            .collect()
+    }
+}
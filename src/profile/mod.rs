@@ -0,0 +1,199 @@
+use bon::bon;
+use chrono::Utc;
+
+use crate::{Debrid, Error, Result};
+
+mod config;
+mod plan;
+
+pub use config::*;
+pub use plan::{Action, HostWarning, Plan, RetentionReason};
+
+/// Max entries of the downloads and torrents lists checked against retention policies.
+const LIST_LIMIT: u64 = 5000;
+
+/// Outcome of applying a [`Profile`].
+#[derive(Debug, Default)]
+pub struct ProvisionReport {
+    /// Plan of the account before applying the profile
+    pub plan: Plan,
+    /// Actions applied, empty on dry runs
+    pub applied: Vec<Action>,
+    /// Actions which failed, with their error
+    pub failed: Vec<(Action, Error)>,
+}
+
+/// Brings RealDebrid accounts in line with a declarative [`Profile`].
+///
+/// The account is compared with the profile through `settings().get()`, `user().get()`,
+/// `hosts().status()` and the torrents and downloads lists, only fetching what the
+/// profile configures. Applying a profile twice does nothing the second time.
+pub struct Provisioner<'rd>(pub(crate) &'rd Debrid);
+
+#[bon]
+impl<'rd> Provisioner<'rd> {
+    /// Compare the account with a profile, without changing anything.
+    ///
+    /// Setting values are checked against the values advertised by `settings().get()`,
+    /// failing with [`Error::InvalidSetting`] before anything is planned.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{profile::Profile, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let profile = Profile::from_json(r#"{ "settings": { "locale": "en" } }"#)?;
+    ///
+    ///     let plan = client.provisioner().plan()
+    ///         .profile(&profile)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(plan) = plan {
+    ///         print!("{}", plan);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn plan(
+        &self,
+        /// Profile to compare the account with
+        profile: &Profile,
+    ) -> Result<Plan> {
+        let mut plan = Plan::default();
+        let now = Utc::now();
+
+        let desired = profile.settings()?;
+        if !desired.is_empty() {
+            let settings = self.0.settings().get().send().await?;
+            plan.actions
+                .extend(plan::setting_actions(&settings, &desired)?);
+        }
+
+        if let Some(avatar) = &profile.avatar {
+            let user = self.0.user().get().send().await?;
+            plan.actions.extend(plan::avatar_action(&user, avatar));
+        }
+
+        if let Some(policy) = &profile.retention.torrents {
+            let torrents = self.0.torrents().get().limit(LIST_LIMIT).send().await?;
+            plan.actions
+                .extend(plan::torrent_actions(&torrents, policy, now));
+        }
+
+        if let Some(policy) = &profile.retention.downloads {
+            let downloads = self
+                .0
+                .downloads()
+                .get()
+                .limit(LIST_LIMIT as usize)
+                .send()
+                .await?;
+            plan.actions
+                .extend(plan::download_actions(&downloads, policy, now));
+        }
+
+        if !profile.hosts.is_empty() {
+            let status = self.0.hosts().status().send().await?;
+            plan.hosts = plan::host_warnings(&profile.hosts, &status);
+        }
+
+        Ok(plan)
+    }
+
+    /// Plan the account against a profile then apply the plan, unless on a dry run.
+    ///
+    /// Every action is tried, those failing being reported with their error.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{profile::Profile, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let profile = Profile::from_json(r#"
+    ///         {
+    ///             "settings": { "download_port": "secured" },
+    ///             "retention": { "downloads": { "max_age_days": 7 } }
+    ///         }
+    ///     "#)?;
+    ///
+    ///     let report = client.provisioner().apply()
+    ///         .profile(&profile)
+    ///         .dry_run(true)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(report) = report {
+    ///         print!("{}", report.plan);
+    ///
+    ///         for (action, e) in report.failed {
+    ///             println!("{} failed: {}", action, e);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = send)]
+    pub async fn apply(
+        &self,
+        /// Profile to apply
+        profile: &Profile,
+        /// Only plan the changes, default: false
+        dry_run: Option<bool>,
+    ) -> Result<ProvisionReport> {
+        let plan = self.plan().profile(profile).send().await?;
+        let mut report = ProvisionReport::default();
+
+        if !dry_run.unwrap_or(false) {
+            for action in &plan.actions {
+                match self.execute(action).await {
+                    Ok(()) => report.applied.push(action.clone()),
+                    Err(e) => report.failed.push((action.clone(), e)),
+                }
+            }
+        }
+
+        report.plan = plan;
+
+        Ok(report)
+    }
+}
+
+impl Provisioner<'_> {
+    async fn execute(&self, action: &Action) -> Result<()> {
+        match action {
+            Action::UpdateSetting { setting, .. } => {
+                self.0
+                    .settings()
+                    .update()
+                    .name(setting.name().to_string())
+                    .value(setting.value().to_string())
+                    .send()
+                    .await
+            }
+            Action::SetAvatar { file } => {
+                let file = tokio::fs::File::open(file).await.map_err(Error::Io)?;
+                self.0.settings().set_avatar().file(file).send().await
+            }
+            Action::DeleteTorrent { id, .. } => {
+                self.0.torrents().delete().id(id.clone()).send().await
+            }
+            Action::DeleteDownload { id, .. } => {
+                self.0.downloads().delete().id(id.clone()).send().await
+            }
+        }
+    }
+}
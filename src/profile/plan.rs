@@ -0,0 +1,400 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use super::RetentionPolicy;
+use crate::{
+    models::{
+        downloads::Download,
+        hosts::{HostInfo, HostStatus},
+        ids::{DownloadId, TorrentId},
+        settings::{Setting, Settings},
+        torrents::{Torrent, TorrentStatus},
+        user::User,
+    },
+    Result,
+};
+
+/// End of the avatar URL of accounts without a custom avatar.
+pub(crate) const DEFAULT_AVATAR: &str = "/images/forum/empty.png";
+
+/// Change planned to bring an account in line with its [`Profile`](super::Profile).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Update a setting from its current value
+    UpdateSetting {
+        setting: Setting,
+        current: Option<String>,
+    },
+    /// Upload an avatar image, replacing the default one
+    SetAvatar { file: PathBuf },
+    /// Delete a finished torrent
+    DeleteTorrent {
+        id: TorrentId,
+        filename: String,
+        reason: RetentionReason,
+    },
+    /// Delete a download
+    DeleteDownload {
+        id: DownloadId,
+        filename: String,
+        reason: RetentionReason,
+    },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::UpdateSetting { setting, current } => write!(
+                f,
+                "~ setting {}: {} -> {}",
+                setting.name(),
+                current.as_deref().unwrap_or("unset"),
+                setting.value()
+            ),
+            Action::SetAvatar { file } => write!(f, "+ avatar {}", file.display()),
+            Action::DeleteTorrent {
+                id,
+                filename,
+                reason,
+            } => write!(f, "- torrent {} {} ({})", id, filename, reason),
+            Action::DeleteDownload {
+                id,
+                filename,
+                reason,
+            } => write!(f, "- download {} {} ({})", id, filename, reason),
+        }
+    }
+}
+
+/// Limit of a [`RetentionPolicy`] an entry is deleted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionReason {
+    /// Older than the max age
+    Age { max_age_days: u64 },
+    /// Beyond the max count of entries kept
+    Count { max_count: usize },
+}
+
+impl fmt::Display for RetentionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetentionReason::Age { max_age_days } => {
+                write!(f, "older than {} days", max_age_days)
+            }
+            RetentionReason::Count { max_count } => write!(f, "beyond the {} newest", max_count),
+        }
+    }
+}
+
+/// Watched host which is not usable on RealDebrid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostWarning {
+    /// Host domain, as given in the profile
+    pub domain: String,
+    /// Status of the host, `None` if RealDebrid does not know it
+    pub status: Option<HostStatus>,
+}
+
+impl fmt::Display for HostWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.status {
+            None => write!(f, "! host {} is not known to RealDebrid", self.domain),
+            Some(HostStatus::Down) => write!(f, "! host {} is down", self.domain),
+            Some(HostStatus::Unsupported) => write!(f, "! host {} is unsupported", self.domain),
+            Some(HostStatus::Up) => write!(f, "! host {} is up", self.domain),
+            Some(HostStatus::Unknown(status)) => {
+                write!(f, "! host {} is {}", self.domain, status)
+            }
+        }
+    }
+}
+
+/// Changes bringing an account in line with its profile, printable as a diff.
+///
+/// Planning an account right after applying its plan gives an empty plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    /// Changes to apply, in order
+    pub actions: Vec<Action>,
+    /// Watched hosts which are down or unsupported, nothing being done about them
+    pub hosts: Vec<HostWarning>,
+}
+
+impl Plan {
+    /// Whether or not the account already matches the profile.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.actions.is_empty() {
+            writeln!(f, "No changes")?;
+        }
+
+        for action in &self.actions {
+            writeln!(f, "{}", action)?;
+        }
+
+        for host in &self.hosts {
+            writeln!(f, "{}", host)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Setting updates differing from the current settings, validated first.
+pub(crate) fn setting_actions(settings: &Settings, desired: &[Setting]) -> Result<Vec<Action>> {
+    Ok(settings
+        .diff(desired)?
+        .into_iter()
+        .map(|setting| Action::UpdateSetting {
+            current: settings.current(setting.name()).map(str::to_string),
+            setting,
+        })
+        .collect())
+}
+
+/// Avatar upload, only while the user has the default avatar since the uploaded
+/// image cannot be compared with a file.
+pub(crate) fn avatar_action(user: &User, file: &Path) -> Option<Action> {
+    user.avatar
+        .ends_with(DEFAULT_AVATAR)
+        .then(|| Action::SetAvatar {
+            file: file.to_path_buf(),
+        })
+}
+
+/// Watched hosts which are missing, down or unsupported.
+pub(crate) fn host_warnings(
+    hosts: &[String],
+    status: &HashMap<String, HostInfo>,
+) -> Vec<HostWarning> {
+    hosts
+        .iter()
+        .filter_map(|domain| {
+            let status = match status.get(&domain.to_ascii_lowercase()) {
+                None => None,
+                Some(info) if !info.supported => Some(HostStatus::Unsupported),
+                Some(info) => match info.status {
+                    HostStatus::Down | HostStatus::Unsupported => Some(info.status.clone()),
+                    _ => return None,
+                },
+            };
+
+            Some(HostWarning {
+                domain: domain.clone(),
+                status,
+            })
+        })
+        .collect()
+}
+
+/// Deletions of the finished torrents beyond the policy, oldest first.
+///
+/// Torrents still being processed are neither deleted nor counted.
+pub(crate) fn torrent_actions(
+    torrents: &[Torrent],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<Action> {
+    let finished = torrents
+        .iter()
+        .filter(|torrent| is_finished(&torrent.status));
+
+    expired(finished, |torrent| &torrent.added, policy, now)
+        .into_iter()
+        .map(|(torrent, reason)| Action::DeleteTorrent {
+            id: torrent.id.clone(),
+            filename: torrent.filename.clone(),
+            reason,
+        })
+        .collect()
+}
+
+/// Deletions of the downloads beyond the policy, oldest first.
+pub(crate) fn download_actions(
+    downloads: &[Download],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<Action> {
+    expired(downloads, |download| &download.generated, policy, now)
+        .into_iter()
+        .map(|(download, reason)| Action::DeleteDownload {
+            id: download.id.clone(),
+            filename: download.filename.clone(),
+            reason,
+        })
+        .collect()
+}
+
+fn is_finished(status: &TorrentStatus) -> bool {
+    matches!(
+        status,
+        TorrentStatus::Downloaded
+            | TorrentStatus::MagnetError
+            | TorrentStatus::Error
+            | TorrentStatus::Virus
+            | TorrentStatus::Dead
+    )
+}
+
+/// Entries beyond the policy, oldest first. Entries with an unreadable date are kept.
+fn expired<'a, T>(
+    entries: impl IntoIterator<Item = &'a T>,
+    date: impl Fn(&T) -> &str,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<(&'a T, RetentionReason)> {
+    let mut dated = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let date = DateTime::parse_from_rfc3339(date(entry)).ok()?;
+            Some((entry, date.with_timezone(&Utc)))
+        })
+        .collect::<Vec<_>>();
+    // Newest first, so that the index is the rank of the entry
+    dated.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    let mut expired = dated
+        .into_iter()
+        .enumerate()
+        .filter_map(|(rank, (entry, date))| {
+            let too_old = policy.max_age_days.filter(|days| {
+                i64::try_from(*days)
+                    .ok()
+                    .and_then(TimeDelta::try_days)
+                    .is_some_and(|max_age| now - date > max_age)
+            });
+
+            if let Some(max_age_days) = too_old {
+                Some((entry, RetentionReason::Age { max_age_days }))
+            } else {
+                policy
+                    .max_count
+                    .filter(|max_count| rank >= *max_count)
+                    .map(|max_count| (entry, RetentionReason::Count { max_count }))
+            }
+        })
+        .collect::<Vec<_>>();
+    expired.reverse();
+
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn torrent(id: &str, added: &str, status: TorrentStatus) -> Torrent {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "filename": format!("{}.mkv", id),
+            "hash": "c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab",
+            "bytes": 1,
+            "host": "example.com",
+            "split": 2000,
+            "progress": 100,
+            "status": status,
+            "added": added,
+            "links": [],
+        }))
+        .unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-10-01T00:00:00.000Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_retention_by_age_and_count() {
+        let torrents = [
+            torrent(
+                "AAAAAAAAAAAAA",
+                "2024-09-30T00:00:00.000Z",
+                TorrentStatus::Downloaded,
+            ),
+            torrent(
+                "BBBBBBBBBBBBB",
+                "2024-08-01T00:00:00.000Z",
+                TorrentStatus::Downloaded,
+            ),
+            torrent(
+                "CCCCCCCCCCCCC",
+                "2024-09-20T00:00:00.000Z",
+                TorrentStatus::Dead,
+            ),
+            torrent(
+                "DDDDDDDDDDDDD",
+                "2024-09-25T00:00:00.000Z",
+                TorrentStatus::Downloaded,
+            ),
+            torrent(
+                "EEEEEEEEEEEEE",
+                "2024-01-01T00:00:00.000Z",
+                TorrentStatus::Downloading,
+            ),
+            torrent("FFFFFFFFFFFFF", "not a date", TorrentStatus::Downloaded),
+        ];
+        let policy = RetentionPolicy {
+            max_age_days: Some(30),
+            max_count: Some(2),
+        };
+
+        let actions = torrent_actions(&torrents, &policy, now());
+        let deleted = actions
+            .iter()
+            .map(|action| match action {
+                Action::DeleteTorrent { id, reason, .. } => (id.as_str(), *reason),
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            deleted,
+            vec![
+                ("BBBBBBBBBBBBB", RetentionReason::Age { max_age_days: 30 }),
+                ("CCCCCCCCCCCCC", RetentionReason::Count { max_count: 2 }),
+            ]
+        );
+        assert!(torrent_actions(&torrents, &RetentionPolicy::default(), now()).is_empty());
+    }
+
+    #[test]
+    fn test_display_plan() {
+        let plan = Plan {
+            actions: vec![
+                Action::UpdateSetting {
+                    setting: Setting::Locale("fr".to_string()),
+                    current: Some("en".to_string()),
+                },
+                Action::DeleteDownload {
+                    id: DownloadId::new("ABCDEFGHIJKLMNO").unwrap(),
+                    filename: "file.mkv".to_string(),
+                    reason: RetentionReason::Count { max_count: 100 },
+                },
+            ],
+            hosts: vec![HostWarning {
+                domain: "example.com".to_string(),
+                status: Some(HostStatus::Down),
+            }],
+        };
+
+        assert_eq!(
+            plan.to_string(),
+            "~ setting locale: en -> fr\n\
+             - download ABCDEFGHIJKLMNO file.mkv (beyond the 100 newest)\n\
+             ! host example.com is down\n"
+        );
+        assert_eq!(Plan::default().to_string(), "No changes\n");
+    }
+}
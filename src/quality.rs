@@ -0,0 +1,331 @@
+use std::sync::LazyLock;
+
+use bon::bon;
+use regex::Regex;
+
+use crate::models::unrestrict::{AlternativeLink, Link, Resolution};
+
+/// Height in a quality label, such as `1080p`, `720p60` or `1280x720`.
+static HEIGHT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\d+x(\d+)|(\d{3,4})p)").expect("valid quality height regex")
+});
+
+/// Extensions of audio only files.
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "m4a", "aac", "ogg", "oga", "opus", "flac", "wav"];
+
+/// Extensions of MP4 files.
+const MP4_EXTENSIONS: &[&str] = &["mp4", "m4v", "m4a"];
+
+/// Represents a downloadable variant of an unrestricted link, the main one or an alternative.
+#[derive(Debug, Clone, Copy)]
+pub enum Variant<'l> {
+    Main(&'l Link),
+    Alternative(&'l AlternativeLink),
+}
+
+impl<'l> Variant<'l> {
+    /// All variants of a link, the main one first.
+    pub fn all(link: &'l Link) -> Vec<Self> {
+        std::iter::once(Variant::Main(link))
+            .chain(link.alternative.iter().flatten().map(Variant::Alternative))
+            .collect()
+    }
+
+    /// Generated download link
+    pub fn download(&self) -> &'l str {
+        match self {
+            Variant::Main(link) => &link.download,
+            Variant::Alternative(link) => &link.download,
+        }
+    }
+
+    /// Original filename
+    pub fn filename(&self) -> &'l str {
+        match self {
+            Variant::Main(link) => &link.filename,
+            Variant::Alternative(link) => &link.filename,
+        }
+    }
+
+    /// MIME type of the file, if applicable
+    pub fn mime_type(&self) -> Option<&'l str> {
+        match self {
+            Variant::Main(link) => link.mime_type.as_deref(),
+            Variant::Alternative(link) => link.mime_type.as_deref(),
+        }
+    }
+
+    /// Quality label of the file, if applicable, falling back to its type
+    pub fn quality(&self) -> Option<&'l str> {
+        let (quality, type_) = match self {
+            Variant::Main(link) => (&link.quality, &link.type_),
+            Variant::Alternative(link) => (&link.quality, &link.type_),
+        };
+
+        quality.as_deref().or(type_.as_deref())
+    }
+
+    /// Video height parsed from the quality label, if known.
+    pub fn height(&self) -> Option<u32> {
+        self.quality().and_then(parse_height)
+    }
+
+    /// Whether or not the file only holds audio.
+    pub fn is_audio(&self) -> bool {
+        self.mime_type().map_or_else(
+            || self.has_extension(AUDIO_EXTENSIONS),
+            |mime| mime.starts_with("audio/"),
+        )
+    }
+
+    /// Whether or not the file is an MP4 container.
+    pub fn is_mp4(&self) -> bool {
+        self.mime_type().map_or_else(
+            || self.has_extension(MP4_EXTENSIONS),
+            |mime| mime.ends_with("/mp4"),
+        )
+    }
+
+    fn has_extension(&self, extensions: &[&str]) -> bool {
+        self.filename()
+            .rsplit_once('.')
+            .is_some_and(|(_, extension)| {
+                extensions.contains(&extension.to_ascii_lowercase().as_str())
+            })
+    }
+}
+
+/// Parses the video height of a quality label, such as `1080p`, `1280x720` or `HD`.
+/// Named qualities are approximated: `4K` 2160, `FHD` 1080, `HD` 720, `HQ` 480, `SD` 360 and `LQ` 240.
+pub fn parse_height(label: &str) -> Option<u32> {
+    if let Some(captures) = HEIGHT.captures(label) {
+        return captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .and_then(|height| height.as_str().parse().ok());
+    }
+
+    match label.trim().to_ascii_lowercase().as_str() {
+        "4k" | "uhd" => Some(2160),
+        "fhd" | "full hd" => Some(1080),
+        "hd" => Some(720),
+        "hq" => Some(480),
+        "sd" => Some(360),
+        "lq" => Some(240),
+        _ => None,
+    }
+}
+
+/// Preferences picking the best variant of an unrestricted link.
+#[derive(Debug, Clone, Default)]
+pub struct QualityPreference {
+    max_height: Option<u32>,
+    prefer_mp4: bool,
+    audio_only: bool,
+}
+
+#[bon]
+impl QualityPreference {
+    /// Creates new quality preferences.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{quality::QualityPreference, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let link = client.unrestrict().link()
+    ///         .link("https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     let preference = QualityPreference::builder()
+    ///         .max_height(720)
+    ///         .prefer_mp4(true)
+    ///         .build();
+    ///
+    ///     if let Ok(link) = link {
+    ///         if let Some(variant) = preference.select(&link) {
+    ///             println!("{:?}: {}", variant.quality(), variant.download());
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// Max video height, such as 720, default: none
+        max_height: Option<u32>,
+        /// Prefer MP4 files over any other quality, default: false
+        prefer_mp4: Option<bool>,
+        /// Only pick audio files, default: false
+        audio_only: Option<bool>,
+    ) -> Self {
+        Self {
+            max_height,
+            prefer_mp4: prefer_mp4.unwrap_or(false),
+            audio_only: audio_only.unwrap_or(false),
+        }
+    }
+}
+
+impl QualityPreference {
+    /// Pick the best variant of a link, or `None` if none matches the preferences.
+    ///
+    /// Video variants are ranked by height, unknown heights last, and are skipped
+    /// when above the max height. Ties go to the main link.
+    pub fn select<'l>(&self, link: &'l Link) -> Option<Variant<'l>> {
+        Variant::all(link)
+            .into_iter()
+            .filter(|variant| variant.is_audio() == self.audio_only)
+            .filter(|variant| {
+                self.audio_only
+                    || self.max_height.map_or(true, |max| {
+                        variant.height().map_or(true, |height| height <= max)
+                    })
+            })
+            .rev()
+            .max_by_key(|variant| {
+                let height = variant.height();
+                (
+                    self.prefer_mp4 && variant.is_mp4(),
+                    height.is_some(),
+                    height,
+                )
+            })
+    }
+
+    /// Pick the best resolution listed by `Check::resolutions`, or `None` if none
+    /// matches the preferences, such as when only picking audio files.
+    pub fn select_resolution(&self, resolutions: &[Resolution]) -> Option<Resolution> {
+        if self.audio_only {
+            return None;
+        }
+
+        resolutions
+            .iter()
+            .filter(|resolution| self.max_height.map_or(true, |max| resolution.height <= max))
+            .max_by_key(|resolution| resolution.height)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link() -> Link {
+        serde_json::from_str(
+            r#"
+            {
+                "id": "ABCDEFGHIJKLMNO",
+                "filename": "video.webm",
+                "mimeType": "video/webm",
+                "filesize": 0,
+                "link": "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "host": "youtube.com",
+                "chunks": 32,
+                "crc": 1,
+                "download": "https://example.com/d/main/video.webm",
+                "streamable": 1,
+                "quality": "1080p",
+                "alternative": [
+                    {
+                        "id": "ABCDEFGHIJKLMNO",
+                        "filename": "video.mp4",
+                        "mimeType": "video/mp4",
+                        "download": "https://example.com/d/720/video.mp4",
+                        "quality": "720p"
+                    },
+                    {
+                        "id": "ABCDEFGHIJKLMNO",
+                        "filename": "video.mp4",
+                        "mimeType": "video/mp4",
+                        "download": "https://example.com/d/hq/video.mp4",
+                        "quality": "HQ"
+                    },
+                    {
+                        "id": "ABCDEFGHIJKLMNO",
+                        "filename": "video.webm",
+                        "mimeType": "video/webm",
+                        "download": "https://example.com/d/unknown/video.webm",
+                        "quality": "original"
+                    },
+                    {
+                        "id": "ABCDEFGHIJKLMNO",
+                        "filename": "audio.mp3",
+                        "download": "https://example.com/d/audio/audio.mp3"
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_height() {
+        assert_eq!(parse_height("1080p"), Some(1080));
+        assert_eq!(parse_height("720p60"), Some(720));
+        assert_eq!(parse_height("1280x720"), Some(720));
+        assert_eq!(parse_height("HQ"), Some(480));
+        assert_eq!(parse_height("original"), None);
+    }
+
+    #[test]
+    fn test_select_resolution() {
+        let resolutions = ["1920x1080", "1280x720", "854x480"]
+            .map(|resolution| resolution.parse::<Resolution>().unwrap());
+
+        assert_eq!(
+            QualityPreference::builder()
+                .max_height(720)
+                .build()
+                .select_resolution(&resolutions),
+            Some(resolutions[1])
+        );
+        assert_eq!(
+            QualityPreference::builder()
+                .audio_only(true)
+                .build()
+                .select_resolution(&resolutions),
+            None
+        );
+    }
+
+    #[test]
+    fn test_select_highest_quality() {
+        let link = link();
+        let select = |preference: QualityPreference| {
+            preference
+                .select(&link)
+                .map(|variant| variant.download().to_string())
+        };
+
+        assert_eq!(
+            select(QualityPreference::default()).as_deref(),
+            Some("https://example.com/d/main/video.webm")
+        );
+        assert_eq!(
+            select(QualityPreference::builder().max_height(700).build()).as_deref(),
+            Some("https://example.com/d/hq/video.mp4")
+        );
+        assert_eq!(
+            select(QualityPreference::builder().prefer_mp4(true).build()).as_deref(),
+            Some("https://example.com/d/720/video.mp4")
+        );
+        assert_eq!(
+            select(QualityPreference::builder().audio_only(true).build()).as_deref(),
+            Some("https://example.com/d/audio/audio.mp3")
+        );
+        assert_eq!(
+            select(QualityPreference::builder().max_height(100).build()).as_deref(),
+            Some("https://example.com/d/unknown/video.webm")
+        );
+    }
+}
@@ -0,0 +1,733 @@
+use std::{cell::Cell, ops::Range, sync::LazyLock};
+
+use regex::{Captures, Regex};
+
+/// Extensions stripped from release names.
+const EXTENSIONS: &[&str] = &[
+    "mkv", "mp4", "m4v", "avi", "mov", "wmv", "webm", "flv", "mpg", "mpeg", "ts", "m2ts", "iso",
+    "srt", "ass", "sub", "idx", "nfo", "txt", "sfv", "jpg", "png", "mp3", "flac", "m4a", "rar",
+    "zip", "7z", "torrent",
+];
+
+/// Builds a regex matching one of `alternatives` as a whole word, its first group being the word.
+fn word(alternatives: &str) -> Regex {
+    Regex::new(&format!(
+        r"(?i)(?:^|[\s._\-\[\(])({})(?:$|[\s._\-\]\)])",
+        alternatives
+    ))
+    .expect("valid release regex")
+}
+
+static WEBSITE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)[\[\(]?\s*www\.[a-z0-9\-]+\.[a-z]{2,4}\s*[\]\)]?\s*-?")
+        .expect("valid website regex")
+});
+static LEADING_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]\s*").expect("valid tag regex"));
+static TRAILING_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*\[[^\]]*\]\s*$").expect("valid tag regex"));
+static YEAR: LazyLock<Regex> = LazyLock::new(|| word(r"(?:19|20)\d{2}"));
+static EPISODES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|[\s._\-\[\(])(s(\d{1,2})[\s._-]?(e\d{1,3}(?:[\s._]?e\d{1,3}|-e?\d{1,3})*))(?:$|[^\d])")
+        .expect("valid episodes regex")
+});
+static EPISODE_NUMBERS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(-?)e?(\d{1,3})").expect("valid episode regex"));
+static CROSS_EPISODE: LazyLock<Regex> = LazyLock::new(|| word(r"(\d{1,2})x(\d{2,3})"));
+static SEASONS: LazyLock<Regex> = LazyLock::new(|| {
+    word(
+        r"s(\d{1,2})(?:[\s._]?-[\s._]?s?(\d{1,2}))?|seasons?[\s._]?(\d{1,2})(?:[\s._]?-[\s._]?(\d{1,2}))?",
+    )
+});
+static LONE_EPISODE: LazyLock<Regex> = LazyLock::new(|| word(r"(?:episode|ep)[\s._]?(\d{1,3})"));
+static ABSOLUTE_EPISODE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\s-\s(\d{1,3})(?:v\d)?(?:$|[\s\[\(])").expect("valid absolute episode regex")
+});
+static RESOLUTION: LazyLock<Regex> =
+    LazyLock::new(|| word(r"(\d{3,4})[pi]|\d{3,4}x(\d{3,4})|4k|uhd"));
+static SOURCE: LazyLock<Regex> = LazyLock::new(|| {
+    word(
+        r"blu-?ray|bdrip|brrip|bdremux|bd|web-?dl|webrip|web|hdtv|pdtv|sdtv|dvdrip|dvdscr|dvd\d?|hdrip|hdcam|cam|hdts|telesync|telecine",
+    )
+});
+static CODEC: LazyLock<Regex> =
+    LazyLock::new(|| word(r"[xh][\s.]?264|avc|[xh][\s.]?265|hevc|xvid|divx|av1|vp9|mpeg-?2"));
+static HDR: LazyLock<Regex> =
+    LazyLock::new(|| word(r"hdr10\+|hdr10plus|hdr10|hdr|dolby[\s.]?vision|dovi|dv|hlg"));
+static AUDIO: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:^|[\s._\-\[\(])(ddp|dd\+|e-?ac-?3|dts-hd[\s._-]?ma|dts-hd|dts-?x|dts|truehd|atmos|dd|ac3|aac|flac|mp3|opus)(?:\d|$|[\s._\-\]\)])",
+    )
+    .expect("valid audio regex")
+});
+static CHANNELS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|[^\d.]|[^\d]\.)([2567])[\s.]([01])(?:$|[^\d])")
+        .expect("valid channels regex")
+});
+static FLAG: LazyLock<Regex> = LazyLock::new(|| {
+    word(
+        r"repack|proper|real|extended|unrated|uncut|remastered|directors?[\s._]?cut|internal|limited|remux|complete|multi|dubbed|subbed|3d",
+    )
+});
+
+/// Source of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReleaseSource {
+    BluRay,
+    WebDl,
+    WebRip,
+    Hdtv,
+    Dvd,
+    HdRip,
+    Cam,
+    Telesync,
+    Telecine,
+}
+
+/// Video codec of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoCodec {
+    H264,
+    H265,
+    Xvid,
+    Av1,
+    Vp9,
+    Mpeg2,
+}
+
+/// High dynamic range format of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hdr {
+    Hdr,
+    Hdr10,
+    Hdr10Plus,
+    DolbyVision,
+    Hlg,
+}
+
+/// Audio format of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioFormat {
+    Aac,
+    Ac3,
+    Eac3,
+    Dts,
+    DtsHd,
+    DtsHdMa,
+    DtsX,
+    TrueHd,
+    Atmos,
+    Flac,
+    Mp3,
+    Opus,
+}
+
+/// Flag of a release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReleaseFlag {
+    Repack,
+    Proper,
+    Real,
+    Extended,
+    Unrated,
+    Uncut,
+    Remastered,
+    DirectorsCut,
+    Internal,
+    Limited,
+    Remux,
+    Complete,
+    Multi,
+    Dubbed,
+    Subbed,
+    ThreeD,
+}
+
+/// Represents what a release name tells about its contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Release {
+    /// Title, with separators replaced by spaces
+    pub title: String,
+    /// Year, if applicable
+    pub year: Option<u32>,
+    /// Seasons, in order, a range being expanded (e.g. `S01-S03`)
+    pub seasons: Vec<u32>,
+    /// Episodes, in order, a range being expanded (e.g. `S01E01-E03`)
+    pub episodes: Vec<u32>,
+    /// Vertical resolution (e.g. 1080), if known
+    pub resolution: Option<u32>,
+    /// Source, if known
+    pub source: Option<ReleaseSource>,
+    /// Video codec, if known
+    pub codec: Option<VideoCodec>,
+    /// High dynamic range formats
+    pub hdr: Vec<Hdr>,
+    /// Audio formats
+    pub audio: Vec<AudioFormat>,
+    /// Audio channels (e.g. "5.1"), if known
+    pub channels: Option<String>,
+    /// Release group, if known
+    pub group: Option<String>,
+    /// Flags (e.g. REPACK, PROPER)
+    pub flags: Vec<ReleaseFlag>,
+}
+
+impl Release {
+    /// Parses a release name, such as a torrent name, a filename or a path inside a torrent.
+    ///
+    /// Directories, known extensions and website tags are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::release::{Release, ReleaseFlag, ReleaseSource, VideoCodec};
+    ///
+    /// let release = Release::parse("/Show.Name.S01E01-E03.REPACK.1080p.WEB-DL.DDP5.1.H.264-GROUP.mkv");
+    ///
+    /// assert_eq!(release.title, "Show Name");
+    /// assert_eq!(release.seasons, vec![1]);
+    /// assert_eq!(release.episodes, vec![1, 2, 3]);
+    /// assert_eq!(release.resolution, Some(1080));
+    /// assert_eq!(release.source, Some(ReleaseSource::WebDl));
+    /// assert_eq!(release.codec, Some(VideoCodec::H264));
+    /// assert_eq!(release.channels.as_deref(), Some("5.1"));
+    /// assert_eq!(release.group.as_deref(), Some("GROUP"));
+    /// assert_eq!(release.flags, vec![ReleaseFlag::Repack]);
+    /// ```
+    pub fn parse(name: &str) -> Self {
+        let mut name = name
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(name)
+            .trim()
+            .to_string();
+
+        if let Some((stem, extension)) = name.rsplit_once('.') {
+            if EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()) {
+                name = stem.to_string();
+            }
+        }
+
+        let mut name = WEBSITE.replace_all(&name, " ").trim().to_string();
+        let mut release = Release::default();
+
+        // Anime style leading group, such as "[Group] Title - 01"
+        if let Some(captures) = LEADING_TAG.captures(&name) {
+            release.group = Some(captures[1].trim().to_string());
+            name = name[captures.get(0).map_or(0, |m| m.end())..].to_string();
+        }
+        // Trailing tags such as a checksum, unless they hold markers (e.g. "[1080p]")
+        while let Some(tag) = TRAILING_TAG.find(&name) {
+            if has_marker(tag.as_str()) {
+                break;
+            }
+            name.truncate(tag.start());
+        }
+        let name = name.trim_matches(|c: char| c.is_whitespace() || matches!(c, '.' | '-' | '_'));
+
+        // Start of the first marker, ending the title
+        let end = Cell::new(name.len());
+        let found = |range: Range<usize>| end.set(end.get().min(range.start));
+        // End of the episode or season marker, whose dashes are not before a group
+        let mut numbering = 0;
+
+        if let Some(captures) = EPISODES.captures(name) {
+            numbering = group_range(&captures).end;
+            found(group_range(&captures));
+            release.seasons.push(number(&captures, 2).unwrap_or(0));
+            release.episodes = episode_numbers(&captures[3]);
+        } else if let Some(captures) = CROSS_EPISODE.captures(name) {
+            numbering = group_range(&captures).end;
+            found(group_range(&captures));
+            release.seasons.extend(number(&captures, 2));
+            release.episodes.extend(number(&captures, 3));
+        } else if let Some(captures) = SEASONS.captures(name) {
+            numbering = group_range(&captures).end;
+            found(group_range(&captures));
+            let first = number(&captures, 2).or_else(|| number(&captures, 4));
+            let last = number(&captures, 3).or_else(|| number(&captures, 5));
+
+            if let Some(first) = first {
+                release.seasons = match last {
+                    Some(last) if last > first => (first..=last).collect(),
+                    _ => vec![first],
+                };
+            }
+
+            if let Some(captures) = LONE_EPISODE.captures(name) {
+                release.episodes.extend(number(&captures, 2));
+            }
+        } else if let Some(captures) = LONE_EPISODE.captures(name) {
+            numbering = group_range(&captures).end;
+            found(group_range(&captures));
+            release.episodes.extend(number(&captures, 2));
+        } else if let Some(captures) = ABSOLUTE_EPISODE.captures(name) {
+            // Anime style absolute episode, such as "Title - 01"
+            numbering = group_range(&captures).end;
+            found(group_range(&captures));
+            release.episodes.extend(number(&captures, 1));
+        }
+
+        // The year is the last one before the quality markers, earlier ones and one
+        // opening the name being part of the title (e.g. "Blade.Runner.2049.2017.1080p")
+        if let Some(captures) = last_year(name) {
+            found(group_range(&captures));
+            release.year = captures[1].parse().ok();
+        }
+
+        if let Some(captures) = RESOLUTION.captures(name) {
+            found(group_range(&captures));
+            release.resolution = number(&captures, 2)
+                .or_else(|| number(&captures, 3))
+                .or(Some(2160));
+        }
+
+        // Descriptive words opening the name or before the year, episode or resolution
+        // are part of the title (e.g. "Real.Steel.2011", "The.Dv.Show.S01E01")
+        let anchor = end.get();
+        let descriptive = |range: Range<usize>| {
+            range.start > 0 && (anchor == name.len() || range.start >= anchor)
+        };
+
+        if let Some(captures) = first_word(&SOURCE, name, descriptive) {
+            found(group_range(&captures));
+            release.source = parse_source(&captures[1]);
+        }
+
+        if let Some(captures) = first_word(&CODEC, name, descriptive) {
+            found(group_range(&captures));
+            release.codec = parse_codec(&captures[1]);
+        }
+
+        for captures in filter_words(&HDR, name, descriptive) {
+            found(group_range(&captures));
+            push_unique(&mut release.hdr, parse_hdr(&captures[1]));
+        }
+
+        for captures in filter_words(&AUDIO, name, descriptive) {
+            found(group_range(&captures));
+            push_unique(&mut release.audio, parse_audio(&captures[1]));
+        }
+
+        if let Some(captures) = CHANNELS.captures(name) {
+            release.channels = Some(format!("{}.{}", &captures[1], &captures[2]));
+        }
+
+        for captures in filter_words(&FLAG, name, descriptive) {
+            found(group_range(&captures));
+            push_unique(&mut release.flags, parse_flag(&captures[1]));
+        }
+
+        let end = end.get();
+        let markers = end.max(numbering);
+        if release.group.is_none() && markers < name.len() {
+            release.group = parse_group(&name[markers..]);
+        }
+
+        release.title = clean_title(&name[..end]);
+        release
+    }
+
+    /// First season, if any.
+    pub fn season(&self) -> Option<u32> {
+        self.seasons.first().copied()
+    }
+
+    /// First episode, if any.
+    pub fn episode(&self) -> Option<u32> {
+        self.episodes.first().copied()
+    }
+
+    /// Whether or not the release holds episodes of a show.
+    pub fn is_episode(&self) -> bool {
+        !self.episodes.is_empty()
+    }
+
+    /// Whether or not the release holds whole seasons of a show.
+    pub fn is_season_pack(&self) -> bool {
+        !self.seasons.is_empty() && self.episodes.is_empty()
+    }
+}
+
+/// Range of the first group of a match, the word without its boundaries.
+fn group_range(captures: &Captures) -> Range<usize> {
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map_or(0..0, |m| m.range())
+}
+
+fn number(captures: &Captures, group: usize) -> Option<u32> {
+    captures.get(group).and_then(|m| m.as_str().parse().ok())
+}
+
+/// Every match of a word, boundaries being shared by consecutive words.
+fn iter_words<'n>(regex: &Regex, name: &'n str) -> Vec<Captures<'n>> {
+    let mut matches = Vec::new();
+    let mut from = 0;
+
+    while let Some(captures) = regex.captures_at(name, from) {
+        from = group_range(&captures).end;
+        matches.push(captures);
+    }
+
+    matches
+}
+
+/// Every match of a word accepted by a filter.
+fn filter_words<'n>(
+    regex: &Regex,
+    name: &'n str,
+    filter: impl Fn(Range<usize>) -> bool,
+) -> Vec<Captures<'n>> {
+    iter_words(regex, name)
+        .into_iter()
+        .filter(|captures| filter(group_range(captures)))
+        .collect()
+}
+
+/// First match of a word accepted by a filter.
+fn first_word<'n>(
+    regex: &Regex,
+    name: &'n str,
+    filter: impl Fn(Range<usize>) -> bool,
+) -> Option<Captures<'n>> {
+    filter_words(regex, name, filter).into_iter().next()
+}
+
+/// Whether or not a text holds any marker parsed from release names.
+fn has_marker(text: &str) -> bool {
+    [
+        &*YEAR,
+        &*EPISODES,
+        &*CROSS_EPISODE,
+        &*SEASONS,
+        &*LONE_EPISODE,
+        &*RESOLUTION,
+        &*SOURCE,
+        &*CODEC,
+        &*HDR,
+        &*AUDIO,
+        &*FLAG,
+    ]
+    .iter()
+    .any(|regex| regex.is_match(text))
+}
+
+/// Last year not opening the name and before the first resolution, source or codec
+/// following a year.
+fn last_year(name: &str) -> Option<Captures<'_>> {
+    let years = filter_words(&YEAR, name, |range| range.start > 0);
+    let first = group_range(years.first()?).end;
+
+    let limit = [&*RESOLUTION, &*SOURCE, &*CODEC]
+        .iter()
+        .filter_map(|regex| regex.captures_at(name, first))
+        .map(|captures| group_range(&captures).start)
+        .min()
+        .unwrap_or(name.len());
+
+    years
+        .into_iter()
+        .take_while(|captures| group_range(captures).start < limit)
+        .last()
+}
+
+/// Episodes of an `E01E02` or `E01-E03` sequence, ranges being expanded.
+/// The end of a range which does not follow its start is left out.
+fn episode_numbers(sequence: &str) -> Vec<u32> {
+    let mut episodes = Vec::<u32>::new();
+
+    for captures in EPISODE_NUMBERS.captures_iter(sequence) {
+        let Some(episode) = number(&captures, 2) else {
+            continue;
+        };
+
+        match episodes.last() {
+            Some(&last) if !captures[1].is_empty() => {
+                if episode > last {
+                    episodes.extend(last + 1..=episode)
+                }
+            }
+            _ => episodes.push(episode),
+        }
+    }
+
+    episodes
+}
+
+fn parse_source(value: &str) -> Option<ReleaseSource> {
+    let value = value.to_ascii_lowercase().replace('-', "");
+
+    Some(match value.as_str() {
+        "bluray" | "bdrip" | "brrip" | "bdremux" | "bd" => ReleaseSource::BluRay,
+        "webdl" | "web" => ReleaseSource::WebDl,
+        "webrip" => ReleaseSource::WebRip,
+        "hdtv" | "pdtv" | "sdtv" => ReleaseSource::Hdtv,
+        "hdrip" => ReleaseSource::HdRip,
+        "hdcam" | "cam" => ReleaseSource::Cam,
+        "hdts" | "telesync" => ReleaseSource::Telesync,
+        "telecine" => ReleaseSource::Telecine,
+        value if value.starts_with("dvd") => ReleaseSource::Dvd,
+        _ => return None,
+    })
+}
+
+fn parse_codec(value: &str) -> Option<VideoCodec> {
+    let value = value.to_ascii_lowercase().replace([' ', '.', '-'], "");
+
+    Some(match value.as_str() {
+        "x264" | "h264" | "avc" => VideoCodec::H264,
+        "x265" | "h265" | "hevc" => VideoCodec::H265,
+        "xvid" | "divx" => VideoCodec::Xvid,
+        "av1" => VideoCodec::Av1,
+        "vp9" => VideoCodec::Vp9,
+        "mpeg2" => VideoCodec::Mpeg2,
+        _ => return None,
+    })
+}
+
+fn parse_hdr(value: &str) -> Option<Hdr> {
+    let value = value.to_ascii_lowercase().replace([' ', '.'], "");
+
+    Some(match value.as_str() {
+        "hdr10+" | "hdr10plus" => Hdr::Hdr10Plus,
+        "hdr10" => Hdr::Hdr10,
+        "hdr" => Hdr::Hdr,
+        "dolbyvision" | "dovi" | "dv" => Hdr::DolbyVision,
+        "hlg" => Hdr::Hlg,
+        _ => return None,
+    })
+}
+
+fn parse_audio(value: &str) -> Option<AudioFormat> {
+    let value = value.to_ascii_lowercase().replace([' ', '.', '_', '-'], "");
+
+    Some(match value.as_str() {
+        "ddp" | "dd+" | "eac3" => AudioFormat::Eac3,
+        "dtshdma" => AudioFormat::DtsHdMa,
+        "dtshd" => AudioFormat::DtsHd,
+        "dtsx" => AudioFormat::DtsX,
+        "dts" => AudioFormat::Dts,
+        "truehd" => AudioFormat::TrueHd,
+        "atmos" => AudioFormat::Atmos,
+        "dd" | "ac3" => AudioFormat::Ac3,
+        "aac" => AudioFormat::Aac,
+        "flac" => AudioFormat::Flac,
+        "mp3" => AudioFormat::Mp3,
+        "opus" => AudioFormat::Opus,
+        _ => return None,
+    })
+}
+
+fn parse_flag(value: &str) -> Option<ReleaseFlag> {
+    let value = value.to_ascii_lowercase().replace([' ', '.', '_'], "");
+
+    Some(match value.as_str() {
+        "repack" => ReleaseFlag::Repack,
+        "proper" => ReleaseFlag::Proper,
+        "real" => ReleaseFlag::Real,
+        "extended" => ReleaseFlag::Extended,
+        "unrated" => ReleaseFlag::Unrated,
+        "uncut" => ReleaseFlag::Uncut,
+        "remastered" => ReleaseFlag::Remastered,
+        "directorscut" | "directorcut" => ReleaseFlag::DirectorsCut,
+        "internal" => ReleaseFlag::Internal,
+        "limited" => ReleaseFlag::Limited,
+        "remux" => ReleaseFlag::Remux,
+        "complete" => ReleaseFlag::Complete,
+        "multi" => ReleaseFlag::Multi,
+        "dubbed" => ReleaseFlag::Dubbed,
+        "subbed" => ReleaseFlag::Subbed,
+        "3d" => ReleaseFlag::ThreeD,
+        _ => return None,
+    })
+}
+
+/// Group after the last dash of the markers, unless the dash joins a marker (e.g. "WEB-DL").
+fn parse_group(markers: &str) -> Option<String> {
+    let (before, group) = markers.rsplit_once('-')?;
+    let group = group.trim();
+
+    let last_word = before.rsplit([' ', '.', '_']).next().unwrap_or_default();
+    let joined = format!("{}-{}", last_word, group);
+
+    let is_marker = [&*SOURCE, &*AUDIO, &*CODEC].iter().any(|regex| {
+        regex
+            .captures(&joined)
+            .is_some_and(|captures| group_range(&captures) == (0..joined.len()))
+    });
+
+    let valid = !group.is_empty()
+        && group.len() <= 24
+        && group.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !group.chars().all(|c| c.is_ascii_digit());
+
+    (valid && !is_marker).then(|| group.to_string())
+}
+
+fn clean_title(title: &str) -> String {
+    let title = title.replace(['.', '_'], " ");
+    let title = title
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '(' | '[' | '{'))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    title
+}
+
+fn push_unique<T: PartialEq>(values: &mut Vec<T>, value: Option<T>) {
+    if let Some(value) = value {
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_movie() {
+        let release = Release::parse(
+            "Big.Buck.Bunny.2008.2160p.UHD.BluRay.REMUX.HDR10.HEVC.TrueHD.Atmos.7.1-GRP.mkv",
+        );
+
+        assert_eq!(release.title, "Big Buck Bunny");
+        assert_eq!(release.year, Some(2008));
+        assert_eq!(release.resolution, Some(2160));
+        assert_eq!(release.source, Some(ReleaseSource::BluRay));
+        assert_eq!(release.codec, Some(VideoCodec::H265));
+        assert_eq!(release.hdr, vec![Hdr::Hdr10]);
+        assert_eq!(release.audio, vec![AudioFormat::TrueHd, AudioFormat::Atmos]);
+        assert_eq!(release.channels.as_deref(), Some("7.1"));
+        assert_eq!(release.group.as_deref(), Some("GRP"));
+        assert_eq!(release.flags, vec![ReleaseFlag::Remux]);
+        assert!(!release.is_episode());
+    }
+
+    #[test]
+    fn test_website_and_year_title() {
+        let release = Release::parse("Big.Buck.Bunny.BDRip.XviD-MEDiC.(www.USABIT.com).avi");
+
+        assert_eq!(release.title, "Big Buck Bunny");
+        assert_eq!(release.source, Some(ReleaseSource::BluRay));
+        assert_eq!(release.codec, Some(VideoCodec::Xvid));
+        assert_eq!(release.group.as_deref(), Some("MEDiC"));
+
+        let release = Release::parse("2012.2009.1080p.BluRay.x264");
+
+        assert_eq!(release.title, "2012");
+        assert_eq!(release.year, Some(2009));
+        assert_eq!(release.group, None);
+
+        let release = Release::parse("Blade.Runner.2049.2017.1080p");
+
+        assert_eq!(release.title, "Blade Runner 2049");
+        assert_eq!(release.year, Some(2017));
+        assert_eq!(release.resolution, Some(1080));
+
+        let release = Release::parse("Blade.Runner.2049.2017");
+
+        assert_eq!(release.title, "Blade Runner 2049");
+        assert_eq!(release.year, Some(2017));
+    }
+
+    #[test]
+    fn test_episodes() {
+        let release = Release::parse("Show.S01E02.1080p.mkv");
+        assert_eq!(release.title, "Show");
+        assert_eq!(release.episodes, vec![2]);
+        assert_eq!(Release::parse("show s2.e10 x264").seasons, vec![2]);
+        assert_eq!(Release::parse("Show.S01E01E02.720p").episodes, vec![1, 2]);
+        assert_eq!(
+            Release::parse("Show.S01E01-03.720p").episodes,
+            vec![1, 2, 3]
+        );
+
+        let release = Release::parse("Show.S01E01-E03.mkv");
+        assert_eq!(release.episodes, vec![1, 2, 3]);
+        assert_eq!(release.group, None);
+
+        let release = Release::parse("Show.S01E05-E03");
+        assert_eq!(release.episodes, vec![5]);
+        assert_eq!(release.group, None);
+
+        let release = Release::parse("Show.S01E01-E03.720p-GRP");
+        assert_eq!(release.episodes, vec![1, 2, 3]);
+        assert_eq!(release.group.as_deref(), Some("GRP"));
+
+        let release = Release::parse("Show 3x07 HDTV");
+        assert_eq!((release.season(), release.episode()), (Some(3), Some(7)));
+        assert_eq!(release.title, "Show");
+
+        let release = Release::parse("Show.Name.S01-S03.COMPLETE.1080p.WEB-DL");
+        assert_eq!(release.seasons, vec![1, 2, 3]);
+        assert!(release.is_season_pack());
+        assert_eq!(release.flags, vec![ReleaseFlag::Complete]);
+        assert_eq!(release.group, None);
+
+        let release = Release::parse("[SubGroup] Show Name - Episode 05 [1080p]");
+        assert_eq!(release.title, "Show Name");
+        assert_eq!(release.episodes, vec![5]);
+        assert_eq!(release.group.as_deref(), Some("SubGroup"));
+
+        let release = Release::parse("Show - 01 [1080p]");
+        assert_eq!(release.title, "Show");
+        assert_eq!(release.episodes, vec![1]);
+        assert_eq!(release.resolution, Some(1080));
+
+        let release = Release::parse("[SubGroup] Show Name - 12 [720p][A1B2C3D4].mkv");
+        assert_eq!(release.title, "Show Name");
+        assert_eq!(release.episodes, vec![12]);
+        assert_eq!(release.resolution, Some(720));
+        assert_eq!(release.group.as_deref(), Some("SubGroup"));
+
+        assert!(!Release::parse("Movie.1920x1080.mkv").is_episode());
+        assert!(!Release::parse("Movie (2008).mkv").is_episode());
+    }
+
+    #[test]
+    fn test_descriptive_words_in_title() {
+        let release = Release::parse("Real.Steel.2011.1080p.BluRay.x264-GRP");
+        assert_eq!(release.title, "Real Steel");
+        assert_eq!(release.flags, vec![]);
+        assert_eq!(release.source, Some(ReleaseSource::BluRay));
+        assert_eq!(release.group.as_deref(), Some("GRP"));
+
+        let release = Release::parse("Web.Junkie.2013.720p.WEB-DL");
+        assert_eq!(release.title, "Web Junkie");
+        assert_eq!(release.source, Some(ReleaseSource::WebDl));
+
+        let release = Release::parse("Limited.Partnership.2014.DVDRip");
+        assert_eq!(release.title, "Limited Partnership");
+        assert_eq!(release.flags, vec![]);
+        assert_eq!(release.source, Some(ReleaseSource::Dvd));
+
+        let release = Release::parse("Cam.2018.1080p");
+        assert_eq!(release.title, "Cam");
+        assert_eq!(release.source, None);
+
+        let release = Release::parse("Multi.Level.2020.1080p");
+        assert_eq!(release.title, "Multi Level");
+        assert_eq!(release.flags, vec![]);
+
+        let release = Release::parse("The.Dv.Show.S01E01");
+        assert_eq!(release.title, "The Dv Show");
+        assert_eq!(release.hdr, vec![]);
+        assert_eq!(release.episodes, vec![1]);
+    }
+
+    #[test]
+    fn test_title_only() {
+        let release =
+            Release::parse("Rick Astley - Never Gonna Give You Up (Official Music Video).mp4");
+
+        assert_eq!(
+            release.title,
+            "Rick Astley - Never Gonna Give You Up (Official Music Video)"
+        );
+        assert_eq!(release.group, None);
+    }
+}
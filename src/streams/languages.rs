@@ -0,0 +1,91 @@
+/// ISO 639-1, ISO 639-2/B and ISO 639-2/T codes and English names of common languages.
+const LANGUAGES: &[(&str, &str, &str, &str)] = &[
+    ("ar", "ara", "ara", "arabic"),
+    ("bg", "bul", "bul", "bulgarian"),
+    ("ca", "cat", "cat", "catalan"),
+    ("cs", "cze", "ces", "czech"),
+    ("da", "dan", "dan", "danish"),
+    ("de", "ger", "deu", "german"),
+    ("el", "gre", "ell", "greek"),
+    ("en", "eng", "eng", "english"),
+    ("es", "spa", "spa", "spanish"),
+    ("et", "est", "est", "estonian"),
+    ("fa", "per", "fas", "persian"),
+    ("fi", "fin", "fin", "finnish"),
+    ("fr", "fre", "fra", "french"),
+    ("he", "heb", "heb", "hebrew"),
+    ("hi", "hin", "hin", "hindi"),
+    ("hr", "hrv", "hrv", "croatian"),
+    ("hu", "hun", "hun", "hungarian"),
+    ("id", "ind", "ind", "indonesian"),
+    ("is", "ice", "isl", "icelandic"),
+    ("it", "ita", "ita", "italian"),
+    ("ja", "jpn", "jpn", "japanese"),
+    ("ko", "kor", "kor", "korean"),
+    ("lt", "lit", "lit", "lithuanian"),
+    ("lv", "lav", "lav", "latvian"),
+    ("ms", "may", "msa", "malay"),
+    ("nl", "dut", "nld", "dutch"),
+    ("no", "nor", "nor", "norwegian"),
+    ("pl", "pol", "pol", "polish"),
+    ("pt", "por", "por", "portuguese"),
+    ("ro", "rum", "ron", "romanian"),
+    ("ru", "rus", "rus", "russian"),
+    ("sk", "slo", "slk", "slovak"),
+    ("sl", "slv", "slv", "slovenian"),
+    ("sr", "srp", "srp", "serbian"),
+    ("sv", "swe", "swe", "swedish"),
+    ("ta", "tam", "tam", "tamil"),
+    ("th", "tha", "tha", "thai"),
+    ("tr", "tur", "tur", "turkish"),
+    ("uk", "ukr", "ukr", "ukrainian"),
+    ("vi", "vie", "vie", "vietnamese"),
+    ("zh", "chi", "zho", "chinese"),
+];
+
+/// Normalizes a language to its ISO 639-2/B code, as used by RealDebrid (e.g. "fre").
+///
+/// ISO 639-1 and ISO 639-2/T codes and English names are accepted, case insensitively.
+/// Languages not known to this crate are returned lowercased.
+///
+/// # Examples
+/// ```
+/// use debrid::streams::language_code;
+///
+/// assert_eq!(language_code("fr"), "fre");
+/// assert_eq!(language_code("FRA"), "fre");
+/// assert_eq!(language_code("French"), "fre");
+/// assert_eq!(language_code("und"), "und");
+/// ```
+pub fn language_code(language: &str) -> String {
+    let language = language.trim().to_ascii_lowercase();
+
+    LANGUAGES
+        .iter()
+        .find(|(iso1, iso2b, iso2t, name)| {
+            [iso1, iso2b, iso2t, name]
+                .iter()
+                .any(|code| **code == language)
+        })
+        .map_or(language, |(_, iso2b, _, _)| iso2b.to_string())
+}
+
+/// Whether or not two languages are the same, whatever their code.
+pub fn same_language(a: &str, b: &str) -> bool {
+    language_code(a) == language_code(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_code() {
+        assert_eq!(language_code("de"), "ger");
+        assert_eq!(language_code("deu"), "ger");
+        assert_eq!(language_code(" Ger "), "ger");
+        assert_eq!(language_code("xx"), "xx");
+        assert!(same_language("zh", "zho"));
+        assert!(!same_language("en", "fre"));
+    }
+}
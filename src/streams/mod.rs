@@ -0,0 +1,9 @@
+mod languages;
+mod resolver;
+mod template;
+mod tracks;
+
+pub use languages::*;
+pub use resolver::*;
+pub use template::*;
+pub use tracks::*;
@@ -0,0 +1,199 @@
+use bon::bon;
+
+use crate::models::streaming::{MediaInfo, StreamFormat, StreamQuality, Transcode};
+
+/// Represents a stream link picked by a [`StreamPreference`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamUrl {
+    /// Format of the stream
+    pub format: StreamFormat,
+    /// Quality of the stream
+    pub quality: StreamQuality,
+    /// Link of the stream
+    pub url: String,
+}
+
+/// Preferences picking the best stream link of a transcoded file.
+#[derive(Debug, Clone)]
+pub struct StreamPreference {
+    formats: Vec<StreamFormat>,
+    max_height: Option<u32>,
+}
+
+impl Default for StreamPreference {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[bon]
+impl StreamPreference {
+    /// Creates new stream preferences.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{models::streaming::StreamFormat, streams::StreamPreference, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let transcode = client.streaming().transcode()
+    ///         .id("ABCDEFGHIJKLMNOP".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     // DASH up to 720p, falling back to HLS
+    ///     let preference = StreamPreference::builder()
+    ///         .formats(vec![StreamFormat::Dash, StreamFormat::Hls])
+    ///         .max_height(720)
+    ///         .build();
+    ///
+    ///     if let Ok(transcode) = transcode {
+    ///         if let Some(stream) = preference.select(&transcode) {
+    ///             println!("{} ({:?}, {})", stream.url, stream.format, stream.quality);
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// Formats in order of preference, the next ones being fallbacks, default: all, HLS first
+        formats: Option<Vec<StreamFormat>>,
+        /// Max video height, such as 720, default: none
+        max_height: Option<u32>,
+    ) -> Self {
+        Self {
+            formats: formats.unwrap_or_else(|| StreamFormat::ALL.to_vec()),
+            max_height,
+        }
+    }
+}
+
+impl StreamPreference {
+    /// Pick the best link of a transcoded file, or `None` if none matches the preferences.
+    ///
+    /// Formats are tried in order of preference. Within a format, the original quality
+    /// ranks first, then transcoded heights, and unknown qualities last. With a max height,
+    /// heights above it are skipped and the original quality, whose height is unknown here,
+    /// ranks with unknown qualities.
+    pub fn select(&self, transcode: &Transcode) -> Option<StreamUrl> {
+        self.formats.iter().find_map(|&format| {
+            let candidates = transcode
+                .urls(format)
+                .iter()
+                .map(|(key, url)| (StreamQuality::from(key.as_str()), url.clone()));
+
+            self.best(candidates, None).map(|(quality, url)| StreamUrl {
+                format,
+                quality,
+                url,
+            })
+        })
+    }
+
+    /// Pick the best link of a file from its media info, or `None` if none matches the preferences
+    /// or the file has no stream.
+    ///
+    /// Links are built from the base URL, its last segment being replaced by the quality and
+    /// format extension. Ranking follows [`select`](Self::select), the height of the original
+    /// quality being that of the video track.
+    pub fn select_media(&self, info: &MediaInfo) -> Option<StreamUrl> {
+        let base_url = info.base_url.as_deref()?;
+        let (base, _) = base_url.rsplit_once('/')?;
+        let formats = info.available_formats.as_ref()?;
+        let qualities = info.available_qualities.as_ref()?;
+        let original_height = info
+            .details
+            .video
+            .values()
+            .map(|track| track.height)
+            .max()
+            .and_then(|height| u32::try_from(height).ok());
+
+        self.formats.iter().find_map(|&format| {
+            let candidates = qualities.iter().map(|(label, key)| {
+                let quality = advertised_quality(label, key);
+                let url = format!("{}/{}.{}", base, key, formats.extension(format));
+
+                (quality, url)
+            });
+
+            self.best(candidates, original_height)
+                .map(|(quality, url)| StreamUrl {
+                    format,
+                    quality,
+                    url,
+                })
+        })
+    }
+
+    fn best(
+        &self,
+        candidates: impl Iterator<Item = (StreamQuality, String)>,
+        original_height: Option<u32>,
+    ) -> Option<(StreamQuality, String)> {
+        candidates
+            .filter_map(|(quality, url)| {
+                let height = match quality {
+                    StreamQuality::Original => original_height,
+                    _ => quality.height(),
+                };
+
+                match (self.max_height, height) {
+                    (Some(max), Some(height)) if height > max => None,
+                    _ => Some((rank(&quality, height, self.max_height), quality, url)),
+                }
+            })
+            // Sorting by url too keeps the pick stable across hashmap orders
+            .max_by(|(a, _, a_url), (b, _, b_url)| a.cmp(b).then_with(|| b_url.cmp(a_url)))
+            .map(|(_, quality, url)| (quality, url))
+    }
+}
+
+/// Quality of an entry of `MediaInfo.available_qualities`, parsed from its key,
+/// or from its label when the key is not known.
+pub(crate) fn advertised_quality(label: &str, key: &str) -> StreamQuality {
+    match StreamQuality::from(key) {
+        StreamQuality::Other(_) => match StreamQuality::from(label) {
+            StreamQuality::Other(_) => StreamQuality::Other(key.to_string()),
+            quality => quality,
+        },
+        quality => quality,
+    }
+}
+
+/// Rank of a quality, the greatest being the best.
+fn rank(quality: &StreamQuality, height: Option<u32>, max_height: Option<u32>) -> (u8, u32) {
+    match (quality, height) {
+        (StreamQuality::Original, _) if max_height.is_none() => (3, 0),
+        (_, Some(height)) => (2, height),
+        (StreamQuality::Original, None) => (1, 0),
+        (_, None) => (0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank() {
+        assert!(
+            rank(&StreamQuality::Original, None, None)
+                > rank(&StreamQuality::Height(2160), Some(2160), None)
+        );
+        assert!(
+            rank(&StreamQuality::Height(480), Some(480), Some(720))
+                > rank(&StreamQuality::Original, None, Some(720))
+        );
+        assert!(
+            rank(&StreamQuality::Original, None, Some(720))
+                > rank(&StreamQuality::Other("best".to_string()), None, Some(720))
+        );
+    }
+}
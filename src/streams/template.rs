@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use bon::bon;
+
+use super::{resolver::advertised_quality, StreamUrl, TrackSelection};
+use crate::{
+    models::streaming::{MediaInfo, StreamFormat, StreamQuality},
+    Error, Result,
+};
+
+/// Value of the subtitles placeholder when no subtitles are burnt in.
+pub const NO_SUBTITLES: &str = "none";
+
+/// Audio codec of the streams when the base URL does not tell it.
+pub const DEFAULT_AUDIO_CODEC: &str = "aac";
+
+/// Builds stream links of a file from the `model_url` template of its media info.
+///
+/// The `{audio}`, `{subtitles}`, `{audioCodec}`, `{quality}` and `{format}` placeholders
+/// are filled in, every choice being checked against the ones the media info advertises.
+pub struct StreamTemplate<'i> {
+    info: &'i MediaInfo,
+}
+
+impl<'i> StreamTemplate<'i> {
+    /// Creates a template from media info.
+    pub fn new(info: &'i MediaInfo) -> Self {
+        Self { info }
+    }
+}
+
+#[bon]
+impl StreamTemplate<'_> {
+    /// Build a stream link.
+    ///
+    /// Tracks are picked by their key in [`MediaDetails`](crate::models::streaming::MediaDetails)
+    /// (e.g. `eng1`) or by their stream ID (e.g. `0:1`). Choices left out default to the
+    /// ones of the base URL.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{
+    ///     models::streaming::{StreamFormat, StreamQuality},
+    ///     streams::StreamTemplate,
+    ///     Debrid, Error, Result,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let info = client.streaming().media_info()
+    ///         .id("ABCDEFGHIJKLMNOP".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     if let Ok(info) = info {
+    ///         let stream = StreamTemplate::new(&info).url()
+    ///             .format(StreamFormat::Hls)
+    ///             .quality(StreamQuality::Height(720))
+    ///             .audio("fre1")
+    ///             .build();
+    ///
+    ///         match stream {
+    ///             Ok(stream) => println!("{}", stream.url),
+    ///             Err(e) => println!("Not available: {}", e),
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder(finish_fn = build)]
+    pub fn url(
+        &self,
+        /// Format of the stream
+        format: StreamFormat,
+        /// Quality of the stream, default: the one of the base URL, or the original
+        quality: Option<StreamQuality>,
+        /// Tracks picked by a [`TrackSelector`](super::TrackSelector), overridden by
+        /// `audio` and `subtitles`
+        tracks: Option<&TrackSelection>,
+        /// Audio track key or stream ID, default: the selected one, or the one of the base URL
+        audio: Option<&str>,
+        /// Subtitle track key or stream ID, default: the selected one, or none
+        subtitles: Option<&str>,
+        /// Audio codec of the stream, default: the one of the base URL, or "aac"
+        audio_codec: Option<&str>,
+    ) -> Result<StreamUrl> {
+        let info = self.info;
+        let model_url = info
+            .model_url
+            .as_deref()
+            .ok_or_else(|| unavailable("the file has no stream template"))?;
+        let base = base_values(model_url, info.base_url.as_deref());
+
+        let extension = info
+            .available_formats
+            .as_ref()
+            .map(|formats| formats.extension(format))
+            .filter(|extension| !extension.is_empty())
+            .ok_or_else(|| unavailable(format!("format {:?}", format)))?;
+
+        let (quality, quality_key) = self.quality(quality, base.get("quality").copied())?;
+        let audio = audio.or_else(|| tracks.and_then(|tracks| tracks.audio.as_deref()));
+        let subtitles = subtitles.or_else(|| tracks.and_then(|tracks| tracks.subtitles.as_deref()));
+
+        let audio = match audio {
+            Some(audio) => track_key(&info.details.audio, audio, |track| &track.stream)
+                .ok_or_else(|| unavailable(format!("audio track {}", audio)))?,
+            None => base
+                .get("audio")
+                .copied()
+                .or_else(|| info.details.audio.keys().min().map(String::as_str))
+                .ok_or_else(|| unavailable("the file has no audio track"))?,
+        };
+
+        let subtitles = match subtitles {
+            Some(subtitles) => track_key(&info.details.subtitles, subtitles, |track| &track.stream)
+                .ok_or_else(|| unavailable(format!("subtitle track {}", subtitles)))?,
+            None => NO_SUBTITLES,
+        };
+
+        let audio_codec = audio_codec
+            .or_else(|| base.get("audioCodec").copied())
+            .unwrap_or(DEFAULT_AUDIO_CODEC);
+
+        let url = model_url
+            .replace("{audio}", audio)
+            .replace("{subtitles}", subtitles)
+            .replace("{audioCodec}", audio_codec)
+            .replace("{quality}", quality_key)
+            .replace("{format}", extension);
+
+        Ok(StreamUrl {
+            format,
+            quality,
+            url,
+        })
+    }
+}
+
+impl StreamTemplate<'_> {
+    /// Advertised quality and its key, the base one or the original one by default.
+    fn quality(
+        &self,
+        quality: Option<StreamQuality>,
+        base: Option<&str>,
+    ) -> Result<(StreamQuality, &str)> {
+        let advertised = self
+            .info
+            .available_qualities
+            .iter()
+            .flatten()
+            .map(|(label, key)| (advertised_quality(label, key), key.as_str()));
+
+        let wanted = quality
+            .clone()
+            .or_else(|| base.map(StreamQuality::from))
+            .unwrap_or(StreamQuality::Original);
+
+        advertised
+            .filter(|(advertised, _)| *advertised == wanted)
+            .min_by_key(|(_, key)| *key)
+            .ok_or_else(|| unavailable(format!("quality {}", wanted)))
+    }
+}
+
+/// Placeholder values of the base URL, which is the template filled in with default choices
+/// and without its extension.
+fn base_values<'u>(model_url: &'u str, base_url: Option<&'u str>) -> HashMap<&'u str, &'u str> {
+    let template = model_url.strip_suffix(".{format}").unwrap_or(model_url);
+    let Some(base_url) = base_url else {
+        return HashMap::new();
+    };
+
+    let segments = template.split('/').collect::<Vec<_>>();
+    let values = base_url.split('/').collect::<Vec<_>>();
+
+    if segments.len() != values.len() {
+        return HashMap::new();
+    }
+
+    segments
+        .into_iter()
+        .zip(values)
+        .filter_map(|(segment, value)| {
+            let name = segment.strip_prefix('{')?.strip_suffix('}')?;
+            Some((name, value))
+        })
+        .collect()
+}
+
+/// Key of a track, looked up by key or stream ID.
+fn track_key<'t, T>(
+    tracks: &'t HashMap<String, T>,
+    id: &str,
+    stream: impl Fn(&T) -> &String,
+) -> Option<&'t str> {
+    tracks
+        .get_key_value(id)
+        .map(|(key, _)| key.as_str())
+        .or_else(|| {
+            tracks
+                .iter()
+                .find(|(_, track)| stream(track) == id)
+                .map(|(key, _)| key.as_str())
+        })
+}
+
+fn unavailable(what: impl Into<String>) -> Error {
+    Error::UnavailableStream(what.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_values() {
+        let values = base_values(
+            "https://example.com/t/ID/{audio}/{subtitles}/{audioCodec}/{quality}.{format}",
+            Some("https://example.com/t/ID/eng1/none/aac/full"),
+        );
+
+        assert_eq!(values.get("audio"), Some(&"eng1"));
+        assert_eq!(values.get("subtitles"), Some(&"none"));
+        assert_eq!(values.get("audioCodec"), Some(&"aac"));
+        assert_eq!(values.get("quality"), Some(&"full"));
+
+        assert!(base_values("https://example.com/{quality}.{format}", None).is_empty());
+        assert!(base_values(
+            "https://example.com/{quality}.{format}",
+            Some("https://example.com/t/full")
+        )
+        .is_empty());
+    }
+}
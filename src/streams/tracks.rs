@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use bon::bon;
+
+use super::languages::same_language;
+use crate::models::streaming::{AudioTrack, MediaDetails, SubtitleTrack};
+
+/// Audio codecs, from the best to the worst.
+const AUDIO_CODECS: &[&str] = &[
+    "truehd", "dts", "eac3", "flac", "ac3", "aac", "opus", "vorbis", "mp3",
+];
+
+/// Subtitles picked by a [`TrackSelector`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SubtitleMode {
+    /// No subtitles
+    #[default]
+    Off,
+    /// Only forced subtitles, translating foreign parts
+    Forced,
+    /// Full subtitles, falling back to forced ones
+    Default,
+}
+
+/// Tracks picked by a [`TrackSelector`], by their key in [`MediaDetails`].
+///
+/// Feeds into [`StreamTemplate::url`](super::StreamTemplate::url).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackSelection {
+    /// Key of the audio track, if any
+    pub audio: Option<String>,
+    /// Key of the subtitle track, if any
+    pub subtitles: Option<String>,
+}
+
+/// Picks the audio and subtitle tracks of a file by language preference.
+#[derive(Debug, Clone, Default)]
+pub struct TrackSelector {
+    languages: Vec<String>,
+    subtitle_languages: Vec<String>,
+    subtitles: SubtitleMode,
+    subtitle_types: Vec<String>,
+}
+
+#[bon]
+impl TrackSelector {
+    /// Creates a new track selector.
+    ///
+    /// Languages are ISO 639-1 or ISO 639-2 codes, or English names, such as "fr", "fre",
+    /// "fra" or "French".
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{
+    ///     models::streaming::StreamFormat,
+    ///     streams::{StreamTemplate, SubtitleMode, TrackSelector},
+    ///     Debrid, Error, Result,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     let info = client.streaming().media_info()
+    ///         .id("ABCDEFGHIJKLMNOP".to_string())
+    ///         .send()
+    ///         .await;
+    ///
+    ///     // Japanese audio if any, with English subtitles, SRT first
+    ///     let selector = TrackSelector::builder()
+    ///         .languages(vec!["ja".to_string(), "en".to_string()])
+    ///         .subtitle_languages(vec!["en".to_string()])
+    ///         .subtitles(SubtitleMode::Default)
+    ///         .subtitle_types(vec!["SRT".to_string(), "ASS".to_string()])
+    ///         .build();
+    ///
+    ///     if let Ok(info) = info {
+    ///         let tracks = selector.select(&info.details);
+    ///         let stream = StreamTemplate::new(&info).url()
+    ///             .format(StreamFormat::Hls)
+    ///             .tracks(&tracks)
+    ///             .build()?;
+    ///
+    ///         println!("{}", stream.url);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// Audio languages in order of preference, default: none
+        languages: Option<Vec<String>>,
+        /// Subtitle languages in order of preference, default: the audio languages
+        subtitle_languages: Option<Vec<String>>,
+        /// Subtitles to pick, default: none
+        subtitles: Option<SubtitleMode>,
+        /// Subtitle types in order of preference (e.g. "SRT", "ASS"), default: any
+        subtitle_types: Option<Vec<String>>,
+    ) -> Self {
+        let languages = languages.unwrap_or_default();
+
+        Self {
+            subtitle_languages: subtitle_languages.unwrap_or_else(|| languages.clone()),
+            languages,
+            subtitles: subtitles.unwrap_or_default(),
+            subtitle_types: subtitle_types.unwrap_or_default(),
+        }
+    }
+}
+
+impl TrackSelector {
+    /// Pick the audio and subtitle tracks of a file.
+    pub fn select(&self, details: &MediaDetails) -> TrackSelection {
+        TrackSelection {
+            audio: self.audio(&details.audio).map(str::to_string),
+            subtitles: self.subtitles(&details.subtitles).map(str::to_string),
+        }
+    }
+
+    /// Key of the best audio track, or `None` if there is none.
+    ///
+    /// Tracks are ranked by language preference, tracks in other languages last,
+    /// then by channel count and codec.
+    pub fn audio<'t>(&self, tracks: &'t HashMap<String, AudioTrack>) -> Option<&'t str> {
+        tracks
+            .iter()
+            .min_by(|(a_key, a), (b_key, b)| {
+                preference(&self.languages, &a.lang_iso)
+                    .cmp(&preference(&self.languages, &b.lang_iso))
+                    .then_with(|| b.channels.total_cmp(&a.channels))
+                    .then_with(|| codec_rank(&a.codec).cmp(&codec_rank(&b.codec)))
+                    .then_with(|| a_key.cmp(b_key))
+            })
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Key of the best subtitle track, or `None` if there is none matching the preferences.
+    ///
+    /// Only tracks in a preferred language are picked, ranked by language preference
+    /// then type. Forced tracks are told by their language label mentioning it,
+    /// such as "English (Forced)".
+    pub fn subtitles<'t>(&self, tracks: &'t HashMap<String, SubtitleTrack>) -> Option<&'t str> {
+        let forced_only = match self.subtitles {
+            SubtitleMode::Off => return None,
+            SubtitleMode::Forced => true,
+            SubtitleMode::Default => false,
+        };
+
+        tracks
+            .iter()
+            .filter(|(_, track)| {
+                preference(&self.subtitle_languages, &track.lang_iso)
+                    < self.subtitle_languages.len()
+            })
+            .filter(|(_, track)| !forced_only || is_forced(track))
+            .min_by(|(a_key, a), (b_key, b)| {
+                preference(&self.subtitle_languages, &a.lang_iso)
+                    .cmp(&preference(&self.subtitle_languages, &b.lang_iso))
+                    .then_with(|| is_forced(a).cmp(&is_forced(b)))
+                    .then_with(|| self.type_rank(&a.type_).cmp(&self.type_rank(&b.type_)))
+                    .then_with(|| a_key.cmp(b_key))
+            })
+            .map(|(key, _)| key.as_str())
+    }
+
+    fn type_rank(&self, type_: &str) -> usize {
+        self.subtitle_types
+            .iter()
+            .position(|preferred| preferred.eq_ignore_ascii_case(type_))
+            .unwrap_or(self.subtitle_types.len())
+    }
+}
+
+/// Index of a language in the preferences, their count if not preferred.
+fn preference(languages: &[String], language: &str) -> usize {
+    languages
+        .iter()
+        .position(|preferred| same_language(preferred, language))
+        .unwrap_or(languages.len())
+}
+
+/// Index of a codec in [`AUDIO_CODECS`], unknown codecs last.
+fn codec_rank(codec: &str) -> usize {
+    let codec = codec.to_ascii_lowercase();
+
+    AUDIO_CODECS
+        .iter()
+        .position(|known| codec.starts_with(known))
+        .unwrap_or(AUDIO_CODECS.len())
+}
+
+fn is_forced(track: &SubtitleTrack) -> bool {
+    track.lang.to_ascii_lowercase().contains("forced")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codec_rank() {
+        assert!(codec_rank("TrueHD") < codec_rank("ac3"));
+        assert!(codec_rank("dts-hd ma") < codec_rank("aac"));
+        assert!(codec_rank("aac") < codec_rank("pcm_s16le"));
+    }
+
+    #[test]
+    fn test_preference() {
+        let languages = vec!["fr".to_string(), "English".to_string()];
+
+        assert_eq!(preference(&languages, "fre"), 0);
+        assert_eq!(preference(&languages, "eng"), 1);
+        assert_eq!(preference(&languages, "und"), 2);
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use chrono::{Days, NaiveDate};
+
+use super::{Quota, QuotaUnit};
+use crate::models::traffic::Detail;
+
+/// Format of the dates keying `traffic().details()`.
+pub(crate) const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Estimated exhaustion of a hoster quota, from its recent usage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    /// Quota of the host
+    pub quota: Quota,
+    /// Average bytes downloaded from the host per day over the history
+    pub daily_average: f64,
+    /// Estimated day the quota runs out, `None` if it is not consumed,
+    /// or not counted in bytes since the history only holds bytes
+    pub exhausted_on: Option<NaiveDate>,
+}
+
+/// Forecast when each quota runs out, averaging the usage of each host over the history.
+///
+/// The history spans from its first day to `today`, days without traffic counting as zero.
+/// The reset of a quota is not accounted for, so the estimate is only meaningful
+/// when it falls before the next reset.
+pub fn forecast(
+    quotas: &[Quota],
+    details: &HashMap<String, Detail>,
+    today: NaiveDate,
+) -> Vec<Forecast> {
+    let days = details
+        .keys()
+        .filter_map(|day| NaiveDate::parse_from_str(day, DATE_FORMAT).ok())
+        .min()
+        .map_or(1, |first| (today - first).num_days().max(0) + 1);
+
+    quotas
+        .iter()
+        .map(|quota| {
+            let total = details
+                .values()
+                .filter_map(|detail| detail.host.get(&quota.host))
+                .sum::<u64>();
+            let daily_average = total as f64 / days as f64;
+
+            let exhausted_on = match quota.unit {
+                QuotaUnit::Bytes if quota.is_exhausted() => Some(today),
+                QuotaUnit::Bytes if daily_average > 0.0 => {
+                    let days_left = (quota.remaining as f64 / daily_average).ceil() as u64;
+                    today.checked_add_days(Days::new(days_left))
+                }
+                _ => None,
+            };
+
+            Forecast {
+                quota: quota.clone(),
+                daily_average,
+                exhausted_on,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(host: &str, unit: QuotaUnit, remaining: u64) -> Quota {
+        Quota {
+            host: host.to_string(),
+            unit,
+            remaining,
+            used: None,
+            limit: None,
+            extra: None,
+            reset: None,
+        }
+    }
+
+    fn detail(hosts: &[(&str, u64)]) -> Detail {
+        Detail {
+            host: hosts.iter().map(|(h, b)| (h.to_string(), *b)).collect(),
+            bytes: hosts.iter().map(|(_, b)| b).sum(),
+        }
+    }
+
+    #[test]
+    fn test_forecast() {
+        let today = NaiveDate::from_ymd_opt(2024, 9, 27).unwrap();
+        let details = HashMap::from([
+            ("2024-09-24".to_string(), detail(&[("a.com", 300)])),
+            (
+                "2024-09-26".to_string(),
+                detail(&[("a.com", 100), ("b.com", 5)]),
+            ),
+        ]);
+        let quotas = [
+            quota("a.com", QuotaUnit::Bytes, 1000),
+            quota("b.com", QuotaUnit::Links, 2),
+            quota("c.com", QuotaUnit::Bytes, 1000),
+            quota("d.com", QuotaUnit::Bytes, 0),
+        ];
+
+        let forecasts = forecast(&quotas, &details, today);
+
+        // 400 bytes over 4 days, 1000 bytes left
+        assert_eq!(forecasts[0].daily_average, 100.0);
+        assert_eq!(
+            forecasts[0].exhausted_on,
+            NaiveDate::from_ymd_opt(2024, 10, 7)
+        );
+        assert_eq!(forecasts[1].exhausted_on, None);
+        assert_eq!(forecasts[2].exhausted_on, None);
+        assert_eq!(forecasts[3].exhausted_on, Some(today));
+    }
+}
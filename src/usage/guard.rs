@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use bon::bon;
+
+use super::Quota;
+use crate::{Debrid, Result};
+
+/// Default time a `traffic().get()` snapshot is trusted for by a [`QuotaGuard`].
+pub const DEFAULT_GUARD_TTL: Duration = Duration::from_secs(60);
+
+/// Refuses unrestricts which would exceed the remaining traffic of a hoster.
+///
+/// Used with [`UnrestrictApi::guarded`](crate::apis::unrestrict::UnrestrictApi::guarded).
+/// The traffic is fetched once per TTL and decreased locally after each unrestrict,
+/// so that the same guard can be shared by many unrestricts.
+pub struct QuotaGuard {
+    ttl: Duration,
+    margin: u64,
+    quotas: Mutex<Option<(Instant, HashMap<String, Quota>)>>,
+}
+
+#[bon]
+impl QuotaGuard {
+    /// Creates a new quota guard.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{usage::QuotaGuard, Debrid, Error, Result};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<()> {
+    ///     let client = Debrid::builder()
+    ///         .token("LOREMIPSUM".to_string())
+    ///         .build()?;
+    ///
+    ///     // Keep 1 GB unused on hosters limited in bytes
+    ///     let guard = QuotaGuard::builder()
+    ///         .margin(1024 * 1024 * 1024)
+    ///         .build();
+    ///
+    ///     let link = client.unrestrict().guarded()
+    ///         .link("https://example.com/abcdefghijkl".to_string())
+    ///         .guard(&guard)
+    ///         .send()
+    ///         .await;
+    ///
+    ///     match link {
+    ///         Ok(link) => println!("{}", link.download),
+    ///         Err(Error::QuotaWouldBeExceeded { host, left, .. }) => {
+    ///             println!("Only {} left on {}", left, host)
+    ///         }
+    ///         Err(e) => println!("Failed: {}", e),
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[builder]
+    pub fn new(
+        /// Time a traffic snapshot is trusted for, default: 60 seconds
+        ttl: Option<Duration>,
+        /// Bytes kept unused on hosters limited in bytes, default: 0
+        margin: Option<u64>,
+    ) -> Self {
+        Self {
+            ttl: ttl.unwrap_or(DEFAULT_GUARD_TTL),
+            margin: margin.unwrap_or(0),
+            quotas: Mutex::new(None),
+        }
+    }
+}
+
+impl QuotaGuard {
+    /// Bytes kept unused on hosters limited in bytes.
+    pub fn margin(&self) -> u64 {
+        self.margin
+    }
+
+    /// Forget the cached traffic, fetching it again on the next check.
+    pub fn invalidate(&self) {
+        *self.quotas.lock().unwrap() = None;
+    }
+
+    /// Quota of a host from the cached traffic, refreshed when older than the TTL.
+    pub(crate) async fn quota(&self, debrid: &Debrid, host: &str) -> Result<Option<Quota>> {
+        let fresh = self
+            .quotas
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|(at, _)| at.elapsed() < self.ttl);
+
+        if !fresh {
+            let quotas = debrid
+                .traffic()
+                .get()
+                .send()
+                .await?
+                .iter()
+                .filter_map(|(host, traffic)| {
+                    Quota::from_traffic(host, traffic).map(|quota| (host.clone(), quota))
+                })
+                .collect();
+
+            *self.quotas.lock().unwrap() = Some((Instant::now(), quotas));
+        }
+
+        let quotas = self.quotas.lock().unwrap();
+        Ok(quotas.as_ref().and_then(|(_, q)| q.get(host).cloned()))
+    }
+
+    /// Decrease the cached remaining traffic of a host.
+    pub(crate) fn consume(&self, host: &str, amount: u64) {
+        if let Some((_, quotas)) = self.quotas.lock().unwrap().as_mut() {
+            if let Some(quota) = quotas.get_mut(host) {
+                quota.remaining = quota.remaining.saturating_sub(amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+mod forecast;
+mod guard;
+mod quota;
+mod report;
+
+pub use forecast::*;
+pub use guard::*;
+pub use quota::*;
+pub use report::*;
@@ -0,0 +1,181 @@
+use crate::models::traffic::{Reset, Traffic};
+
+/// Bytes in a gigabyte, as counted by RealDebrid.
+const GIGABYTE: u64 = 1024 * 1024 * 1024;
+
+/// Unit of a [`Quota`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QuotaUnit {
+    /// Bytes, including quotas RealDebrid reports in gigabytes
+    Bytes,
+    /// Unrestricted links
+    Links,
+    /// Unit not known to this crate, holding the raw traffic type
+    Other(String),
+}
+
+/// Normalized view of a hoster [`Traffic`] limit, in bytes or links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quota {
+    /// Host main domain
+    pub host: String,
+    /// Unit of every amount of the quota
+    pub unit: QuotaUnit,
+    /// Amount left to use
+    pub remaining: u64,
+    /// Amount used, if known
+    pub used: Option<u64>,
+    /// Maximum amount, without extra, if applicable
+    pub limit: Option<u64>,
+    /// Additional amount the user may have bought, if applicable
+    pub extra: Option<u64>,
+    /// Reset frequency of the limit, if applicable
+    pub reset: Option<Reset>,
+}
+
+impl Quota {
+    /// Normalizes the traffic of a host, or `None` if the amount left is unknown.
+    ///
+    /// # Examples
+    /// ```
+    /// use debrid::{models::traffic::Traffic, usage::{Quota, QuotaUnit}};
+    ///
+    /// let traffic = Traffic::Gigabytes {
+    ///     left: 4 * 1024 * 1024 * 1024,
+    ///     bytes: Some(1024 * 1024 * 1024),
+    ///     limit: Some(5),
+    ///     extra: None,
+    ///     reset: None,
+    /// };
+    ///
+    /// let quota = Quota::from_traffic("example.com", &traffic).unwrap();
+    ///
+    /// assert_eq!(quota.unit, QuotaUnit::Bytes);
+    /// assert_eq!(quota.limit, Some(5 * 1024 * 1024 * 1024));
+    /// assert_eq!(quota.percent_used(), Some(20.0));
+    /// ```
+    pub fn from_traffic(host: impl Into<String>, traffic: &Traffic) -> Option<Self> {
+        let (unit, remaining, used, limit, extra, reset) = match traffic.clone() {
+            Traffic::Links {
+                left,
+                links,
+                limit,
+                extra,
+                reset,
+            } => (QuotaUnit::Links, left, Some(links), limit, extra, reset),
+            Traffic::Gigabytes {
+                left,
+                bytes,
+                limit,
+                extra,
+                reset,
+            } => (
+                QuotaUnit::Bytes,
+                left,
+                bytes,
+                limit.map(|limit| limit.saturating_mul(GIGABYTE)),
+                extra.map(|extra| extra.saturating_mul(GIGABYTE)),
+                reset,
+            ),
+            Traffic::Bytes {
+                left,
+                bytes,
+                limit,
+                extra,
+                reset,
+            } => (QuotaUnit::Bytes, left, bytes, limit, extra, reset),
+            Traffic::Unknown {
+                type_,
+                left,
+                limit,
+                extra,
+                reset,
+                ..
+            } => (QuotaUnit::Other(type_), left?, None, limit, extra, reset),
+        };
+
+        Some(Self {
+            host: host.into(),
+            unit,
+            remaining,
+            used,
+            limit,
+            extra,
+            reset,
+        })
+    }
+
+    /// Percentage of the quota consumed, from 0 to 100, if known.
+    ///
+    /// Computed from the amount used when known, from the limit and extra otherwise.
+    pub fn percent_used(&self) -> Option<f64> {
+        let (used, total) = match (self.used, self.limit) {
+            (Some(used), _) => (used, used.saturating_add(self.remaining)),
+            (None, Some(limit)) => {
+                let total = limit.saturating_add(self.extra.unwrap_or(0));
+                (total.saturating_sub(self.remaining), total)
+            }
+            (None, None) => return None,
+        };
+
+        match total {
+            0 => None,
+            total => Some(used as f64 * 100.0 / total as f64),
+        }
+    }
+
+    /// Whether or not nothing is left to use.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_quota() {
+        let traffic = Traffic::Links {
+            left: 3,
+            links: 2,
+            limit: Some(5),
+            extra: None,
+            reset: Some(Reset::Daily),
+        };
+        let quota = Quota::from_traffic("example.com", &traffic).unwrap();
+
+        assert_eq!(quota.unit, QuotaUnit::Links);
+        assert_eq!(quota.used, Some(2));
+        assert_eq!(quota.percent_used(), Some(40.0));
+        assert!(!quota.is_exhausted());
+    }
+
+    #[test]
+    fn test_unknown_quota() {
+        let traffic = Traffic::Unknown {
+            type_: "seconds".to_string(),
+            left: Some(3600),
+            bytes: None,
+            links: None,
+            limit: Some(7200),
+            extra: None,
+            reset: None,
+        };
+        let quota = Quota::from_traffic("example.com", &traffic).unwrap();
+
+        assert_eq!(quota.unit, QuotaUnit::Other("seconds".to_string()));
+        assert_eq!(quota.percent_used(), Some(50.0));
+
+        let traffic = Traffic::Unknown {
+            type_: "seconds".to_string(),
+            left: None,
+            bytes: None,
+            links: None,
+            limit: None,
+            extra: None,
+            reset: None,
+        };
+        assert_eq!(Quota::from_traffic("example.com", &traffic), None);
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, Days, NaiveDate};
+
+use super::DATE_FORMAT;
+use crate::{models::traffic::Detail, Error, Result};
+
+/// Max days covered by a single `traffic().details()` request.
+pub const MAX_DETAILS_DAYS: u64 = 31;
+
+/// Period traffic is aggregated over in a [`UsageReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    /// Calendar day, labelled `2024-09-27`
+    Day,
+    /// ISO week, labelled `2024-W39`
+    Week,
+    /// Calendar month, labelled `2024-09`
+    Month,
+}
+
+impl Period {
+    /// Label of the period a day falls in.
+    pub fn label(&self, day: NaiveDate) -> String {
+        match self {
+            Period::Day => day.format(DATE_FORMAT).to_string(),
+            Period::Week => {
+                let week = day.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            Period::Month => format!("{}-{:02}", day.year(), day.month()),
+        }
+    }
+}
+
+/// Bytes downloaded from a host over a period.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UsageRow {
+    /// Period label, see [`Period`]
+    pub period: String,
+    /// Host main domain
+    pub host: String,
+    /// Bytes downloaded
+    pub bytes: u64,
+}
+
+/// Traffic downloaded per day and host over a date range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageReport {
+    /// First day of the report
+    pub start: NaiveDate,
+    /// Last day of the report, included
+    pub end: NaiveDate,
+    /// Traffic details of each day with traffic
+    pub days: BTreeMap<NaiveDate, Detail>,
+}
+
+impl UsageReport {
+    /// Creates a report from `traffic().details()` results, keeping the days within the range.
+    /// Days found in several results are only counted once.
+    pub fn new(
+        start: NaiveDate,
+        end: NaiveDate,
+        details: impl IntoIterator<Item = HashMap<String, Detail>>,
+    ) -> Self {
+        let days = details
+            .into_iter()
+            .flatten()
+            .filter_map(|(day, detail)| {
+                let day = NaiveDate::parse_from_str(&day, DATE_FORMAT).ok()?;
+                (start <= day && day <= end).then_some((day, detail))
+            })
+            .collect();
+
+        Self { start, end, days }
+    }
+
+    /// Total bytes downloaded over the report.
+    pub fn total(&self) -> u64 {
+        self.days.values().map(|detail| detail.bytes).sum()
+    }
+
+    /// Total bytes downloaded from each host over the report.
+    pub fn by_host(&self) -> BTreeMap<String, u64> {
+        let mut hosts = BTreeMap::new();
+
+        for (host, bytes) in self.days.values().flat_map(|detail| &detail.host) {
+            *hosts.entry(host.clone()).or_default() += bytes;
+        }
+
+        hosts
+    }
+
+    /// Bytes downloaded from each host per period, sorted by period then host.
+    pub fn aggregate(&self, period: Period) -> Vec<UsageRow> {
+        let mut rows = BTreeMap::<(String, String), u64>::new();
+
+        for (day, detail) in &self.days {
+            let label = period.label(*day);
+
+            for (host, bytes) in &detail.host {
+                *rows.entry((label.clone(), host.clone())).or_default() += bytes;
+            }
+        }
+
+        rows.into_iter()
+            .map(|((period, host), bytes)| UsageRow {
+                period,
+                host,
+                bytes,
+            })
+            .collect()
+    }
+
+    /// Export the traffic per period as CSV, with a `period,host,bytes` header.
+    pub fn to_csv(&self, period: Period) -> String {
+        let mut csv = String::from("period,host,bytes\n");
+
+        for row in self.aggregate(period) {
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                escape_csv(&row.period),
+                escape_csv(&row.host),
+                row.bytes
+            ));
+        }
+
+        csv
+    }
+
+    /// Export the traffic per period as a JSON array of [`UsageRow`]s.
+    pub fn to_json(&self, period: Period) -> Result<String> {
+        serde_json::to_string_pretty(&self.aggregate(period)).map_err(Error::Json)
+    }
+}
+
+/// Splits a date range into consecutive ranges of at most `max_days` days, bounds included.
+pub(crate) fn windows(
+    start: NaiveDate,
+    end: NaiveDate,
+    max_days: u64,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut windows = Vec::new();
+    let mut from = start;
+
+    while from <= end {
+        let to = from
+            .checked_add_days(Days::new(max_days.max(1) - 1))
+            .map_or(end, |to| to.min(end));
+        windows.push((from, to));
+
+        match to.succ_opt() {
+            Some(next) => from = next,
+            None => break,
+        }
+    }
+
+    windows
+}
+
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(day, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_windows() {
+        assert_eq!(
+            windows(day("2024-01-01"), day("2024-03-01"), 31),
+            vec![
+                (day("2024-01-01"), day("2024-01-31")),
+                (day("2024-02-01"), day("2024-03-01")),
+            ]
+        );
+        assert_eq!(
+            windows(day("2024-01-01"), day("2024-01-01"), 31),
+            vec![(day("2024-01-01"), day("2024-01-01"))]
+        );
+        assert!(windows(day("2024-01-02"), day("2024-01-01"), 31).is_empty());
+    }
+
+    #[test]
+    fn test_period_labels() {
+        assert_eq!(Period::Day.label(day("2024-09-27")), "2024-09-27");
+        assert_eq!(Period::Week.label(day("2024-09-27")), "2024-W39");
+        assert_eq!(Period::Week.label(day("2024-12-30")), "2025-W01");
+        assert_eq!(Period::Month.label(day("2024-09-27")), "2024-09");
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(escape_csv("example.com"), "example.com");
+        assert_eq!(escape_csv("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -0,0 +1,88 @@
+use crate::mocked::*;
+
+use debrid::{containers::CcfKey, Error};
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+fn key() -> CcfKey {
+    CcfKey {
+        key: b"debrid-test-key!".to_vec(),
+        iv: *b"debrid-test-iv!!",
+    }
+}
+
+fn expected() -> Vec<String> {
+    vec![
+        "https://example.com/abcdefghijkl".to_string(),
+        "https://example.com/mnopqrstuvwx".to_string(),
+    ]
+}
+
+#[tokio::test]
+async fn should_decode_ccf_file_locally() {
+    mocked(|mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.ccf").unwrap();
+
+        let links = debrid
+            .containers()
+            .file()
+            .data(data)
+            .name("links.ccf")
+            .ccf_keys(vec![key()])
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, expected());
+        assert!(mock.received_requests().await.unwrap().is_empty());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_decode_ccf_link_locally() {
+    mocked(|mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.ccf").unwrap();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/links.ccf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(data))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let links = debrid
+            .containers()
+            .link()
+            .link(format!("{}/files/links.ccf", mock.uri()))
+            .ccf_keys(vec![key()])
+            .local_only(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, expected());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_to_decode_ccf_with_wrong_key() {
+    mocked(|_mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.ccf").unwrap();
+        let mut key = key();
+        key.key = b"wrong-test-key!!".to_vec();
+
+        let res = debrid
+            .containers()
+            .file()
+            .data(data)
+            .name("links.ccf")
+            .ccf_keys(vec![key])
+            .local_only(true)
+            .send()
+            .await;
+
+        assert!(matches!(res, Err(Error::InvalidContainer(_))));
+    })
+    .await
+}
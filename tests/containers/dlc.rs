@@ -0,0 +1,91 @@
+use crate::mocked::*;
+
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+const KEY: &str =
+    "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4vMDEyMzQ1Njc4OTo7PD0+P0BB";
+
+#[tokio::test]
+async fn should_decode_dlc_with_key_exchange() {
+    mocked(|mock, debrid| async move {
+        let response =
+            std::fs::read_to_string("tests/.resources/containers/dlc_service.xml").unwrap();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/dlcrypt/service.php"))
+            .and(matchers::query_param("srcType", "dlc"))
+            .and(matchers::query_param("data", KEY))
+            .respond_with(ResponseTemplate::new(200).set_body_string(response))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let data = std::fs::read("tests/.resources/containers/links.dlc").unwrap();
+
+        let links = debrid
+            .containers()
+            .file()
+            .data(data)
+            .name("links.dlc")
+            .dlc_service(format!("{}/dlcrypt/service.php", mock.uri()))
+            .local_only(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/abcdefghijkl".to_string(),
+                "https://example.com/mnopqrstuvwx".to_string(),
+            ]
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_send_token_outside_realdebrid() {
+    mocked(|mock, debrid| async move {
+        let response =
+            std::fs::read_to_string("tests/.resources/containers/dlc_service.xml").unwrap();
+        let data = std::fs::read("tests/.resources/containers/links.dlc").unwrap();
+
+        Mock::given(matchers::header_exists("Authorization"))
+            .respond_with(ResponseTemplate::new(401))
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/links.dlc"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(data))
+            .expect(1)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/dlcrypt/service.php"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(response))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let links = debrid
+            .containers()
+            .link()
+            .link(format!("{}/files/links.dlc", mock.uri()))
+            .dlc_service(format!("{}/dlcrypt/service.php", mock.uri()))
+            .local_only(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links.len(), 2);
+        assert!(mock
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .all(|request| !request.headers.contains_key("Authorization")));
+    })
+    .await
+}
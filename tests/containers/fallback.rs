@@ -0,0 +1,91 @@
+use crate::mocked::*;
+
+use debrid::Error;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_send_ccf_to_realdebrid() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<Vec<String>>("tests/.resources/unrestrict/container_file.json");
+
+        Mock::given(matchers::method("PUT"))
+            .and(matchers::path("/unrestrict/containerFile"))
+            .and(matchers::body_bytes(vec![0x00, 0xFF, 0x10]))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let links = debrid
+            .containers()
+            .file()
+            .data(vec![0x00, 0xFF, 0x10])
+            .name("links.ccf")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, r);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_locally_without_fallback() {
+    mocked(|_mock, debrid| async move {
+        let res = debrid
+            .containers()
+            .file()
+            .data(vec![0x00, 0xFF, 0x10])
+            .local_only(true)
+            .send()
+            .await;
+
+        assert!(matches!(res, Err(Error::InvalidContainer(_))));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_send_link_to_realdebrid_when_dlc_service_fails() {
+    mocked(|mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.dlc").unwrap();
+        let r = resource::<Vec<String>>("tests/.resources/unrestrict/container_link.json");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/links.dlc"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(data))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/dlcrypt/service.php"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let link = format!("{}/files/links.dlc", mock.uri());
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/containerLink"))
+            .and(matchers::body_string_contains("links.dlc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let links = debrid
+            .containers()
+            .link()
+            .link(link)
+            .dlc_service(format!("{}/dlcrypt/service.php", mock.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, r);
+    })
+    .await
+}
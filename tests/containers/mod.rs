@@ -0,0 +1,4 @@
+mod ccf;
+mod dlc;
+mod fallback;
+mod rsdf;
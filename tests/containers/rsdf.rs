@@ -0,0 +1,63 @@
+use crate::mocked::*;
+
+use debrid::containers::decode_rsdf;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+fn expected() -> Vec<String> {
+    vec![
+        "https://example.com/abcdefghijkl".to_string(),
+        "https://example.com/mnopqrstuvwx".to_string(),
+    ]
+}
+
+#[test]
+fn should_decode_rsdf() {
+    let data = std::fs::read("tests/.resources/containers/links.rsdf").unwrap();
+
+    assert_eq!(decode_rsdf(&data).unwrap(), expected());
+}
+
+#[tokio::test]
+async fn should_decode_rsdf_file_locally() {
+    mocked(|_mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.rsdf").unwrap();
+
+        // No name, detected from the contents
+        let links = debrid
+            .containers()
+            .file()
+            .data(data)
+            .local_only(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, expected());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_decode_rsdf_link_locally() {
+    mocked(|mock, debrid| async move {
+        let data = std::fs::read("tests/.resources/containers/links.rsdf").unwrap();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/links.rsdf"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(data))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let links = debrid
+            .containers()
+            .link()
+            .link(format!("{}/files/links.rsdf?x=1", mock.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(links, expected());
+    })
+    .await
+}
@@ -0,0 +1,318 @@
+use std::{
+    io::{Cursor, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::mocked::*;
+
+use debrid::{
+    download::{match_torrent_files, ArchiveFormat, DownloadManager, Event},
+    models, Error,
+};
+use wiremock::{matchers, Mock};
+
+fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+    for (name, data) in entries {
+        writer
+            .start_file(*name, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+
+    writer.finish().unwrap().into_inner()
+}
+
+fn tar_gz(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    for (name, data) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, name, *data).unwrap();
+    }
+
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn sorted(mut files: Vec<PathBuf>) -> Vec<PathBuf> {
+    files.sort();
+    files
+}
+
+#[tokio::test]
+async fn should_detect_archive_formats() {
+    let directory = scratch("extract-detect");
+
+    let detect = |name: &str, data: Vec<u8>, mime_type: Option<&'static str>| {
+        let path = directory.join(name);
+        std::fs::write(&path, data).unwrap();
+        async move { ArchiveFormat::detect(&path, mime_type).await.unwrap() }
+    };
+
+    assert_eq!(
+        detect("a.zip", zip(&[("a.txt", b"a")]), None).await,
+        Some(ArchiveFormat::Zip)
+    );
+    assert_eq!(
+        detect("a.tgz", tar_gz(&[("a.txt", b"a")]), None).await,
+        Some(ArchiveFormat::TarGz)
+    );
+    assert_eq!(detect("a.gz", gzip(b"not a tarball"), None).await, None);
+    assert_eq!(detect("a.bin", b"\x1aE\xdf\xa3".to_vec(), None).await, None);
+    assert_eq!(
+        detect("b.bin", b"???".to_vec(), Some("application/zip")).await,
+        Some(ArchiveFormat::Zip)
+    );
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[tokio::test]
+async fn should_extract_zip_and_remove_archive() {
+    mocked(|_mock, debrid| async move {
+        let directory = scratch("extract-zip");
+        let archive = directory.join("Release.zip");
+        std::fs::write(
+            &archive,
+            zip(&[
+                ("Release/video.mkv", b"video"),
+                ("Release/video.srt", b"subs"),
+            ]),
+        )
+        .unwrap();
+
+        let files = debrid
+            .downloader()
+            .extract()
+            .archive(&archive)
+            .remove_archive(true)
+            .send()
+            .await
+            .unwrap()
+            .unwrap();
+
+        let destination = directory.join("Release");
+        assert_eq!(
+            sorted(files),
+            vec![
+                destination.join("Release/video.mkv"),
+                destination.join("Release/video.srt"),
+            ]
+        );
+        assert_eq!(
+            std::fs::read(destination.join("Release/video.mkv")).unwrap(),
+            b"video"
+        );
+        assert!(!archive.exists());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_extract_tar_gz() {
+    mocked(|_mock, debrid| async move {
+        let directory = scratch("extract-tar");
+        let archive = directory.join("Release.tar.gz");
+        std::fs::write(&archive, tar_gz(&[("sub/a.txt", b"a"), ("b.txt", b"bb")])).unwrap();
+
+        let destination = directory.join("out");
+        let files = debrid
+            .downloader()
+            .extract()
+            .archive(&archive)
+            .destination(&destination)
+            .send()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            sorted(files),
+            vec![destination.join("b.txt"), destination.join("sub/a.txt")]
+        );
+        assert!(archive.exists());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_extract_7z() {
+    mocked(|_mock, debrid| async move {
+        let directory = scratch("extract-7z");
+        let content = directory.join("content");
+        std::fs::create_dir_all(content.join("sub")).unwrap();
+        std::fs::write(content.join("sub/a.txt"), b"a").unwrap();
+
+        let archive = directory.join("Release.7z");
+        sevenz_rust::compress_to_path(&content, &archive).unwrap();
+
+        let files = debrid
+            .downloader()
+            .extract()
+            .archive(&archive)
+            .send()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(files, vec![directory.join("Release/sub/a.txt")]);
+        assert_eq!(std::fs::read(&files[0]).unwrap(), b"a");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_not_extract_other_files() {
+    mocked(|_mock, debrid| async move {
+        let directory = scratch("extract-none");
+        let file = directory.join("video.mkv");
+        std::fs::write(&file, b"\x1aE\xdf\xa3").unwrap();
+
+        let files = debrid
+            .downloader()
+            .extract()
+            .archive(&file)
+            .remove_archive(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(files.is_none());
+        assert!(file.exists());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_refuse_path_traversal() {
+    mocked(|_mock, debrid| async move {
+        let directory = scratch("extract-traversal");
+        let archive = directory.join("evil.zip");
+        std::fs::write(&archive, zip(&[("../evil.sh", b"rm -rf")])).unwrap();
+
+        let result = debrid.downloader().extract().archive(&archive).send().await;
+
+        assert!(matches!(result, Err(Error::UnsafePath(_))));
+        assert!(!directory.join("evil.sh").exists());
+    })
+    .await;
+}
+
+#[test]
+fn should_match_torrent_files() {
+    let directory = scratch("extract-match");
+    let write = |path: &Path, len: usize| {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; len]).unwrap();
+        path.to_path_buf()
+    };
+
+    let mut torrent =
+        resource::<models::torrents::TorrentInfo>("tests/.resources/torrents/info.json");
+    torrent.files = vec![
+        models::torrents::TorrentFile {
+            id: 1,
+            path: "/Release/video.mkv".to_string(),
+            bytes: 10,
+            selected: true,
+        },
+        models::torrents::TorrentFile {
+            id: 2,
+            path: "/Release/Extras/video.srt".to_string(),
+            bytes: 4,
+            selected: true,
+        },
+        models::torrents::TorrentFile {
+            id: 3,
+            path: "/Release/sample.mkv".to_string(),
+            bytes: 2,
+            selected: false,
+        },
+        models::torrents::TorrentFile {
+            id: 4,
+            path: "/Release/missing.nfo".to_string(),
+            bytes: 1,
+            selected: true,
+        },
+    ];
+
+    let files = vec![
+        write(&directory.join("flat/video.srt"), 4),
+        write(&directory.join("Release/video.mkv"), 10),
+    ];
+
+    let matches = match_torrent_files(&torrent, &files)
+        .into_iter()
+        .map(|(file, path)| (file.id, path))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        matches,
+        vec![
+            (1, Some(files[1].clone())),
+            (2, Some(files[0].clone())),
+            (4, None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn should_extract_completed_jobs() {
+    mocked(|mock, debrid| async move {
+        let body = zip(&[("inner.txt", b"hello")]);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/Release.zip"))
+            .respond_with(RangeResponder(body.clone()))
+            .mount(&mock)
+            .await;
+
+        let directory = scratch("extract-manager");
+        let manager = DownloadManager::builder()
+            .debrid(debrid)
+            .directory(&directory)
+            .watch_traffic(false)
+            .build();
+        manager.extract_archives(true);
+
+        let mut events = manager.subscribe();
+        let source = debrid::download::Source {
+            url: format!("{}/d/Release.zip", mock.uri()),
+            filename: "Release.zip".to_string(),
+            filesize: body.len() as u64,
+            chunks: 1,
+            mime_type: Some("application/zip".to_string()),
+        };
+        let id = manager.add(debrid::download::Item::Source {
+            source,
+            host: "example.com".to_string(),
+        });
+
+        let files = loop {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("timed out waiting for events")
+                .unwrap();
+
+            match event {
+                Event::Extracted { id: job, files } if job == id => break files,
+                Event::Failed { error, .. } => panic!("job failed: {}", error),
+                _ => {}
+            }
+        };
+
+        assert_eq!(files, vec![directory.join("Release/inner.txt")]);
+        assert!(!directory.join("Release.zip").exists());
+    })
+    .await;
+}
@@ -0,0 +1,228 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use crate::mocked::*;
+
+use debrid::Error;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+const VIDEO: &str = "ABCDEFGHIJKLMNO/video.mp4";
+
+#[tokio::test]
+async fn should_download_link_in_chunks() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body.clone()))
+            .expect(5)
+            .mount(&mock)
+            .await;
+
+        let path = scratch("download-chunks").join("video.mp4");
+        let downloaded = Arc::new(AtomicU64::new(0));
+        let progress = downloaded.clone();
+
+        let res = debrid
+            .downloader()
+            .file()
+            .source(served_link(&mock.uri(), VIDEO, 1000, 4))
+            .path(&path)
+            .progress(Arc::new(move |p| {
+                progress.fetch_max(p.downloaded, Ordering::SeqCst);
+            }))
+            .send()
+            .await;
+
+        assert_eq!(res.unwrap(), 1000);
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert_eq!(downloaded.load(Ordering::SeqCst), 1000);
+        assert!(!path.with_extension("mp4.part").exists());
+        assert!(!path.with_extension("mp4.part.json").exists());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_download_without_range_support() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+            .expect(2)
+            .mount(&mock)
+            .await;
+
+        let path = scratch("download-single").join("video.mp4");
+
+        let res = debrid
+            .downloader()
+            .file()
+            .source(served_link(&mock.uri(), VIDEO, 0, 4))
+            .path(&path)
+            .send()
+            .await;
+
+        assert_eq!(res.unwrap(), 1000);
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_to_download_with_size_mismatch() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body(1000)))
+            .mount(&mock)
+            .await;
+
+        let path = scratch("download-mismatch").join("video.mp4");
+
+        let res = debrid
+            .downloader()
+            .file()
+            .source(served_link(&mock.uri(), VIDEO, 2000, 2))
+            .path(&path)
+            .send()
+            .await;
+
+        assert!(res.is_err_and(|e| matches!(
+            e,
+            Error::SizeMismatch {
+                expected: 2000,
+                actual: 1000
+            }
+        )));
+        assert!(!path.exists());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_resume_partial_download() {
+    let body = body(1000);
+    let path = scratch("download-resume").join("video.mp4");
+
+    mocked(|mock, debrid| {
+        let body = body.clone();
+        let path = path.clone();
+
+        async move {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::header("Range", "bytes=500-999"))
+                .respond_with(ResponseTemplate::new(503))
+                .with_priority(1)
+                .mount(&mock)
+                .await;
+
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+                .respond_with(RangeResponder(body))
+                .mount(&mock)
+                .await;
+
+            let res = debrid
+                .downloader()
+                .file()
+                .source(served_link(&mock.uri(), VIDEO, 1000, 2))
+                .path(&path)
+                .send()
+                .await;
+
+            assert!(res.is_err_and(|e| matches!(e, Error::Reqwest(_))));
+            assert!(path.with_extension("mp4.part.json").exists());
+        }
+    })
+    .await;
+
+    mocked(|mock, debrid| {
+        let body = body.clone();
+        let path = path.clone();
+
+        async move {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::header("Range", "bytes=500-999"))
+                .respond_with(RangeResponder(body.clone()))
+                .with_priority(1)
+                .expect(1)
+                .mount(&mock)
+                .await;
+
+            Mock::given(matchers::method("GET"))
+                .and(matchers::header("Range", "bytes=0-0"))
+                .respond_with(RangeResponder(body.clone()))
+                .expect(1)
+                .mount(&mock)
+                .await;
+
+            let res = debrid
+                .downloader()
+                .file()
+                .source(served_link(&mock.uri(), VIDEO, 1000, 2))
+                .path(&path)
+                .send()
+                .await;
+
+            assert_eq!(res.unwrap(), 1000);
+            assert_eq!(std::fs::read(&path).unwrap(), body);
+            assert!(!path.with_extension("mp4.part.json").exists());
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_not_send_token_to_download_hosts() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::header_exists("Authorization"))
+            .respond_with(ResponseTemplate::new(401))
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body.clone()))
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/single.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body.clone()))
+            .mount(&mock)
+            .await;
+
+        let directory = scratch("download-token");
+
+        let ranged = debrid
+            .downloader()
+            .file()
+            .source(format!("{}/d/{}", mock.uri(), VIDEO))
+            .path(directory.join("video.mp4"))
+            .send()
+            .await;
+        let single = debrid
+            .downloader()
+            .file()
+            .source(served_link(&mock.uri(), "ABCDEFGHIJKLMNO/single.mp4", 0, 4))
+            .path(directory.join("single.mp4"))
+            .send()
+            .await;
+
+        assert_eq!(ranged.unwrap(), 1000);
+        assert_eq!(single.unwrap(), 1000);
+        assert!(mock
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .all(|request| !request.headers.contains_key("Authorization")));
+    })
+    .await
+}
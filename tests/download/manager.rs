@@ -0,0 +1,290 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::mocked::*;
+
+use debrid::{
+    download::{DownloadManager, Event, JobId, JobStatus, PauseReason},
+    models,
+};
+use tokio::sync::broadcast::Receiver;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+/// Collects events until `done` returns true for one of them.
+async fn until(events: &mut Receiver<Event>, done: impl Fn(&Event) -> bool) -> Vec<Event> {
+    let mut seen = Vec::new();
+
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("timed out waiting for events")
+            .unwrap();
+        let stop = done(&event);
+        seen.push(event);
+
+        if stop {
+            return seen;
+        }
+    }
+}
+
+/// Waits until `condition` holds.
+async fn eventually(condition: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for condition")
+}
+
+#[tokio::test]
+async fn should_download_queued_jobs() {
+    mocked(|mock, debrid| async move {
+        let traffic = resource::<HashMap<String, models::traffic::Traffic>>(
+            "tests/.resources/traffic/index.json",
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(traffic))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        for name in ["a.bin", "b.bin"] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path(format!("/d/{}", name)))
+                .respond_with(RangeResponder(body(1000)))
+                .mount(&mock)
+                .await;
+        }
+
+        let directory = scratch("manager-queue");
+        let manager = DownloadManager::builder()
+            .debrid(debrid)
+            .directory(&directory)
+            .concurrency(1)
+            .bytes_per_second(1_000_000)
+            .build();
+
+        let mut events = manager.subscribe();
+        let a = manager.add(served_link(&mock.uri(), "a.bin", 1000, 2));
+        let b = manager.add(served_link(&mock.uri(), "b.bin", 1000, 2));
+
+        let seen = until(
+            &mut events,
+            |e| matches!(e, Event::Completed { id, .. } if *id == b),
+        )
+        .await;
+
+        assert_eq!(seen[0], Event::Queued { id: a });
+        assert_eq!(seen[1], Event::Queued { id: b });
+        assert!(seen.contains(&Event::Completed {
+            id: a,
+            path: directory.join("a.bin"),
+            bytes: 1000
+        }));
+        assert!(seen.contains(&Event::Progress {
+            id: b,
+            downloaded: 1000,
+            total: Some(1000)
+        }));
+
+        let started = seen
+            .iter()
+            .filter_map(|e| match e {
+                Event::Started { id, .. } => Some(*id),
+                _ => None,
+            })
+            .collect::<Vec<JobId>>();
+        assert_eq!(started, vec![a, b]);
+
+        assert_eq!(manager.status(a), Some(JobStatus::Completed));
+        assert_eq!(std::fs::read(directory.join("b.bin")).unwrap(), body(1000));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_pause_job_exceeding_traffic() {
+    mocked(|mock, debrid| async move {
+        let mut traffic = HashMap::new();
+        traffic.insert(
+            "example.com".to_string(),
+            models::traffic::Traffic::Bytes {
+                left: 1500,
+                bytes: None,
+                limit: None,
+                extra: None,
+                reset: None,
+            },
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(traffic))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/big.bin"))
+            .respond_with(RangeResponder(body(2000)))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let manager = DownloadManager::builder()
+            .debrid(debrid)
+            .directory(scratch("manager-quota"))
+            .build();
+
+        let mut events = manager.subscribe();
+        let id = manager.add(served_link(&mock.uri(), "big.bin", 2000, 2));
+
+        let seen = until(&mut events, |e| matches!(e, Event::Paused { .. })).await;
+        let reason = PauseReason::QuotaExceeded {
+            host: "example.com".to_string(),
+            needed: 2000,
+            left: 1500,
+        };
+
+        assert_eq!(
+            seen.last(),
+            Some(&Event::Paused {
+                id,
+                reason: reason.clone()
+            })
+        );
+        assert_eq!(manager.status(id), Some(JobStatus::Paused(reason)));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_refresh_traffic_after_a_job_completes() {
+    mocked(|mock, debrid| async move {
+        let traffic = |left| {
+            let mut traffic = HashMap::new();
+            traffic.insert(
+                "example.com".to_string(),
+                models::traffic::Traffic::Bytes {
+                    left,
+                    bytes: None,
+                    limit: None,
+                    extra: None,
+                    reset: None,
+                },
+            );
+            ResponseTemplate::new(200).set_body_json(traffic)
+        };
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic"))
+            .respond_with(traffic(1500))
+            .up_to_n_times(1)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic"))
+            .respond_with(traffic(500))
+            .mount(&mock)
+            .await;
+
+        for name in ["a.bin", "b.bin"] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path(format!("/d/{}", name)))
+                .respond_with(RangeResponder(body(1000)))
+                .mount(&mock)
+                .await;
+        }
+
+        let manager = DownloadManager::builder()
+            .debrid(debrid)
+            .directory(scratch("manager-traffic"))
+            .concurrency(1)
+            .build();
+
+        let mut events = manager.subscribe();
+        let a = manager.add(served_link(&mock.uri(), "a.bin", 1000, 2));
+        let b = manager.add(served_link(&mock.uri(), "b.bin", 1000, 2));
+
+        until(&mut events, |e| matches!(e, Event::Paused { .. })).await;
+
+        assert_eq!(manager.status(a), Some(JobStatus::Completed));
+        assert_eq!(
+            manager.status(b),
+            Some(JobStatus::Paused(PauseReason::QuotaExceeded {
+                host: "example.com".to_string(),
+                needed: 1000,
+                left: 500,
+            }))
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_pause_resume_and_cancel_jobs() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/slow.bin"))
+            .respond_with(RangeResponder(body(1000)))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/next.bin"))
+            .respond_with(RangeResponder(body(1000)))
+            .mount(&mock)
+            .await;
+
+        let directory = scratch("manager-control");
+        let manager = DownloadManager::builder()
+            .debrid(debrid)
+            .directory(&directory)
+            .concurrency(1)
+            .watch_traffic(false)
+            .bytes_per_second(1)
+            .build();
+
+        let mut events = manager.subscribe();
+        let slow = manager.add(served_link(&mock.uri(), "slow.bin", 1000, 2));
+        let next = manager.add(served_link(&mock.uri(), "next.bin", 1000, 2));
+
+        until(
+            &mut events,
+            |e| matches!(e, Event::Started { id, .. } if *id == slow),
+        )
+        .await;
+
+        assert!(manager.pause(next));
+        assert_eq!(
+            manager.status(next),
+            Some(JobStatus::Paused(PauseReason::Requested))
+        );
+
+        let part = directory.join("slow.bin.part");
+        eventually(|| part.exists()).await;
+
+        assert!(manager.cancel(slow));
+        assert!(!manager.cancel(slow));
+        assert_eq!(manager.status(slow), Some(JobStatus::Cancelled));
+
+        // Removed by the job task once it stopped
+        eventually(|| !part.exists() && !part.with_extension("part.json").exists()).await;
+
+        assert!(manager.resume(next));
+        until(
+            &mut events,
+            |e| matches!(e, Event::Started { id, .. } if *id == next),
+        )
+        .await;
+
+        assert!(manager.pause(next));
+        assert!(manager.resume(next));
+        assert!(!manager.resume(next));
+        assert_eq!(manager.status(next), Some(JobStatus::Queued));
+    })
+    .await
+}
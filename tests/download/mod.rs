@@ -0,0 +1,6 @@
+#[cfg(feature = "extract")]
+mod extract;
+mod file;
+mod manager;
+mod remote;
+mod verify;
@@ -0,0 +1,154 @@
+use std::io::SeekFrom;
+
+use crate::mocked::*;
+
+use debrid::Error;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_read_remote_file() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::method("HEAD"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Accept-Ranges", "bytes")
+                    .insert_header("Content-Length", "1000"),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body.clone()))
+            .expect(4)
+            .mount(&mock)
+            .await;
+
+        let mut file = debrid
+            .downloader()
+            .remote()
+            .source(format!("{}/d/ABCDEFGHIJKLMNO/video.mp4", mock.uri()))
+            .block_size(100)
+            .read_ahead(3)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(file.len(), 1000);
+
+        let mut buf = vec![0u8; 150];
+        file.seek(SeekFrom::Start(250)).await.unwrap();
+        file.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf, body[250..400]);
+
+        // Served from the cache, no request
+        file.seek(SeekFrom::Current(-100)).await.unwrap();
+        file.read_exact(&mut buf[..50]).await.unwrap();
+        assert_eq!(buf[..50], body[300..350]);
+
+        let mut tail = Vec::new();
+        file.seek(SeekFrom::End(-50)).await.unwrap();
+        file.read_to_end(&mut tail).await.unwrap();
+        assert_eq!(tail, body[950..]);
+
+        // Two more requests, for blocks 0-3 and 6-9
+        let mut all = Vec::new();
+        file.rewind().await.unwrap();
+        file.read_to_end(&mut all).await.unwrap();
+        assert_eq!(all, body);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_learn_remote_file_length_from_content_range() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body.clone()))
+            .mount(&mock)
+            .await;
+
+        let mut file = debrid
+            .downloader()
+            .remote()
+            .source(format!("{}/d/ABCDEFGHIJKLMNO/video.mp4", mock.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(file.len(), 1000);
+
+        let mut all = Vec::new();
+        file.read_to_end(&mut all).await.unwrap();
+        assert_eq!(all, body);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_to_open_remote_file_without_range_support() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body(1000)))
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .downloader()
+            .remote()
+            .source(format!("{}/d/ABCDEFGHIJKLMNO/video.mp4", mock.uri()))
+            .send()
+            .await;
+
+        assert!(res.is_err_and(|e| matches!(e, Error::RangeNotSupported)));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_send_token_to_remote_file_host() {
+    mocked(|mock, debrid| async move {
+        let body = body(1000);
+
+        Mock::given(matchers::header_exists("Authorization"))
+            .respond_with(ResponseTemplate::new(401))
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/ABCDEFGHIJKLMNO/video.mp4"))
+            .respond_with(RangeResponder(body.clone()))
+            .mount(&mock)
+            .await;
+
+        let mut file = debrid
+            .downloader()
+            .remote()
+            .source(format!("{}/d/ABCDEFGHIJKLMNO/video.mp4", mock.uri()))
+            .block_size(100)
+            .send()
+            .await
+            .unwrap();
+
+        let mut all = Vec::new();
+        file.read_to_end(&mut all).await.unwrap();
+
+        assert_eq!(all, body);
+        assert!(mock
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .all(|request| !request.headers.contains_key("Authorization")));
+    })
+    .await
+}
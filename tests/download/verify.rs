@@ -0,0 +1,391 @@
+use sha1::{Digest, Sha1};
+
+use crate::mocked::*;
+
+use debrid::{
+    download::{Metainfo, Source},
+    models, Error,
+};
+use wiremock::{matchers, Mock};
+
+const PIECE_LENGTH: usize = 1024;
+
+fn body(len: usize, seed: usize) -> Vec<u8> {
+    (0..len).map(|i| ((i + seed) % 251) as u8).collect()
+}
+
+fn pieces(data: &[u8]) -> Vec<u8> {
+    data.chunks(PIECE_LENGTH)
+        .flat_map(|piece| Sha1::digest(piece).to_vec())
+        .collect()
+}
+
+fn string(value: &str) -> Vec<u8> {
+    bytes(value.as_bytes())
+}
+
+fn bytes(value: &[u8]) -> Vec<u8> {
+    [format!("{}:", value.len()).as_bytes(), value].concat()
+}
+
+/// Bencodes a multi-file torrent of `files`, returning it with its info dictionary.
+fn torrent(files: &[(&[&str], &[u8])]) -> (Vec<u8>, Vec<u8>) {
+    let mut list = b"l".to_vec();
+
+    for (path, data) in files {
+        list.extend(format!("d6:lengthi{}e4:pathl", data.len()).as_bytes());
+        path.iter().for_each(|part| list.extend(string(part)));
+        list.extend(b"ee");
+    }
+    list.push(b'e');
+
+    let data = files
+        .iter()
+        .flat_map(|(_, data)| data.to_vec())
+        .collect::<Vec<_>>();
+    let info = [
+        b"d5:files".to_vec(),
+        list,
+        string("name"),
+        string("Release"),
+        string("piece length"),
+        format!("i{}e", PIECE_LENGTH).into_bytes(),
+        string("pieces"),
+        bytes(&pieces(&data)),
+        b"e".to_vec(),
+    ]
+    .concat();
+
+    let torrent = [
+        b"d8:announce".to_vec(),
+        string("udp://tracker.example.com:80"),
+        string("info"),
+        info.clone(),
+        b"e".to_vec(),
+    ]
+    .concat();
+
+    (torrent, info)
+}
+
+#[test]
+fn should_parse_torrent_file() {
+    let (torrent, info) = torrent(&[
+        (&["a.bin"], &body(3000, 0)),
+        (&["sub", "b.bin"], &body(1500, 7)),
+    ]);
+
+    let metainfo = Metainfo::from_bytes(&torrent).unwrap();
+
+    let hash = Sha1::digest(&info)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    assert_eq!(metainfo.info_hash.as_str(), hash);
+    assert_eq!(metainfo.name, "Release");
+    assert_eq!(metainfo.piece_length, 1024);
+    assert_eq!(metainfo.pieces.len(), 5);
+    assert_eq!(metainfo.len(), 4500);
+    assert_eq!(
+        metainfo
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.length))
+            .collect::<Vec<_>>(),
+        vec![("/a.bin", 3000), ("/sub/b.bin", 1500)]
+    );
+}
+
+#[test]
+fn should_fail_to_parse_invalid_torrent_file() {
+    let (mut torrent, _) = torrent(&[(&["a.bin"], &body(3000, 0))]);
+    torrent.truncate(torrent.len() - 1);
+
+    assert!(matches!(
+        Metainfo::from_bytes(&torrent),
+        Err(Error::InvalidTorrent(_))
+    ));
+}
+
+#[test]
+fn should_fail_to_parse_unsafe_torrent_paths() {
+    let data = body(1000, 0);
+
+    for path in [
+        &["..", "a.bin"][..],
+        &["sub", "..", "..", "a.bin"],
+        &["", "a.bin"],
+        &["."],
+        &["sub/../../a.bin"],
+        &["/etc", "a.bin"],
+    ] {
+        let (torrent, _) = torrent(&[(path, &data)]);
+
+        assert!(
+            matches!(
+                Metainfo::from_bytes(&torrent),
+                Err(Error::InvalidTorrent(_))
+            ),
+            "{:?} was accepted",
+            path
+        );
+    }
+}
+
+#[test]
+fn should_fail_to_parse_overflowing_torrent_size() {
+    let torrent = [
+        b"d4:infod5:filesl".to_vec(),
+        format!("d6:lengthi{}e4:pathl5:a.binee", i64::MAX).into_bytes(),
+        format!("d6:lengthi{}e4:pathl5:b.binee", i64::MAX).into_bytes(),
+        format!("d6:lengthi{}e4:pathl5:c.binee", i64::MAX).into_bytes(),
+        b"e4:name7:Release12:piece lengthi1024e6:pieces0:ee".to_vec(),
+    ]
+    .concat();
+
+    assert!(matches!(
+        Metainfo::from_bytes(&torrent),
+        Err(Error::InvalidTorrent(_))
+    ));
+}
+
+#[tokio::test]
+async fn should_verify_and_refetch_corrupt_ranges() {
+    mocked(|mock, debrid| async move {
+        let a = body(3000, 0);
+        let b = body(1500, 7);
+        let (torrent, _) = torrent(&[(&["a.bin"], &a), (&["sub", "b.bin"], &b)]);
+        let metainfo = Metainfo::from_bytes(&torrent).unwrap();
+
+        let directory = scratch("verify-refetch");
+        std::fs::create_dir_all(directory.join("sub")).unwrap();
+        std::fs::write(directory.join("a.bin"), &a).unwrap();
+
+        // Piece 3 covers bytes 72 to 1095 of b.bin
+        let mut corrupted = b.clone();
+        corrupted[100] ^= 0xFF;
+        std::fs::write(directory.join("sub/b.bin"), &corrupted).unwrap();
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(!verification.is_valid());
+        assert!(verification.files[0].is_valid());
+        assert_eq!(verification.files[1].actual, Some(1500));
+        assert_eq!(verification.files[1].corrupt, vec![72..=1095]);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/d/b.bin"))
+            .respond_with(RangeResponder(b.clone()))
+            .mount(&mock)
+            .await;
+
+        let report = &verification.files[1];
+        assert!(report.prepare_refetch().await.unwrap());
+
+        let mut source = Source::from(format!("{}/d/b.bin", mock.uri()));
+        source.filesize = 1500;
+
+        debrid
+            .downloader()
+            .file()
+            .source(source)
+            .path(&report.local)
+            .send()
+            .await
+            .unwrap();
+
+        let ranges = mock
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter_map(|r| {
+                r.headers
+                    .get("Range")
+                    .map(|v| v.to_str().unwrap().to_string())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec!["bytes=0-0", "bytes=72-1095"]);
+        assert_eq!(std::fs::read(&report.local).unwrap(), b);
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .send()
+            .await
+            .unwrap();
+        assert!(verification.is_valid());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_report_missing_files() {
+    mocked(|_mock, debrid| async move {
+        let a = body(3000, 0);
+        let b = body(1500, 7);
+        let (torrent, _) = torrent(&[(&["a.bin"], &a), (&["sub", "b.bin"], &b)]);
+        let metainfo = Metainfo::from_bytes(&torrent).unwrap();
+
+        let directory = scratch("verify-missing");
+        std::fs::write(directory.join("a.bin"), &a).unwrap();
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(verification.files[0].corrupt.is_empty());
+        assert_eq!(verification.files[1].actual, None);
+        assert_eq!(verification.unverified_pieces, vec![2, 3, 4]);
+        assert!(!verification.is_valid());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_verify_flattened_files_from_piece_hashes() {
+    mocked(|_mock, debrid| async move {
+        let a = body(3000, 0);
+        let b = body(1000, 7);
+
+        let mut torrent =
+            resource::<models::torrents::TorrentInfo>("tests/.resources/torrents/info.json");
+        torrent.files = vec![
+            models::torrents::TorrentFile {
+                id: 2,
+                path: "/Extras/b.bin".to_string(),
+                bytes: 1000,
+                selected: true,
+            },
+            models::torrents::TorrentFile {
+                id: 1,
+                path: "/a.bin".to_string(),
+                bytes: 3000,
+                selected: true,
+            },
+        ];
+
+        let data = [a.clone(), b.clone()].concat();
+        let hashes = pieces(&data)
+            .chunks(20)
+            .map(|hash| hash.try_into().unwrap())
+            .collect();
+        let metainfo = Metainfo::from_pieces(&torrent, PIECE_LENGTH as u64, hashes).unwrap();
+
+        let directory = scratch("verify-flatten");
+        std::fs::write(directory.join("a.bin"), &a).unwrap();
+        std::fs::write(directory.join("b.bin"), &b[..900]).unwrap();
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .flatten(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(verification.files[0].is_valid());
+        assert_eq!(verification.files[1].actual, Some(900));
+        assert_eq!(verification.files[1].corrupt, vec![72..=999]);
+        assert!(!verification.files[1].prepare_refetch().await.unwrap());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_verify_files_outside_the_directory() {
+    mocked(|_mock, debrid| async move {
+        let a = body(1000, 0);
+        let (torrent, _) = torrent(&[(&["a.bin"], &a)]);
+        let mut metainfo = Metainfo::from_bytes(&torrent).unwrap();
+        metainfo.files[0].path = "/../a.bin".to_string();
+
+        let directory = scratch("verify-unsafe");
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(directory.join("inner"))
+            .send()
+            .await;
+
+        assert!(matches!(verification, Err(Error::InvalidTorrent(_))));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_verify_pieces_larger_than_the_files() {
+    mocked(|_mock, debrid| async move {
+        let a = body(1000, 0);
+        let (torrent, _) = torrent(&[(&["a.bin"], &a)]);
+        let mut metainfo = Metainfo::from_bytes(&torrent).unwrap();
+        metainfo.piece_length = i64::MAX as u64;
+
+        let directory = scratch("verify-piece-length");
+        std::fs::write(directory.join("a.bin"), &a).unwrap();
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(verification.is_valid());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_verify_huge_declared_pieces() {
+    mocked(|_mock, debrid| async move {
+        let length = 1u64 << 50;
+        let torrent = [
+            b"d4:infod5:filesl".to_vec(),
+            format!("d6:lengthi{}e4:pathl5:a.binee", length).into_bytes(),
+            b"e4:name7:Release".to_vec(),
+            format!("12:piece lengthi{}e", length).into_bytes(),
+            string("pieces"),
+            bytes(&[0; 20]),
+            b"ee".to_vec(),
+        ]
+        .concat();
+        let metainfo = Metainfo::from_bytes(&torrent).unwrap();
+
+        let directory = scratch("verify-huge-piece");
+        std::fs::write(directory.join("a.bin"), body(1000, 0)).unwrap();
+
+        let verification = debrid
+            .downloader()
+            .verify()
+            .metainfo(&metainfo)
+            .directory(&directory)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(verification.files[0].actual, Some(1000));
+        assert_eq!(verification.files[0].corrupt, vec![0..=length - 1]);
+    })
+    .await
+}
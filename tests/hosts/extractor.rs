@@ -0,0 +1,65 @@
+use crate::mocked::*;
+
+use debrid::hosts::LinkKind;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_extract_supported_links() {
+    mocked(|mock, debrid| async move {
+        for (path, resource_path) in [
+            ("/hosts/regex", "tests/.resources/hosts/regex.json"),
+            (
+                "/hosts/regexFolder",
+                "tests/.resources/hosts/regex_folder.json",
+            ),
+            ("/hosts/domains", "tests/.resources/hosts/domains.json"),
+        ] {
+            let r = resource::<Vec<String>>(resource_path);
+
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path(path))
+                .respond_with(ResponseTemplate::new(200).set_body_json(r))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let extractor = debrid.hosts().extractor().send().await.unwrap();
+
+        let post = r#"
+            Part 1: https://www.example0.com/abcdefghijkl
+            Part 2: <a href="https://example2.com/mnopqrstuvwx">mirror</a>
+            Part 1 again: https://www.example0.com/abcdefghijkl
+            Extras: https://drive.google.com/drive/folders/123ABC456def "folder"
+            Unsupported: https://example9.com/abcdefghijkl
+        "#;
+
+        let links = extractor
+            .extract(post)
+            .into_iter()
+            .map(|link| (link.url, link.kind, link.host))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "https://www.example0.com/abcdefghijkl".to_string(),
+                    LinkKind::File,
+                    Some("example0.com".to_string())
+                ),
+                (
+                    "https://example2.com/mnopqrstuvwx".to_string(),
+                    LinkKind::File,
+                    Some("example2.com".to_string())
+                ),
+                (
+                    "https://drive.google.com/drive/folders/123ABC456def".to_string(),
+                    LinkKind::Folder,
+                    None
+                ),
+            ]
+        );
+    })
+    .await
+}
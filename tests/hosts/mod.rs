@@ -1,5 +1,8 @@
 mod domains;
+mod extractor;
 mod get;
+mod monitor;
 mod regex;
 mod regex_folder;
+mod registry;
 mod status;
@@ -0,0 +1,121 @@
+use std::{collections::HashMap, time::Duration};
+
+use crate::mocked::*;
+
+use debrid::{
+    hosts::{HostEvent, HostMonitor},
+    models::hosts::{HostInfo, HostStatus},
+};
+use futures::StreamExt;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_stream_status_changes() {
+    mocked(|mock, debrid| async move {
+        let up = resource::<HashMap<String, HostInfo>>("tests/.resources/hosts/status.json");
+
+        let mut down = up.clone();
+        let host = down.get_mut("example0.com").unwrap();
+        host.status = HostStatus::Down;
+        host.check_time = "2024-09-27T16:00:00.000Z".to_string();
+
+        // Served in order: baseline, example0.com down, then up again
+        for (priority, body) in [(1, &up), (2, &down)] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path("/hosts/status"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .up_to_n_times(1)
+                .with_priority(priority)
+                .mount(&mock)
+                .await;
+        }
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&up))
+            .with_priority(3)
+            .mount(&mock)
+            .await;
+
+        let events = HostMonitor::builder()
+            .debrid(debrid)
+            .interval(Duration::from_millis(10))
+            .build()
+            .events()
+            .take(3)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                HostEvent::Down {
+                    domain: "example0.com".to_string(),
+                    check_time: "2024-09-27T16:00:00.000Z".to_string(),
+                },
+                HostEvent::CompetitorsUp {
+                    domain: "example0.com".to_string(),
+                    competitors: vec!["competitor0.com".to_string()],
+                    check_time: "2024-09-27T16:00:00.000Z".to_string(),
+                },
+                HostEvent::Up {
+                    domain: "example0.com".to_string(),
+                    check_time: "2024-09-27T15:53:09.000Z".to_string(),
+                },
+            ]
+        );
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_keep_polling_after_errors() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+
+        let up = resource::<HashMap<String, HostInfo>>("tests/.resources/hosts/status.json");
+        let mut unsupported = up.clone();
+        unsupported.get_mut("example1.com").unwrap().supported = false;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&up))
+            .up_to_n_times(1)
+            .with_priority(2)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&unsupported))
+            .with_priority(3)
+            .mount(&mock)
+            .await;
+
+        let events = HostMonitor::builder()
+            .debrid(debrid)
+            .interval(Duration::from_millis(10))
+            .build()
+            .events()
+            .take(2)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(events[0].is_err());
+        assert_eq!(
+            events[1].as_ref().unwrap(),
+            &HostEvent::Unsupported {
+                domain: "example1.com".to_string(),
+                check_time: "2024-09-27T15:53:14.000Z".to_string(),
+            }
+        );
+    })
+    .await
+}
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::mocked::*;
+
+use debrid::hosts::HostRegistry;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+async fn mount(mock: &MockServer, times: u64) {
+    let hosts = resource::<serde_json::Value>("tests/.resources/hosts/index.json");
+    let status = resource::<serde_json::Value>("tests/.resources/hosts/status.json");
+
+    for (path, body) in [("/hosts", hosts), ("/hosts/status", status)] {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(times)
+            .mount(mock)
+            .await;
+    }
+
+    for (path, resource_path) in [
+        ("/hosts/regex", "tests/.resources/hosts/regex.json"),
+        (
+            "/hosts/regexFolder",
+            "tests/.resources/hosts/regex_folder.json",
+        ),
+        ("/hosts/domains", "tests/.resources/hosts/domains.json"),
+    ] {
+        let r = resource::<Vec<String>>(resource_path);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(times)
+            .mount(mock)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn should_answer_lookups_from_cache() {
+    mocked(|mock, debrid| async move {
+        mount(&mock, 1).await;
+
+        let registry = HostRegistry::builder().debrid(debrid).build();
+        assert!(registry.is_stale());
+        assert!(!registry.is_supported("https://example0.com/abcdefghijkl"));
+
+        assert!(registry.refresh_if_stale().await.unwrap());
+        assert!(!registry.refresh_if_stale().await.unwrap());
+
+        let url = "https://www.example0.com/abcdefghijkl";
+        assert!(registry.is_supported(url));
+        assert!(!registry.is_supported("https://example9.com/abcdefghijkl"));
+        assert_eq!(
+            registry.domain_for_url(url).as_deref(),
+            Some("example0.com")
+        );
+        assert_eq!(
+            registry.host_for_url(url).map(|h| h.id).as_deref(),
+            Some("e0")
+        );
+        assert_eq!(registry.is_up(url), Some(true));
+        assert_eq!(registry.is_up("example9.com"), None);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_refresh_when_stale() {
+    mocked(|mock, debrid| async move {
+        mount(&mock, 2).await;
+
+        let registry = HostRegistry::builder()
+            .debrid(debrid)
+            .ttl(Duration::ZERO)
+            .build();
+
+        assert!(registry.refresh_if_stale().await.unwrap());
+        assert!(registry.is_stale());
+        assert!(registry.refresh_if_stale().await.unwrap());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_skip_status_without_authentication() {
+    mocked(|mock, debrid| async move {
+        let hosts = resource::<serde_json::Value>("tests/.resources/hosts/index.json");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(hosts))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        for path in ["/hosts/regex", "/hosts/regexFolder", "/hosts/domains"] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path(path))
+                .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<String>::new()))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let registry = HostRegistry::builder().debrid(debrid).status(false).build();
+
+        registry.refresh().await.unwrap();
+        assert!(registry.snapshot().unwrap().status.is_none());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_restore_snapshot_offline() {
+    let path = scratch("hosts-snapshot").join("hosts.json");
+
+    mocked(|mock, debrid| {
+        let path = path.clone();
+
+        async move {
+            mount(&mock, 1).await;
+
+            let registry = HostRegistry::builder().debrid(debrid).build();
+            registry.refresh().await.unwrap();
+            registry.save(&path).await.unwrap();
+        }
+    })
+    .await;
+
+    mocked(|_mock, debrid| async move {
+        let registry = HostRegistry::builder().debrid(debrid).build();
+        registry.restore(&path).await.unwrap();
+
+        assert!(!registry.is_stale());
+        assert!(registry.is_supported("https://example1.com/abcdefghijkl"));
+        assert!(registry
+            .extractor()
+            .unwrap()
+            .classify("https://drive.google.com/drive/folders/123ABC")
+            .is_some());
+
+        assert_eq!(registry.snapshot().unwrap().hosts.len(), 3);
+    })
+    .await
+}
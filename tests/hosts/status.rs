@@ -46,3 +46,37 @@ async fn should_fail_to_get_hosts_status() {
     })
     .await
 }
+
+#[tokio::test]
+async fn should_get_hosts_status_with_unknown_status() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<HashMap<String, models::hosts::HostInfo>>(
+            "tests/.resources/hosts/status_unknown.json",
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/hosts/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid.hosts().status().send().await.unwrap();
+        let host = &res["example2.com"];
+
+        assert_eq!(
+            host.status,
+            models::hosts::HostStatus::Unknown("maintenance".to_string())
+        );
+        assert_eq!(
+            host.competitors_status["competitor6.com"].status,
+            models::hosts::HostStatus::Unknown("slow".to_string())
+        );
+        assert_eq!(res["example0.com"].status, models::hosts::HostStatus::Up);
+
+        let value = serde_json::to_value(host).unwrap();
+        assert_eq!(value["status"], "maintenance");
+        assert_eq!(value["competitors_status"]["competitor6.com"]["status"], "slow");
+    })
+    .await
+}
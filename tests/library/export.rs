@@ -0,0 +1,297 @@
+use std::fs;
+
+use crate::mocked::*;
+
+use debrid::{
+    library::MANIFEST_FILE,
+    models::{
+        downloads::Download,
+        streaming::{MediaInfo, MediaType, StreamFormat},
+        torrents::{Torrent, TorrentInfo},
+        unrestrict::Link,
+    },
+    streams::StreamPreference,
+    Debrid,
+};
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+/// Mounts a library of a movie and an audio download, and an episode torrent.
+pub async fn mount_library(mock: &MockServer) {
+    let mut downloads = resource::<Vec<Download>>("tests/.resources/downloads/index.json");
+    downloads.truncate(2);
+    downloads[0].id = "AUDIOABCDEFGH".into();
+    downloads[1].id = "MOVIEABCDEFGH".into();
+    downloads[1].download = "https://example.com/d/MOVIEABCDEFGH/movie.mkv".to_string();
+
+    let torrents = resource::<Vec<Torrent>>("tests/.resources/torrents/index.json");
+    let info = resource::<TorrentInfo>("tests/.resources/torrents/info.json");
+
+    let mut link = resource::<Link>("tests/.resources/unrestrict/link.json");
+    link.id = "EPISODEABCDEF".into();
+    link.filename = "Show.S01E03.mkv".to_string();
+    link.download = "https://example.com/d/EPISODEABCDEF/Show.S01E03.mkv".to_string();
+
+    let audio = resource::<MediaInfo>("tests/.resources/streaming/media_infos.json");
+    let movie = resource::<MediaInfo>("tests/.resources/streaming/media_infos_movie.json");
+    let mut episode = movie.clone();
+    episode.type_ = MediaType::Show;
+    episode.filename = "Show".to_string();
+    episode.season = Some("1".to_string());
+    episode.episode = Some("3".to_string());
+    episode.poster_path = Some("https://example.com/poster.jpg".to_string());
+
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/downloads"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(downloads))
+        .mount(mock)
+        .await;
+
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/torrents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(torrents))
+        .mount(mock)
+        .await;
+
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/torrents/info/ABCDEFGHIJKLM"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(info))
+        .mount(mock)
+        .await;
+
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/unrestrict/link"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(link))
+        .mount(mock)
+        .await;
+
+    for (id, info) in [
+        ("AUDIOABCDEFGH", audio),
+        ("MOVIEABCDEFGH", movie),
+        ("EPISODEABCDEF", episode),
+    ] {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(format!("/streaming/mediaInfos/{}", id)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(info))
+            .mount(mock)
+            .await;
+    }
+}
+
+async fn export(debrid: &Debrid, name: &str, nfo: bool) -> std::path::PathBuf {
+    let root = scratch(name);
+
+    let report = debrid
+        .library()
+        .export()
+        .root(root.clone())
+        .nfo(nfo)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(report.written.len(), 2);
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.failed.is_empty());
+
+    root
+}
+
+#[tokio::test]
+async fn should_export_strm_files() {
+    mocked(|mock, debrid| async move {
+        mount_library(&mock).await;
+
+        let root = export(&debrid, "library-export", false).await;
+
+        assert_eq!(
+            fs::read_to_string(
+                root.join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).strm")
+            )
+            .unwrap(),
+            "https://example.com/d/MOVIEABCDEFGH/movie.mkv\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("Shows/Show/Season 01/Show - S01E03.strm")).unwrap(),
+            "https://example.com/d/EPISODEABCDEF/Show.S01E03.mkv\n"
+        );
+        assert!(!root
+            .join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).nfo")
+            .exists());
+        assert!(root.join(MANIFEST_FILE).exists());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_export_nfo_files() {
+    mocked(|mock, debrid| async move {
+        mount_library(&mock).await;
+
+        let root = export(&debrid, "library-nfo", true).await;
+
+        let movie =
+            fs::read_to_string(root.join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).nfo"))
+                .unwrap();
+        assert!(movie.contains("<movie>"));
+        assert!(movie.contains("<title>Big Buck Bunny</title>"));
+        assert!(movie.contains("<year>2008</year>"));
+        assert!(movie.contains("<runtime>10</runtime>"));
+
+        let episode =
+            fs::read_to_string(root.join("Shows/Show/Season 01/Show - S01E03.nfo")).unwrap();
+        assert!(episode.contains("<episodedetails>"));
+        assert!(episode.contains("<season>1</season>"));
+        assert!(episode.contains("<episode>3</episode>"));
+        assert!(episode.contains("<thumb aspect=\"poster\">https://example.com/poster.jpg</thumb>"));
+
+        let show = fs::read_to_string(root.join("Shows/Show/tvshow.nfo")).unwrap();
+        assert!(show.contains("<tvshow>"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_export_stream_links() {
+    mocked(|mock, debrid| async move {
+        mount_library(&mock).await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path_regex("^/streaming/transcode/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(resource::<
+                debrid::models::streaming::Transcode,
+            >(
+                "tests/.resources/streaming/transcode_qualities.json",
+            )))
+            .mount(&mock)
+            .await;
+
+        let root = scratch("library-stream");
+        let preference = StreamPreference::builder()
+            .formats(vec![StreamFormat::Dash])
+            .build();
+
+        let report = debrid
+            .library()
+            .export()
+            .root(root.clone())
+            .stream(&preference)
+            .torrents(false)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.written.len(), 1);
+        assert_eq!(
+            fs::read_to_string(
+                root.join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).strm")
+            )
+            .unwrap(),
+            "https://example.com/t/ABCDEFGHIJKLMNO/eng1/none/aac/full.mpd\n"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_reuse_downloads_of_torrent_links() {
+    mocked(|mock, debrid| async move {
+        let info = resource::<TorrentInfo>("tests/.resources/torrents/info.json");
+
+        let mut downloads = resource::<Vec<Download>>("tests/.resources/downloads/index.json");
+        downloads.truncate(1);
+        downloads[0].id = "EPISODEABCDEF".into();
+        downloads[0].link = info.links[0].clone();
+        downloads[0].download = "https://example.com/d/EPISODEABCDEF/Show.S01E03.mkv".to_string();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/downloads"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(downloads))
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(ResponseTemplate::new(503))
+            .with_priority(1)
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        mount_library(&mock).await;
+
+        let root = scratch("library-reuse");
+
+        let report = debrid
+            .library()
+            .export()
+            .root(root.clone())
+            .downloads(false)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.written.len(), 1);
+        assert!(report.failed.is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join("Shows/Show/Season 01/Show - S01E03.strm")).unwrap(),
+            "https://example.com/d/EPISODEABCDEF/Show.S01E03.mkv\n"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_skip_files_sharing_a_path() {
+    mocked(|mock, debrid| async move {
+        let mut downloads = resource::<Vec<Download>>("tests/.resources/downloads/index.json");
+        downloads.truncate(2);
+        downloads[0].id = "MOVIEABCDEFGH".into();
+        downloads[0].filename = "Big.Buck.Bunny.2008.1080p.mkv".to_string();
+        downloads[0].download = "https://example.com/d/MOVIEABCDEFGH/movie.mkv".to_string();
+        downloads[1].id = "MOVIE4KBCDEFG".into();
+        downloads[1].filename = "Big.Buck.Bunny.2008.2160p.mkv".to_string();
+        downloads[1].download = "https://example.com/d/MOVIE4KBCDEFG/movie.mkv".to_string();
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/downloads"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(downloads))
+            .with_priority(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/streaming/mediaInfos/MOVIE4KBCDEFG"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(resource::<MediaInfo>(
+                    "tests/.resources/streaming/media_infos_movie.json",
+                )),
+            )
+            .mount(&mock)
+            .await;
+
+        mount_library(&mock).await;
+
+        let root = scratch("library-collision");
+
+        let report = debrid
+            .library()
+            .export()
+            .root(root.clone())
+            .torrents(false)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.written.len(), 1);
+        assert_eq!(report.skipped, vec!["Big.Buck.Bunny.2008.2160p.mkv"]);
+        assert_eq!(
+            fs::read_to_string(
+                root.join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).strm")
+            )
+            .unwrap(),
+            "https://example.com/d/MOVIEABCDEFGH/movie.mkv\n"
+        );
+    })
+    .await;
+}
@@ -0,0 +1,73 @@
+use std::{fs, time::Duration};
+
+use super::export::mount_library;
+use crate::mocked::*;
+
+use debrid::models::unrestrict::Link;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_refresh_expired_links() {
+    mocked(|mock, debrid| async move {
+        mount_library(&mock).await;
+
+        let root = scratch("library-refresh");
+
+        debrid
+            .library()
+            .export()
+            .root(root.clone())
+            .send()
+            .await
+            .unwrap();
+
+        // Fresh links are kept
+        let report = debrid
+            .library()
+            .refresh()
+            .root(root.clone())
+            .send()
+            .await
+            .unwrap();
+
+        assert!(report.written.is_empty());
+
+        // Every link is expired, the movie being unrestricted again
+        mock.reset().await;
+
+        let mut link = resource::<Link>("tests/.resources/unrestrict/link.json");
+        link.download = "https://example.com/d/REFRESHED/file.mkv".to_string();
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(link))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        fs::remove_file(root.join("Shows/Show/Season 01/Show - S01E03.strm")).unwrap();
+
+        let report = debrid
+            .library()
+            .refresh()
+            .root(root.clone())
+            .max_age(Duration::ZERO)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.written.len(), 1);
+        assert_eq!(
+            report.skipped,
+            vec!["Shows/Show/Season 01/Show - S01E03.strm".to_string()]
+        );
+        assert_eq!(
+            fs::read_to_string(
+                root.join("Movies/Big Buck Bunny (2008)/Big Buck Bunny (2008).strm")
+            )
+            .unwrap(),
+            "https://example.com/d/REFRESHED/file.mkv\n"
+        );
+    })
+    .await;
+}
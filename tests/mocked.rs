@@ -1,8 +1,9 @@
-use std::{fs, future::Future};
+use std::{env, fs, future::Future, path::PathBuf};
 
-use debrid::Debrid;
+use debrid::{models::unrestrict::Link, Debrid};
 use serde::de::DeserializeOwned;
-use wiremock::MockServer;
+use serde_json::json;
+use wiremock::{MockServer, Request, Respond, ResponseTemplate};
 
 pub async fn mocked<F, Fut, T>(closure: F) -> T
 where
@@ -24,3 +25,60 @@ pub fn resource<T: DeserializeOwned>(path: &str) -> T {
     let contents = fs::read_to_string(path).unwrap();
     serde_json::from_str(&contents).unwrap()
 }
+
+/// RealDebrid error response with the given error code.
+pub fn error(code: i32) -> ResponseTemplate {
+    ResponseTemplate::new(503).set_body_json(json!({ "error": "error", "error_code": code }))
+}
+
+/// Deterministic file contents of `len` bytes.
+pub fn body(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// Unrestricted link to a file served by the mock server at `/d/{path}`.
+pub fn served_link(uri: &str, path: &str, filesize: u64, chunks: u64) -> Link {
+    let mut link = resource::<Link>("tests/.resources/unrestrict/link.json");
+    link.download = format!("{}/d/{}", uri, path);
+    link.filename = path.rsplit('/').next().unwrap_or(path).to_string();
+    link.host = "example.com".to_string();
+    link.filesize = filesize;
+    link.chunks = chunks;
+    link
+}
+
+/// Serves a byte buffer, honouring `Range` requests like a download server.
+pub struct RangeResponder(pub Vec<u8>);
+
+impl Respond for RangeResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let len = self.0.len();
+
+        let range = request
+            .headers
+            .get("Range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("bytes="))
+            .and_then(|v| v.split_once('-'))
+            .map(|(start, end)| {
+                let start = start.parse::<usize>().unwrap();
+                let end = end.parse::<usize>().map_or(len - 1, |e| e.min(len - 1));
+                (start, end)
+            });
+
+        match range {
+            Some((start, end)) => ResponseTemplate::new(206)
+                .insert_header("Content-Range", format!("bytes {}-{}/{}", start, end, len))
+                .set_body_bytes(self.0[start..=end].to_vec()),
+            None => ResponseTemplate::new(200).set_body_bytes(self.0.clone()),
+        }
+    }
+}
+
+/// Returns a fresh, empty scratch directory for a test.
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("debrid-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
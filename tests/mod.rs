@@ -1,7 +1,13 @@
 mod mocked;
 
+mod containers;
+mod download;
 mod downloads;
 mod hosts;
+mod library;
+mod playlist;
+mod profile;
+mod release;
 mod root;
 mod settings;
 mod streaming;
@@ -0,0 +1,105 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::{
+        downloads::Download,
+        streaming::{MediaInfo, Transcode},
+        torrents::TorrentInfo,
+        unrestrict::Link,
+    },
+    playlist::{Playlist, PlaylistEntry},
+};
+
+fn download(filename: &str) -> Download {
+    let mut download = resource::<Vec<Download>>("tests/.resources/downloads/index.json").remove(0);
+    download.filename = filename.to_string();
+    download.download = format!("https://example.com/d/{}", filename);
+    download
+}
+
+fn link(filename: &str) -> Link {
+    let mut link = resource::<Link>("tests/.resources/unrestrict/link.json");
+    link.filename = filename.to_string();
+    link.download = format!("https://example.com/d/{}", filename);
+    link
+}
+
+#[test]
+fn should_render_downloads_sorted_by_episode() {
+    let mut playlist = Playlist::from_downloads(&[
+        download("Show.S01E10.mkv"),
+        download("Extras.mkv"),
+        download("Show.S01E02.mkv"),
+        download("Show.S02E01.mkv"),
+    ]);
+    playlist.sort_by_episode();
+
+    assert_eq!(
+        playlist.to_m3u(),
+        "#EXTM3U\n\
+         #EXTINF:-1,Show.S01E02\n\
+         https://example.com/d/Show.S01E02.mkv\n\
+         #EXTINF:-1,Show.S01E10\n\
+         https://example.com/d/Show.S01E10.mkv\n\
+         #EXTINF:-1,Show.S02E01\n\
+         https://example.com/d/Show.S02E01.mkv\n\
+         #EXTINF:-1,Extras\n\
+         https://example.com/d/Extras.mkv\n"
+    );
+}
+
+#[test]
+fn should_render_torrent_with_relative_paths() {
+    let mut torrent = resource::<TorrentInfo>("tests/.resources/torrents/info.json");
+    torrent.filename = "Show Season 1".to_string();
+    torrent.links = vec![
+        "https://example.com/d/A".to_string(),
+        "https://example.com/d/B".to_string(),
+    ];
+
+    let playlist = Playlist::from_torrent(
+        &torrent,
+        &[link("Show.S01E02.mkv"), link("Show.S01E01.mkv")],
+    );
+
+    assert_eq!(
+        playlist.to_m3u_relative(),
+        "#EXTM3U\n\
+         #EXTINF:-1,Show.S01E02\n\
+         Show Season 1/Show.S01E02.mkv\n\
+         #EXTINF:-1,Show.S01E01\n\
+         Show Season 1/Show.S01E01.mkv\n"
+    );
+
+    // A single link is not put in a directory
+    torrent.links.truncate(1);
+    let playlist = Playlist::from_torrent(&torrent, &[link("Movie.mkv")]);
+
+    assert_eq!(playlist.entries[0].path.as_deref(), Some("Movie.mkv"));
+}
+
+#[test]
+fn should_use_media_info_and_transcode() {
+    let mut info = resource::<MediaInfo>("tests/.resources/streaming/media_infos_movie.json");
+    info.filename = "Big Buck Bunny".to_string();
+    info.season = Some("1".to_string());
+    info.episode = Some("3".to_string());
+
+    let transcode = resource::<Transcode>("tests/.resources/streaming/transcode_qualities.json");
+
+    let entry = PlaylistEntry::from_transcode("Bunny", &transcode)
+        .unwrap()
+        .with_media_info(&info);
+
+    assert_eq!(entry.season, Some(1));
+    assert_eq!(entry.episode, Some(3));
+
+    let playlist = Playlist::from_iter([entry]);
+
+    assert_eq!(
+        playlist.to_m3u(),
+        "#EXTM3U\n\
+         #EXTINF:596,Big Buck Bunny - S01E03\n\
+         https://example.com/t/ABCDEFGHIJKLMNO/eng1/none/aac/full.m3u8\n"
+    );
+}
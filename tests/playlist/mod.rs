@@ -0,0 +1 @@
+mod m3u;
@@ -0,0 +1,153 @@
+use super::plan::{mount_account, mount_default_account};
+use crate::mocked::*;
+
+use debrid::profile::{Action, Profile};
+use serde_json::Value;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_apply_profile() {
+    mocked(|mock, debrid| async move {
+        mount_default_account(&mock).await;
+
+        let routes = [
+            ("POST", "/settings/update"),
+            ("PUT", "/settings/avatarFile"),
+            ("DELETE", "/torrents/delete/ABCDEFGHIJKLM"),
+            ("DELETE", "/downloads/delete/ABCDEFGHIJKLMNO"),
+        ];
+
+        for (method, path) in routes {
+            Mock::given(matchers::method(method))
+                .and(matchers::path(path))
+                .respond_with(ResponseTemplate::new(204))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let profile = Profile::load("tests/.resources/profile/team.json")
+            .await
+            .unwrap();
+
+        let report = debrid
+            .provisioner()
+            .apply()
+            .profile(&profile)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.applied, report.plan.actions);
+        assert_eq!(report.applied.len(), 4);
+        assert!(report.failed.is_empty());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_change_anything_on_dry_run() {
+    mocked(|mock, debrid| async move {
+        mount_default_account(&mock).await;
+
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let profile = Profile::load("tests/.resources/profile/team.json")
+            .await
+            .unwrap();
+
+        let report = debrid
+            .provisioner()
+            .apply()
+            .profile(&profile)
+            .dry_run(true)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.plan.actions.len(), 4);
+        assert!(report.applied.is_empty());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_do_nothing_once_applied() {
+    mocked(|mock, debrid| async move {
+        let mut settings = resource::<Value>("tests/.resources/settings/index.json");
+        settings["locale"] = "fr".into();
+
+        let mut user = resource::<Value>("tests/.resources/user/index.json");
+        user["avatar"] = "https://fcdn.real-debrid.com/images/avatars/12345678.png".into();
+
+        let mut torrents = resource::<Value>("tests/.resources/profile/torrents.json");
+        torrents.as_array_mut().unwrap().remove(0);
+
+        let mut downloads = resource::<Value>("tests/.resources/profile/downloads.json");
+        downloads.as_array_mut().unwrap().remove(0);
+
+        mount_account(&mock, settings, user, torrents, downloads).await;
+
+        let profile = Profile::load("tests/.resources/profile/team.json")
+            .await
+            .unwrap();
+
+        let report = debrid
+            .provisioner()
+            .apply()
+            .profile(&profile)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(report.plan.is_empty());
+        assert!(report.applied.is_empty());
+        assert_eq!(report.plan.hosts.len(), 2);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_report_failed_actions() {
+    mocked(|mock, debrid| async move {
+        mount_default_account(&mock).await;
+
+        Mock::given(matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+        Mock::given(matchers::method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+
+        let mut profile = Profile::load("tests/.resources/profile/team.json")
+            .await
+            .unwrap();
+        profile.avatar = Some(scratch("profile-missing-avatar").join("avatar.png"));
+
+        let report = debrid
+            .provisioner()
+            .apply()
+            .profile(&profile)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.applied.len(), 3);
+        assert!(matches!(
+            &report.failed[..],
+            [(Action::SetAvatar { .. }, debrid::Error::Io(_))]
+        ));
+    })
+    .await
+}
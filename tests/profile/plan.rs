@@ -0,0 +1,171 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::{hosts::HostStatus, settings::Setting},
+    profile::{Action, HostWarning, Profile, RetentionReason},
+    Error,
+};
+use serde_json::Value;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+/// Serves an account whose settings, avatar and lists are as given.
+pub async fn mount_account(
+    mock: &MockServer,
+    settings: Value,
+    user: Value,
+    torrents: Value,
+    downloads: Value,
+) {
+    let routes = [
+        ("/settings", settings),
+        ("/user", user),
+        ("/torrents", torrents),
+        ("/downloads", downloads),
+        (
+            "/hosts/status",
+            resource::<Value>("tests/.resources/hosts/status.json"),
+        ),
+    ];
+
+    for (path, body) in routes {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(mock)
+            .await;
+    }
+}
+
+pub async fn mount_default_account(mock: &MockServer) {
+    mount_account(
+        mock,
+        resource("tests/.resources/settings/index.json"),
+        resource("tests/.resources/user/index.json"),
+        resource("tests/.resources/profile/torrents.json"),
+        resource("tests/.resources/profile/downloads.json"),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn should_plan_profile() {
+    mocked(|mock, debrid| async move {
+        mount_default_account(&mock).await;
+
+        let profile = Profile::load("tests/.resources/profile/team.json")
+            .await
+            .unwrap();
+
+        let plan = debrid
+            .provisioner()
+            .plan()
+            .profile(&profile)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(plan.actions.len(), 4);
+        assert_eq!(
+            plan.actions[0],
+            Action::UpdateSetting {
+                setting: Setting::Locale("fr".to_string()),
+                current: Some("en".to_string()),
+            }
+        );
+        assert!(matches!(
+            &plan.actions[1],
+            Action::SetAvatar { file } if file.ends_with("profile/avatar.png")
+        ));
+        assert!(matches!(
+            &plan.actions[2],
+            Action::DeleteTorrent { id, reason: RetentionReason::Age { max_age_days: 30 }, .. }
+                if id.as_str() == "ABCDEFGHIJKLM"
+        ));
+        assert!(matches!(
+            &plan.actions[3],
+            Action::DeleteDownload { id, reason: RetentionReason::Count { max_count: 1 }, .. }
+                if id.as_str() == "ABCDEFGHIJKLMNO"
+        ));
+        assert_eq!(
+            plan.hosts,
+            vec![
+                HostWarning {
+                    domain: "example2.com".to_string(),
+                    status: Some(HostStatus::Unsupported),
+                },
+                HostWarning {
+                    domain: "missing.com".to_string(),
+                    status: None,
+                },
+            ]
+        );
+        assert!(plan
+            .to_string()
+            .starts_with("~ setting locale: en -> fr\n+ avatar "));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_only_fetch_what_the_profile_configures() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/settings"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(resource::<Value>("tests/.resources/settings/index.json")),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let profile = Profile::from_json(r#"{ "settings": { "locale": "en" } }"#).unwrap();
+
+        let plan = debrid
+            .provisioner()
+            .plan()
+            .profile(&profile)
+            .send()
+            .await
+            .unwrap();
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "No changes\n");
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_to_plan_invalid_profile() {
+    mocked(|mock, debrid| async move {
+        mount_default_account(&mock).await;
+
+        let profile = Profile::from_json(r#"{ "settings": { "locale": "xx" } }"#).unwrap();
+        let res = debrid.provisioner().plan().profile(&profile).send().await;
+
+        assert!(res.is_err_and(|e| matches!(e, Error::InvalidSetting { .. })));
+
+        let profile = Profile::from_json(r#"{ "settings": { "theme": "dark" } }"#).unwrap();
+        let res = debrid.provisioner().plan().profile(&profile).send().await;
+
+        assert!(res.is_err_and(|e| matches!(e, Error::InvalidProfile(_))));
+
+        let res = Profile::from_json(r#"{ "setting": {} }"#);
+
+        assert!(res.is_err_and(|e| matches!(e, Error::Json(_))));
+    })
+    .await
+}
+
+#[cfg(feature = "toml")]
+#[tokio::test]
+async fn should_load_toml_profile() {
+    let toml = Profile::load("tests/.resources/profile/team.toml")
+        .await
+        .unwrap();
+    let json = Profile::load("tests/.resources/profile/team.json")
+        .await
+        .unwrap();
+
+    assert_eq!(toml, json);
+}
@@ -0,0 +1,54 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::{
+        downloads::Download,
+        torrents::{Torrent, TorrentInfo},
+    },
+    release::{ReleaseSource, VideoCodec},
+};
+
+#[test]
+fn should_parse_torrent_names() {
+    let torrents = resource::<Vec<Torrent>>("tests/.resources/torrents/index.json");
+    let release = torrents[0].release();
+
+    assert_eq!(release.title, "Big Buck Bunny");
+    assert_eq!(release.source, Some(ReleaseSource::BluRay));
+    assert_eq!(release.codec, Some(VideoCodec::Xvid));
+    assert_eq!(release.group.as_deref(), Some("MEDiC"));
+
+    let info = resource::<TorrentInfo>("tests/.resources/torrents/info.json");
+    assert_eq!(info.release(), release);
+}
+
+#[test]
+fn should_parse_torrent_file_paths() {
+    let mut info = resource::<TorrentInfo>("tests/.resources/torrents/info.json");
+    info.files[1].path = "/Show.S02.1080p.WEB-DL/Show.S02E05.1080p.WEB-DL.x265-GRP.mkv".to_string();
+
+    let release = info.files[1].release();
+
+    assert_eq!(release.title, "Show");
+    assert_eq!(release.seasons, vec![2]);
+    assert_eq!(release.episodes, vec![5]);
+    assert_eq!(release.resolution, Some(1080));
+    assert_eq!(release.codec, Some(VideoCodec::H265));
+    assert_eq!(release.group.as_deref(), Some("GRP"));
+
+    let release = info.files[0].release();
+    assert_eq!(release.title, "README");
+}
+
+#[test]
+fn should_parse_download_filenames() {
+    let downloads = resource::<Vec<Download>>("tests/.resources/downloads/index.json");
+    let release = downloads[0].release();
+
+    assert_eq!(
+        release.title,
+        "Rick Astley - Never Gonna Give You Up (Official Music Video)"
+    );
+    assert_eq!(release.year, None);
+    assert!(!release.is_episode());
+}
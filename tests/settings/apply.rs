@@ -0,0 +1,163 @@
+use crate::mocked::*;
+
+use debrid::{models::settings::Setting, Error};
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_apply_changed_settings() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/settings"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(resource::<serde_json::Value>(
+                    "tests/.resources/settings/index.json",
+                )),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/settings/update"))
+            .and(matchers::body_string_contains("setting_name=locale"))
+            .and(matchers::body_string_contains("setting_value=fr"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .settings()
+            .apply()
+            .desired(vec![
+                Setting::Locale("fr".to_string()),
+                Setting::StreamingQuality("original".to_string()),
+                Setting::DownloadProtocol("IPv4".to_string()),
+            ])
+            .send()
+            .await;
+
+        assert_eq!(res.unwrap(), vec![Setting::Locale("fr".to_string())]);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_apply_invalid_settings() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/settings"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(resource::<serde_json::Value>(
+                    "tests/.resources/settings/index.json",
+                )),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/settings/update"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .settings()
+            .apply()
+            .desired(vec![
+                Setting::Locale("fr".to_string()),
+                Setting::StreamingQuality("ultra".to_string()),
+            ])
+            .send()
+            .await;
+
+        assert!(res.is_err_and(|e| matches!(
+            e,
+            Error::InvalidSetting { name, value } if name == "streaming_quality" && value == "ultra"
+        )));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_set_valid_setting() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/settings"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(resource::<serde_json::Value>(
+                    "tests/.resources/settings/index.json",
+                )),
+            )
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/settings/update"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .settings()
+            .set()
+            .setting(Setting::DownloadPort("normal".to_string()))
+            .send()
+            .await;
+        assert!(res.is_ok());
+
+        let res = debrid
+            .settings()
+            .set()
+            .setting(Setting::Locale("xx".to_string()))
+            .send()
+            .await;
+        assert!(res.is_err_and(|e| matches!(e, Error::InvalidSetting { .. })));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_apply_unadvertised_settings() {
+    mocked(|mock, debrid| async move {
+        let mut settings = resource::<serde_json::Value>("tests/.resources/settings/index.json");
+        settings
+            .as_object_mut()
+            .unwrap()
+            .remove("download_protocols");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/settings"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(settings))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/settings/update"))
+            .and(matchers::body_string_contains(
+                "setting_name=download_protocol",
+            ))
+            .and(matchers::body_string_contains("setting_value=IPv6"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .settings()
+            .apply()
+            .desired(vec![Setting::DownloadProtocol("IPv6".to_string())])
+            .send()
+            .await;
+
+        assert_eq!(
+            res.unwrap(),
+            vec![Setting::DownloadProtocol("IPv6".to_string())]
+        );
+    })
+    .await
+}
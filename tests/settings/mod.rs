@@ -1,6 +1,7 @@
+mod apply;
 mod change_password;
 mod convert_points;
+mod delete_avatar;
 mod get;
 mod set_avatar;
 mod update;
-mod delete_avatar;
\ No newline at end of file
@@ -78,3 +78,34 @@ async fn should_fail_to_get_media_info() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn should_get_media_info_with_unknown_type() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<models::streaming::MediaInfo>(
+            "tests/.resources/streaming/media_infos_unknown.json",
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/streaming/mediaInfos/ABCDEFGHIJKLMNO"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .streaming()
+            .media_info()
+            .id("ABCDEFGHIJKLMNO".to_string())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res.type_,
+            models::streaming::MediaType::Unknown("podcast".to_string())
+        );
+        assert_eq!(serde_json::to_value(&res).unwrap()["type"], "podcast");
+    })
+    .await
+}
@@ -1,2 +1,5 @@
 mod media_info;
+mod stream;
+mod template;
+mod tracks;
 mod transcode;
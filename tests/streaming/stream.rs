@@ -0,0 +1,147 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::{
+        self,
+        streaming::{StreamFormat, StreamQuality},
+    },
+    streams::StreamPreference,
+    DebridError, Error,
+};
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+fn transcode() -> models::streaming::Transcode {
+    resource("tests/.resources/streaming/transcode_qualities.json")
+}
+
+fn movie() -> models::streaming::MediaInfo {
+    resource("tests/.resources/streaming/media_infos_movie.json")
+}
+
+#[test]
+fn should_select_original_quality_by_default() {
+    let stream = StreamPreference::default().select(&transcode()).unwrap();
+
+    assert_eq!(stream.format, StreamFormat::Hls);
+    assert_eq!(stream.quality, StreamQuality::Original);
+    assert!(stream.url.ends_with("/full.m3u8"));
+}
+
+#[test]
+fn should_select_best_quality_under_max_height() {
+    let preference = StreamPreference::builder().max_height(800).build();
+    let stream = preference.select(&transcode()).unwrap();
+
+    assert_eq!(stream.format, StreamFormat::Hls);
+    assert_eq!(stream.quality, StreamQuality::Height(720));
+    assert!(stream.url.ends_with("/720.m3u8"));
+}
+
+#[test]
+fn should_fall_back_to_next_format() {
+    let preference = StreamPreference::builder()
+        .formats(vec![StreamFormat::WebM, StreamFormat::LiveMp4])
+        .max_height(720)
+        .build();
+    let stream = preference.select(&transcode()).unwrap();
+
+    // WebM has no stream, and the original height of liveMP4 is unknown
+    assert_eq!(stream.format, StreamFormat::LiveMp4);
+    assert_eq!(stream.quality, StreamQuality::Original);
+
+    let preference = StreamPreference::builder()
+        .formats(vec![StreamFormat::WebM])
+        .build();
+
+    assert_eq!(preference.select(&transcode()), None);
+}
+
+#[test]
+fn should_select_from_media_info() {
+    let stream = StreamPreference::builder()
+        .formats(vec![StreamFormat::Dash])
+        .build()
+        .select_media(&movie())
+        .unwrap();
+
+    assert_eq!(stream.quality, StreamQuality::Original);
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/eng1/none/aac/full.mpd"
+    );
+
+    // The original is 1080p, above the max height
+    let stream = StreamPreference::builder()
+        .max_height(720)
+        .build()
+        .select_media(&movie())
+        .unwrap();
+
+    assert_eq!(stream.quality, StreamQuality::Height(720));
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/eng1/none/aac/720.m3u8"
+    );
+
+    let mut info = movie();
+    info.base_url = None;
+
+    assert_eq!(StreamPreference::default().select_media(&info), None);
+}
+
+#[tokio::test]
+async fn should_get_stream() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/streaming/transcode/ABCDEFGHIJKLMNO"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(transcode()))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let preference = StreamPreference::builder()
+            .formats(vec![StreamFormat::Dash])
+            .max_height(1080)
+            .build();
+
+        let res = debrid
+            .streaming()
+            .stream()
+            .id("ABCDEFGHIJKLMNO".to_string())
+            .preference(&preference)
+            .send()
+            .await;
+
+        let stream = res.unwrap().unwrap();
+        assert_eq!(stream.format, StreamFormat::Dash);
+        assert_eq!(stream.quality, StreamQuality::Height(720));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn should_fail_to_get_stream() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/streaming/transcode/ABCDEFGHIJKLMNO"))
+            .respond_with(
+                ResponseTemplate::new(401).set_body_json(models::error::Error {
+                    code: 8,
+                    message: "bad_token".to_string(),
+                }),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .streaming()
+            .stream()
+            .id("ABCDEFGHIJKLMNO".to_string())
+            .send()
+            .await;
+
+        assert!(res.is_err_and(|e| matches!(e, Error::Debrid(DebridError::BadToken))));
+    })
+    .await;
+}
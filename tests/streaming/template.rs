@@ -0,0 +1,98 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::streaming::{MediaInfo, StreamFormat, StreamQuality},
+    streams::StreamTemplate,
+    Error,
+};
+
+fn movie() -> MediaInfo {
+    resource("tests/.resources/streaming/media_infos_movie.json")
+}
+
+#[test]
+fn should_build_url_with_base_defaults() {
+    let info = movie();
+    let stream = StreamTemplate::new(&info)
+        .url()
+        .format(StreamFormat::Dash)
+        .build()
+        .unwrap();
+
+    assert_eq!(stream.quality, StreamQuality::Original);
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/eng1/none/aac/full.mpd"
+    );
+}
+
+#[test]
+fn should_build_url_with_tracks_and_quality() {
+    let info = movie();
+    let template = StreamTemplate::new(&info);
+
+    let stream = template
+        .url()
+        .format(StreamFormat::Hls)
+        .quality(StreamQuality::Height(720))
+        .audio("fre1")
+        .subtitles("eng1")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/fre1/eng1/aac/720.m3u8"
+    );
+
+    // Tracks can be picked by stream ID too
+    let stream = template
+        .url()
+        .format(StreamFormat::WebM)
+        .audio("0:2")
+        .subtitles("0:5")
+        .audio_codec("ac3")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/eng2/fre1/ac3/full.webm"
+    );
+}
+
+#[test]
+fn should_refuse_unavailable_choices() {
+    let info = movie();
+    let template = StreamTemplate::new(&info);
+
+    let res = template
+        .url()
+        .format(StreamFormat::Hls)
+        .quality(StreamQuality::Height(1080))
+        .build();
+    assert!(res.is_err_and(|e| matches!(e, Error::UnavailableStream(_))));
+
+    let res = template
+        .url()
+        .format(StreamFormat::Hls)
+        .audio("ger1")
+        .build();
+    assert!(res.is_err_and(|e| matches!(e, Error::UnavailableStream(_))));
+
+    let res = template
+        .url()
+        .format(StreamFormat::Hls)
+        .subtitles("0:1")
+        .build();
+    assert!(res.is_err_and(|e| matches!(e, Error::UnavailableStream(_))));
+
+    let mut info = movie();
+    info.model_url = None;
+
+    let res = StreamTemplate::new(&info)
+        .url()
+        .format(StreamFormat::Hls)
+        .build();
+    assert!(res.is_err_and(|e| matches!(e, Error::UnavailableStream(_))));
+}
@@ -0,0 +1,110 @@
+use crate::mocked::*;
+
+use debrid::{
+    models::streaming::{MediaInfo, StreamFormat},
+    streams::{StreamTemplate, SubtitleMode, TrackSelection, TrackSelector},
+};
+
+fn movie() -> MediaInfo {
+    resource("tests/.resources/streaming/media_infos_movie.json")
+}
+
+#[test]
+fn should_select_audio_by_language_then_channels() {
+    let info = movie();
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["en".to_string()])
+        .build();
+    assert_eq!(
+        selector.select(&info.details),
+        TrackSelection {
+            audio: Some("eng2".to_string()),
+            subtitles: None,
+        }
+    );
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["fra".to_string(), "eng".to_string()])
+        .build();
+    assert_eq!(selector.audio(&info.details.audio), Some("fre1"));
+
+    // No preferred language, the best track wins
+    let selector = TrackSelector::builder()
+        .languages(vec!["ja".to_string()])
+        .build();
+    assert_eq!(selector.audio(&info.details.audio), Some("eng2"));
+}
+
+#[test]
+fn should_select_subtitles_by_mode_and_type() {
+    let info = movie();
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["ja".to_string()])
+        .subtitle_languages(vec!["French".to_string(), "en".to_string()])
+        .subtitles(SubtitleMode::Default)
+        .subtitle_types(vec!["SRT".to_string()])
+        .build();
+    assert_eq!(selector.subtitles(&info.details.subtitles), Some("fre2"));
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["en".to_string()])
+        .subtitles(SubtitleMode::Default)
+        .build();
+    assert_eq!(selector.subtitles(&info.details.subtitles), Some("eng1"));
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["en".to_string()])
+        .subtitles(SubtitleMode::Forced)
+        .build();
+    assert_eq!(selector.subtitles(&info.details.subtitles), Some("eng2"));
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["fr".to_string()])
+        .subtitles(SubtitleMode::Forced)
+        .build();
+    assert_eq!(selector.subtitles(&info.details.subtitles), None);
+
+    let selector = TrackSelector::builder()
+        .languages(vec!["en".to_string()])
+        .build();
+    assert_eq!(selector.subtitles(&info.details.subtitles), None);
+}
+
+#[test]
+fn should_feed_selection_into_template() {
+    let info = movie();
+    let tracks = TrackSelector::builder()
+        .languages(vec!["fr".to_string()])
+        .subtitle_languages(vec!["en".to_string()])
+        .subtitles(SubtitleMode::Forced)
+        .build()
+        .select(&info.details);
+
+    let stream = StreamTemplate::new(&info)
+        .url()
+        .format(StreamFormat::Hls)
+        .tracks(&tracks)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/fre1/eng2/aac/full.m3u8"
+    );
+
+    // Explicit tracks win over the selection
+    let stream = StreamTemplate::new(&info)
+        .url()
+        .format(StreamFormat::Hls)
+        .tracks(&tracks)
+        .audio("eng1")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        stream.url,
+        "https://example.com/t/ABCDEFGHIJKLMNO/eng1/eng2/aac/full.m3u8"
+    );
+}
@@ -21,3 +21,29 @@ async fn should_get_torrents() {
     })
     .await
 }
+
+#[tokio::test]
+async fn should_get_torrents_with_unknown_status() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<Vec<models::torrents::Torrent>>(
+            "tests/.resources/torrents/index_unknown.json",
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/torrents"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid.torrents().get().send().await.unwrap();
+
+        assert_eq!(res[0].status, models::torrents::TorrentStatus::Downloaded);
+        assert_eq!(
+            res[1].status,
+            models::torrents::TorrentStatus::Unknown("seeding".to_string())
+        );
+        assert_eq!(serde_json::to_value(&res[1]).unwrap()["status"], "seeding");
+    })
+    .await
+}
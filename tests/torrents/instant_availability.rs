@@ -8,7 +8,7 @@ use wiremock::{matchers, Mock, ResponseTemplate};
 #[tokio::test]
 async fn should_get_instant_availability() {
     mocked(|mock, debrid| async move {
-        let r = resource::<HashMap<String, models::torrents::InstantAvailability>>(
+        let r = resource::<HashMap<models::ids::InfoHash, models::torrents::InstantAvailability>>(
             "tests/.resources/torrents/instant_availability.json",
         );
 
@@ -24,7 +24,7 @@ async fn should_get_instant_availability() {
         let res = debrid
             .torrents()
             .instant_availability()
-            .hashes(vec!["c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab".to_string()])
+            .hashes(vec!["c39fe3eefbdb62da9c27eb6398ff4a7d2e26e7ab".into()])
             .send()
             .await;
 
@@ -25,3 +25,44 @@ async fn should_get_traffic() {
     })
     .await
 }
+
+#[tokio::test]
+async fn should_get_traffic_with_unknown_values() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<HashMap<String, models::traffic::Traffic>>(
+            "tests/.resources/traffic/index_unknown.json",
+        );
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid.traffic().get().send().await.unwrap();
+
+        assert!(matches!(
+            &res["example0.com"],
+            models::traffic::Traffic::Gigabytes {
+                reset: Some(models::traffic::Reset::Unknown(reset)),
+                ..
+            } if reset == "yearly"
+        ));
+        assert!(matches!(
+            &res["example1.com"],
+            models::traffic::Traffic::Unknown {
+                type_,
+                left: Some(3600),
+                reset: Some(models::traffic::Reset::Daily),
+                ..
+            } if type_ == "seconds"
+        ));
+
+        let value = serde_json::to_value(&res).unwrap();
+        assert_eq!(value["example0.com"]["reset"], "yearly");
+        assert_eq!(value["example1.com"]["type"], "seconds");
+        assert_eq!(value["example1.com"]["left"], 3600);
+    })
+    .await
+}
@@ -1,2 +1,4 @@
 mod details;
 mod get;
+mod quotas;
+mod report;
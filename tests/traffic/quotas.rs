@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use crate::mocked::*;
+
+use chrono::{Days, Utc};
+use debrid::{models, usage::QuotaUnit};
+use serde_json::json;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+const GIGABYTE: u64 = 1024 * 1024 * 1024;
+
+async fn mount_traffic(mock: &MockServer) {
+    let r = resource::<HashMap<String, models::traffic::Traffic>>(
+        "tests/.resources/traffic/index_unknown.json",
+    );
+
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/traffic"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(r))
+        .expect(1)
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn should_get_normalized_quotas() {
+    mocked(|mock, debrid| async move {
+        mount_traffic(&mock).await;
+
+        let quotas = debrid.traffic().quotas().send().await.unwrap();
+
+        assert_eq!(quotas[0].host, "example0.com");
+        assert_eq!(quotas[0].unit, QuotaUnit::Bytes);
+        assert_eq!(quotas[0].remaining, 5 * GIGABYTE);
+        assert_eq!(quotas[0].limit, Some(5 * GIGABYTE));
+        assert_eq!(quotas[0].percent_used(), Some(0.0));
+
+        assert_eq!(quotas[1].unit, QuotaUnit::Other("seconds".to_string()));
+        assert_eq!(quotas[1].percent_used(), Some(50.0));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_forecast_quota_exhaustion() {
+    mocked(|mock, debrid| async move {
+        mount_traffic(&mock).await;
+
+        let today = Utc::now().date_naive();
+        let yesterday = today - Days::new(1);
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/traffic/details"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                yesterday.format("%Y-%m-%d").to_string(): {
+                    "host": { "example0.com": GIGABYTE },
+                    "bytes": GIGABYTE
+                },
+                today.format("%Y-%m-%d").to_string(): {
+                    "host": { "example0.com": GIGABYTE },
+                    "bytes": GIGABYTE
+                }
+            })))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let forecasts = debrid.traffic().forecast().send().await.unwrap();
+
+        assert_eq!(forecasts[0].daily_average, GIGABYTE as f64);
+        assert_eq!(forecasts[0].exhausted_on, Some(today + Days::new(5)));
+        assert_eq!(forecasts[1].exhausted_on, None);
+    })
+    .await
+}
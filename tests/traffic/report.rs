@@ -0,0 +1,84 @@
+use crate::mocked::*;
+
+use chrono::NaiveDate;
+use debrid::usage::Period;
+use serde_json::{json, Value};
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+fn day(host: &str, bytes: u64) -> Value {
+    json!({ "host": { host: bytes }, "bytes": bytes })
+}
+
+async fn mount_window(mock: &MockServer, start: &str, end: &str, body: Value) {
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/traffic/details"))
+        .and(matchers::query_param("start", start))
+        .and(matchers::query_param("end", end))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(1)
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn should_merge_report_windows() {
+    mocked(|mock, debrid| async move {
+        mount_window(
+            &mock,
+            "2024-08-01",
+            "2024-08-31",
+            json!({
+                "2024-08-05": day("example0.com", 100),
+                "2024-08-31": day("example1.com", 10),
+            }),
+        )
+        .await;
+
+        mount_window(
+            &mock,
+            "2024-09-01",
+            "2024-09-27",
+            json!({
+                "2024-07-31": day("example0.com", 1000),
+                "2024-09-01": day("example0.com", 200),
+                "2024-09-27": day("example0.com", 300),
+            }),
+        )
+        .await;
+
+        let report = debrid
+            .traffic()
+            .report()
+            .start(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap())
+            .end(NaiveDate::from_ymd_opt(2024, 9, 27).unwrap())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(report.days.len(), 4);
+        assert_eq!(report.total(), 610);
+        assert_eq!(report.by_host()["example0.com"], 600);
+
+        assert_eq!(
+            report.to_csv(Period::Month),
+            "period,host,bytes\n\
+             2024-08,example0.com,100\n\
+             2024-08,example1.com,10\n\
+             2024-09,example0.com,500\n"
+        );
+
+        // 2024-08-31 and 2024-09-01 are in the same ISO week
+        let weeks = report.aggregate(Period::Week);
+        assert_eq!(weeks.len(), 4);
+        assert_eq!(weeks[1].period, "2024-W35");
+        assert_eq!(weeks[1].bytes, 200);
+        assert_eq!(weeks[2].period, "2024-W35");
+
+        let json: Value = serde_json::from_str(&report.to_json(Period::Day).unwrap()).unwrap();
+        assert_eq!(
+            json[0],
+            json!({ "period": "2024-08-05", "host": "example0.com", "bytes": 100 })
+        );
+    })
+    .await
+}
@@ -26,3 +26,21 @@ async fn should_post_unrestrict_check() {
     })
     .await
 }
+
+#[test]
+fn should_parse_check_qualities() {
+    let check = resource::<models::unrestrict::Check>("tests/.resources/unrestrict/check.json");
+
+    assert_eq!(
+        check.title(),
+        "Rick Astley - Never Gonna Give You Up (Official Music Video)"
+    );
+    assert_eq!(
+        check
+            .resolutions()
+            .iter()
+            .map(|r| r.height)
+            .collect::<Vec<_>>(),
+        vec![1080, 720, 480, 360, 240]
+    );
+}
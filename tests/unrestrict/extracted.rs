@@ -0,0 +1,78 @@
+use crate::mocked::*;
+
+use debrid::{
+    hosts::{ExtractedLink, LinkKind},
+    models, DebridError, Error,
+};
+use serde_json::json;
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn should_unrestrict_extracted_folder() {
+    mocked(|mock, debrid| async move {
+        let folder = resource::<Vec<String>>("tests/.resources/unrestrict/folder.json");
+        let link = resource::<models::unrestrict::Link>("tests/.resources/unrestrict/link.json");
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/folder"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&folder))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(link))
+            .expect(folder.len() as u64)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .extracted()
+            .link(ExtractedLink {
+                url: "https://drive.google.com/drive/folders/123ABC456def".to_string(),
+                kind: LinkKind::Folder,
+                host: None,
+            })
+            .send()
+            .await;
+
+        assert_eq!(res.len(), folder.len());
+        assert!(res.iter().all(Result::is_ok));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_to_unrestrict_extracted_folder() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/folder"))
+            .respond_with(
+                ResponseTemplate::new(503)
+                    .set_body_json(json!({ "error": "hoster_unavailable", "error_code": 19 })),
+            )
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .extracted()
+            .link(ExtractedLink {
+                url: "https://drive.google.com/drive/folders/123ABC456def".to_string(),
+                kind: LinkKind::Folder,
+                host: None,
+            })
+            .send()
+            .await;
+
+        assert_eq!(res.len(), 1);
+        assert!(matches!(
+            res[0],
+            Err(Error::Debrid(DebridError::HosterTemporarilyUnavailable))
+        ));
+    })
+    .await
+}
@@ -0,0 +1,126 @@
+use crate::mocked::*;
+
+use debrid::{models, usage::QuotaGuard, Error};
+use serde_json::json;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+async fn mount_check(mock: &MockServer, filesize: u64) {
+    let mut check = resource::<models::unrestrict::Check>("tests/.resources/unrestrict/check.json");
+    check.host = "example.com".to_string();
+    check.filesize = filesize;
+
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/unrestrict/check"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(check))
+        .mount(mock)
+        .await;
+}
+
+async fn mount_traffic(mock: &MockServer, traffic: serde_json::Value) {
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/traffic"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "example.com": traffic })))
+        .expect(1)
+        .mount(mock)
+        .await;
+}
+
+async fn mount_link(mock: &MockServer, times: u64) {
+    let link = resource::<models::unrestrict::Link>("tests/.resources/unrestrict/link.json");
+
+    Mock::given(matchers::method("POST"))
+        .and(matchers::path("/unrestrict/link"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(link))
+        .expect(times)
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn should_refuse_links_exceeding_bytes_left() {
+    mocked(|mock, debrid| async move {
+        mount_check(&mock, 100).await;
+        mount_traffic(&mock, json!({ "left": 1000, "bytes": 0, "type": "bytes" })).await;
+        mount_link(&mock, 1).await;
+
+        let guard = QuotaGuard::builder().margin(850).build();
+        let unrestrict = debrid.unrestrict();
+
+        let res = unrestrict
+            .guarded()
+            .link("https://example.com/abcdefghijkl".to_string())
+            .guard(&guard)
+            .send()
+            .await;
+        assert!(res.is_ok());
+
+        // The first unrestrict is deducted from the cached traffic
+        let res = unrestrict
+            .guarded()
+            .link("https://example.com/abcdefghijkl".to_string())
+            .guard(&guard)
+            .send()
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::QuotaWouldBeExceeded {
+                needed: 100,
+                margin: 850,
+                left: 900,
+                ..
+            })
+        ));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_refuse_links_without_links_left() {
+    mocked(|mock, debrid| async move {
+        mount_check(&mock, 0).await;
+        mount_traffic(&mock, json!({ "left": 0, "links": 5, "type": "links" })).await;
+        mount_link(&mock, 0).await;
+
+        let guard = QuotaGuard::builder().build();
+
+        let res = debrid
+            .unrestrict()
+            .guarded()
+            .link("https://example.com/abcdefghijkl".to_string())
+            .guard(&guard)
+            .send()
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(Error::QuotaWouldBeExceeded {
+                needed: 1,
+                left: 0,
+                ..
+            })
+        ));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_guard_unknown_sizes() {
+    mocked(|mock, debrid| async move {
+        mount_check(&mock, 0).await;
+        mount_traffic(&mock, json!({ "left": 0, "bytes": 0, "type": "bytes" })).await;
+        mount_link(&mock, 1).await;
+
+        let guard = QuotaGuard::builder().build();
+
+        let res = debrid
+            .unrestrict()
+            .guarded()
+            .link("https://example.com/abcdefghijkl".to_string())
+            .guard(&guard)
+            .send()
+            .await;
+
+        assert!(res.is_ok());
+    })
+    .await
+}
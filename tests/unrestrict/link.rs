@@ -1,6 +1,6 @@
 use crate::mocked::*;
 
-use debrid::models;
+use debrid::{models, quality::QualityPreference};
 use wiremock::{matchers, Mock, ResponseTemplate};
 
 #[tokio::test]
@@ -25,4 +25,23 @@ async fn should_post_unrestrict_link() {
         assert!(res.is_ok());
     })
     .await
-}
\ No newline at end of file
+}
+
+#[test]
+fn should_select_link_quality() {
+    let link = resource::<models::unrestrict::Link>("tests/.resources/unrestrict/link.json");
+
+    let variant = QualityPreference::builder()
+        .max_height(720)
+        .build()
+        .select(&link)
+        .unwrap();
+    assert_eq!(variant.quality(), Some("720p"));
+
+    let variant = QualityPreference::builder()
+        .audio_only(true)
+        .build()
+        .select(&link)
+        .unwrap();
+    assert_eq!(variant.mime_type(), Some("audio/mpeg"));
+}
@@ -0,0 +1,160 @@
+use crate::mocked::*;
+
+use debrid::{models, DebridError, Error};
+use wiremock::{matchers, Mock, ResponseTemplate};
+
+fn link(name: &str) -> models::unrestrict::Link {
+    let mut link = resource::<models::unrestrict::Link>("tests/.resources/unrestrict/link.json");
+    link.link = format!("https://example.com/{}", name);
+    link.filename = name.to_string();
+    link
+}
+
+fn links(names: &[&str]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| format!("https://example.com/{}", name))
+        .collect()
+}
+
+#[tokio::test]
+async fn should_unrestrict_links_in_order() {
+    mocked(|mock, debrid| async move {
+        for name in ["first", "third"] {
+            Mock::given(matchers::method("POST"))
+                .and(matchers::path("/unrestrict/link"))
+                .and(matchers::body_string_contains(name))
+                .respond_with(ResponseTemplate::new(200).set_body_json(link(name)))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .and(matchers::body_string_contains("second"))
+            .respond_with(error(24))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .links()
+            .links(links(&["first", "second", "third"]))
+            .concurrency(2)
+            .send()
+            .await;
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].as_ref().unwrap().filename, "first");
+        assert!(matches!(
+            res[1],
+            Err(Error::Debrid(DebridError::FileUnavailable))
+        ));
+        assert_eq!(res[2].as_ref().unwrap().filename, "third");
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_skip_unsupported_links() {
+    mocked(|mock, debrid| async move {
+        let mut check =
+            resource::<models::unrestrict::Check>("tests/.resources/unrestrict/check.json");
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/check"))
+            .and(matchers::body_string_contains("supported"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(check.clone()))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        check.supported = false;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/check"))
+            .and(matchers::body_string_contains("unknown"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(check))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(link("supported")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .links()
+            .links(links(&["supported", "unknown"]))
+            .check(true)
+            .send()
+            .await;
+
+        assert!(res[0].is_ok());
+        assert!(matches!(
+            res[1],
+            Err(Error::Debrid(DebridError::UnsupportedHoster))
+        ));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_retry_rate_limited_links() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(34))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(link("first")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .links()
+            .links(links(&["first"]))
+            .send()
+            .await;
+
+        assert!(res[0].is_ok());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_give_up_after_retries() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(5))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid
+            .unrestrict()
+            .links()
+            .links(links(&["first"]))
+            .retries(0)
+            .send()
+            .await;
+
+        assert!(matches!(res[0], Err(Error::Debrid(DebridError::SlowDown))));
+    })
+    .await
+}
@@ -1,5 +1,9 @@
 mod check;
 mod container_file;
 mod container_link;
+mod extracted;
 mod folder;
-mod link;
\ No newline at end of file
+mod guarded;
+mod link;
+mod links;
+mod resolve;
@@ -0,0 +1,150 @@
+use crate::mocked::*;
+
+use debrid::{hosts::Passwords, models, DebridError, Error};
+use regex::Regex;
+use serde_json::json;
+use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
+
+const LINK: &str = "https://dl.example.com/abcdefghijkl";
+
+fn link() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(resource::<models::unrestrict::Link>(
+        "tests/.resources/unrestrict/link.json",
+    ))
+}
+
+async fn mount_remote_traffic(mock: &MockServer, left: u64) {
+    Mock::given(matchers::method("GET"))
+        .and(matchers::path("/traffic"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "remote": { "left": left, "bytes": 0, "links": 0, "limit": 0, "type": "bytes", "extra": 0, "reset": "monthly" }
+        })))
+        .expect(1)
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn should_try_passwords_in_order() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .and(matchers::body_string_contains("password=secret"))
+            .respond_with(link())
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(13))
+            .with_priority(10)
+            .expect(2)
+            .mount(&mock)
+            .await;
+
+        let passwords = Passwords::new()
+            .host("example.com", "wrong")
+            .host("other.com", "unused")
+            .pattern(Regex::new("/abcdef").unwrap(), "secret");
+
+        let res = debrid
+            .unrestrict()
+            .resolve()
+            .link(LINK.to_string())
+            .passwords(&passwords)
+            .send()
+            .await;
+
+        assert!(res.is_ok());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_fail_without_matching_password() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(13))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let passwords = Passwords::new().host("other.com", "unused");
+
+        let res = debrid
+            .unrestrict()
+            .resolve()
+            .link(LINK.to_string())
+            .passwords(&passwords)
+            .send()
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(Error::Debrid(DebridError::InvalidPassword))
+        ));
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_retry_with_remote_traffic() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .and(matchers::body_string_contains("remote=1"))
+            .respond_with(link())
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(18))
+            .with_priority(10)
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        mount_remote_traffic(&mock, 1024).await;
+
+        let res = debrid
+            .unrestrict()
+            .resolve()
+            .link(LINK.to_string())
+            .send()
+            .await;
+
+        assert!(res.is_ok());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn should_not_retry_without_remote_traffic_left() {
+    mocked(|mock, debrid| async move {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/unrestrict/link"))
+            .respond_with(error(23))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        mount_remote_traffic(&mock, 0).await;
+
+        let res = debrid
+            .unrestrict()
+            .resolve()
+            .link(LINK.to_string())
+            .send()
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(Error::Debrid(DebridError::TrafficExhausted))
+        ));
+    })
+    .await
+}
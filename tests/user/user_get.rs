@@ -32,3 +32,26 @@ async fn should_fail_to_get_user() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn should_get_user_with_unknown_type() {
+    mocked(|mock, debrid| async move {
+        let r = resource::<models::user::User>("tests/.resources/user/index_unknown.json");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/user"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(r))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let res = debrid.user().get().send().await.unwrap();
+
+        assert_eq!(
+            res.type_,
+            models::user::Type::Unknown("lifetime".to_string())
+        );
+        assert_eq!(serde_json::to_value(&res).unwrap()["type"], "lifetime");
+    })
+    .await
+}